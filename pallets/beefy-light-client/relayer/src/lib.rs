@@ -0,0 +1,332 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Building the arguments of the BEEFY light client calls from the BEEFY justifications,
+//! so the relayers don't need to reimplement the proofs format.
+//!
+//! Commitment is submitted in two phases:
+//! 1. `submit_initial_commitment` with [`CommitmentProofBuilder::initial_commitment`].
+//! 2. `submit_signature_commitment_with_leaf_proof` with
+//!    [`CommitmentProofBuilder::signature_commitment`], after the ticket randomness is pinned
+//!    and the random bitfield is available via `BeefyLightClientAPI::get_random_bitfield`
+//!    or [`random_bitfield`].
+//!
+//! Messages are verified with [`message_proof`] for the MMR leaf of the block and its MMR proof.
+
+use beefy_light_client::{
+    random_n_bits_with_prior_check, SubstrateBridgeMessageProof, VersionedMessageProof,
+};
+use bridge_common::beefy_types::{
+    BeefyMMRLeaf, Commitment, EthAddress, ValidatorProof, ValidatorSet,
+};
+use bridge_common::bitfield::BitField;
+use bridge_common::simplified_proof::{
+    convert_to_simplified_mmr_proof, hasher, LeafProof, MMRLeafProof,
+};
+use bridge_types::types::AuxiliaryDigest;
+use codec::Encode;
+use sp_core::H256;
+use sp_io::hashing::keccak_256;
+use sp_runtime::traits::{Convert, Hash, Keccak256};
+
+pub use beefy_light_client::required_number_of_signatures;
+
+#[cfg(test)]
+mod tests;
+
+pub type AuthorityId = sp_beefy::crypto::AuthorityId;
+pub type SignedCommitment = sp_beefy::SignedCommitment<u32, sp_beefy::crypto::Signature>;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Error {
+    #[error("Authority {0} can't be converted to the Ethereum address")]
+    InvalidAuthority(usize),
+    #[error(
+        "Commitment has {signatures} signatures, but validator set has {authorities} authorities"
+    )]
+    AuthoritiesMismatch {
+        signatures: usize,
+        authorities: usize,
+    },
+    #[error("Validator {0} signature is required but missing")]
+    MissingSignature(usize),
+    #[error("Random bitfield has {0} bits, expected one bit per validator")]
+    InvalidBitfieldLength(usize),
+    #[error("Commitment payload has no MMR root")]
+    MMRPayloadNotFound,
+    #[error("Leaf proof doesn't match the commitment MMR root")]
+    InvalidMMRProof,
+    #[error("Digest doesn't match the leaf digest hash")]
+    InvalidDigestHash,
+    #[error("MMR proof is generated for {0} leaves, expected one leaf")]
+    InvalidLeafCount(usize),
+    #[error("MMR proof items don't match the leaf index and the leaf count")]
+    MalformedMMRProof,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Validator set as it is stored in the light client, with the addresses of the validators.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthoritySet {
+    pub id: u64,
+    pub addresses: Vec<EthAddress>,
+}
+
+impl AuthoritySet {
+    /// Convert the BEEFY authorities to the Ethereum addresses the same way as `pallet_beefy_mmr`.
+    pub fn new(id: u64, authorities: &[AuthorityId]) -> Result<Self> {
+        let addresses = authorities
+            .iter()
+            .enumerate()
+            .map(|(i, authority)| {
+                let address = pallet_beefy_mmr::BeefyEcdsaToEthereum::convert(authority.clone());
+                if address.len() != 20 {
+                    return Err(Error::InvalidAuthority(i));
+                }
+                Ok(EthAddress::from_slice(&address))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { id, addresses })
+    }
+
+    pub fn len(&self) -> usize {
+        self.addresses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.addresses.is_empty()
+    }
+
+    pub fn root(&self) -> H256 {
+        beefy_merkle_tree::merkle_root::<Keccak256, _>(self.addresses.clone())
+    }
+
+    /// Validator set to compare with `BeefyLightClientAPI::get_current_validator_set`.
+    pub fn validator_set(&self) -> ValidatorSet {
+        ValidatorSet {
+            id: self.id,
+            len: self.len() as u32,
+            root: self.root(),
+        }
+    }
+
+    /// Merkle proof of the validator address in the validator set root.
+    pub fn address_proof(&self, position: usize) -> Vec<H256> {
+        beefy_merkle_tree::merkle_proof::<Keccak256, _, _>(self.addresses.clone(), position).proof
+    }
+}
+
+/// Arguments of `submit_initial_commitment`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InitialCommitment {
+    pub commitment_hash: H256,
+    pub validator_set_id: u64,
+    pub validator_claims_bitfield: BitField,
+}
+
+/// Arguments of `submit_signature_commitment_with_leaf_proof`, or of
+/// `submit_signature_commitment` if `proof` is [`LeafProof::Simplified`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureCommitment {
+    pub commitment: Commitment,
+    pub validator_proof: ValidatorProof,
+    pub latest_mmr_leaf: BeefyMMRLeaf,
+    pub proof: LeafProof<H256>,
+}
+
+/// Builds the light client proofs for the signed commitment.
+#[derive(Debug, Clone)]
+pub struct CommitmentProofBuilder {
+    signed_commitment: SignedCommitment,
+    authorities: AuthoritySet,
+}
+
+impl CommitmentProofBuilder {
+    /// `authorities` are the authorities of the set which signed the commitment.
+    pub fn new(signed_commitment: SignedCommitment, authorities: &[AuthorityId]) -> Result<Self> {
+        if signed_commitment.signatures.len() != authorities.len() {
+            return Err(Error::AuthoritiesMismatch {
+                signatures: signed_commitment.signatures.len(),
+                authorities: authorities.len(),
+            });
+        }
+        let authorities =
+            AuthoritySet::new(signed_commitment.commitment.validator_set_id, authorities)?;
+        Ok(Self {
+            signed_commitment,
+            authorities,
+        })
+    }
+
+    pub fn commitment(&self) -> &Commitment {
+        &self.signed_commitment.commitment
+    }
+
+    pub fn commitment_hash(&self) -> H256 {
+        Keccak256::hash_of(&self.signed_commitment.commitment)
+    }
+
+    pub fn authorities(&self) -> &AuthoritySet {
+        &self.authorities
+    }
+
+    /// Bitfield of all validators which signed the commitment.
+    pub fn validator_claims_bitfield(&self) -> BitField {
+        let bits_to_set = self
+            .signed_commitment
+            .signatures
+            .iter()
+            .enumerate()
+            .filter_map(|(i, signature)| signature.as_ref().map(|_| i as u32))
+            .collect::<Vec<_>>();
+        BitField::create_bitfield(&bits_to_set, self.authorities.len())
+    }
+
+    pub fn initial_commitment(&self) -> InitialCommitment {
+        InitialCommitment {
+            commitment_hash: self.commitment_hash(),
+            validator_set_id: self.signed_commitment.commitment.validator_set_id,
+            validator_claims_bitfield: self.validator_claims_bitfield(),
+        }
+    }
+
+    /// Validator proof with the signatures of the validators set in `random_bitfield`.
+    pub fn validator_proof(&self, random_bitfield: &BitField) -> Result<ValidatorProof> {
+        if random_bitfield.len() != self.authorities.len() {
+            return Err(Error::InvalidBitfieldLength(random_bitfield.len()));
+        }
+        let mut proof = ValidatorProof {
+            validator_claims_bitfield: self.validator_claims_bitfield(),
+            signatures: vec![],
+            positions: vec![],
+            public_keys: vec![],
+            public_key_merkle_proofs: vec![],
+        };
+        for position in (0..random_bitfield.len()).filter(|i| random_bitfield.is_set(*i)) {
+            let signature = self.signed_commitment.signatures[position]
+                .as_ref()
+                .ok_or(Error::MissingSignature(position))?;
+            let mut signature = AsRef::<[u8]>::as_ref(signature).to_vec();
+            // Light client expects Ethereum recovery id
+            signature[64] += 27;
+            proof.signatures.push(signature);
+            proof.positions.push(position as u128);
+            proof.public_keys.push(self.authorities.addresses[position]);
+            proof
+                .public_key_merkle_proofs
+                .push(self.authorities.address_proof(position));
+        }
+        Ok(proof)
+    }
+
+    /// Validator proof with the signatures of all validators, as `report_equivocation` expects.
+    pub fn full_validator_proof(&self) -> Result<ValidatorProof> {
+        self.validator_proof(&self.validator_claims_bitfield())
+    }
+
+    /// Arguments of `submit_signature_commitment`. `latest_mmr_leaf` should be included
+    /// in the MMR root from the commitment payload.
+    pub fn signature_commitment(
+        &self,
+        random_bitfield: &BitField,
+        latest_mmr_leaf: BeefyMMRLeaf,
+        proof: impl Into<LeafProof<H256>>,
+    ) -> Result<SignatureCommitment> {
+        let proof = proof.into();
+        let mmr_root = self
+            .signed_commitment
+            .commitment
+            .payload
+            .get_decoded::<H256>(&sp_beefy::known_payloads::MMR_ROOT_ID)
+            .ok_or(Error::MMRPayloadNotFound)?;
+        let leaf_hash = Keccak256::hash_of(&latest_mmr_leaf);
+        if !bridge_common::simplified_proof::verify_leaf_proof(mmr_root, leaf_hash, &proof) {
+            return Err(Error::InvalidMMRProof);
+        }
+        Ok(SignatureCommitment {
+            commitment: self.signed_commitment.commitment.clone(),
+            validator_proof: self.validator_proof(random_bitfield)?,
+            latest_mmr_leaf,
+            proof,
+        })
+    }
+}
+
+/// Random bitfield for the ticket with the pinned randomness `seed` of the runtime `Hash` type,
+/// the same as `BeefyLightClientAPI::get_random_bitfield` returns.
+pub fn random_bitfield<Hash: Encode>(
+    seed: Hash,
+    commitment_hash: H256,
+    prior: &BitField,
+) -> BitField {
+    let length = prior.len() as u32;
+    random_n_bits_with_prior_check(
+        seed,
+        commitment_hash,
+        prior,
+        required_number_of_signatures(length),
+        length,
+    )
+}
+
+/// Proof for `Verifier::verify` of the messages committed in the `digest` of the leaf.
+/// `proof` is the MMR proof of the leaf, as returned by the `mmr_generateProof` RPC.
+pub fn message_proof(
+    leaf: BeefyMMRLeaf,
+    proof: sp_mmr_primitives::Proof<H256>,
+    digest: AuxiliaryDigest,
+) -> Result<VersionedMessageProof> {
+    if digest.using_encoded(keccak_256) != leaf.leaf_extra.digest_hash.0 {
+        return Err(Error::InvalidDigestHash);
+    }
+    let [leaf_index] = proof.leaf_indices[..] else {
+        return Err(Error::InvalidLeafCount(proof.leaf_indices.len()));
+    };
+    let mmr_proof = MMRLeafProof {
+        leaf_index,
+        leaf_count: proof.leaf_count,
+        items: proof.items,
+    };
+    // Conversion expects the items of the leaf proof
+    if mmr_proof.root(hasher, Keccak256::hash_of(&leaf)).is_none() {
+        return Err(Error::MalformedMMRProof);
+    }
+    Ok(SubstrateBridgeMessageProof {
+        proof: convert_to_simplified_mmr_proof(
+            mmr_proof.leaf_index,
+            mmr_proof.leaf_count,
+            &mmr_proof.items,
+        ),
+        leaf,
+        digest,
+    }
+    .into())
+}
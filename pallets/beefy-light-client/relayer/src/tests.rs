@@ -0,0 +1,359 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use crate::*;
+use beefy_light_client::fixtures::{
+    full_validator_proof, generate_bench_fixture, validator_claims_bitfield, Fixture,
+};
+use bridge_common::simplified_proof::Proof;
+use bridge_types::types::AuxiliaryDigestItem;
+use bridge_types::{GenericNetworkId, SubNetworkId};
+use codec::Decode;
+use sp_keystore::{testing::KeyStore, KeystoreExt};
+use std::sync::Arc;
+
+/// Fixtures keep the validator keys in the keystore.
+fn with_keystore<R>(f: impl FnOnce() -> R) -> R {
+    let mut ext = sp_io::TestExternalities::default();
+    ext.register_extension(KeystoreExt(Arc::new(KeyStore::new())));
+    ext.execute_with(f)
+}
+
+fn digest() -> AuxiliaryDigest {
+    AuxiliaryDigest {
+        logs: vec![AuxiliaryDigestItem::Commitment(
+            GenericNetworkId::Sub(SubNetworkId::Mainnet),
+            H256::repeat_byte(1),
+        )],
+    }
+}
+
+fn decode_commitment(fixture: &Fixture) -> SignedCommitment {
+    Decode::decode(&mut &fixture.commitment[..]).unwrap()
+}
+
+fn authorities(fixture: &Fixture) -> Vec<AuthorityId> {
+    fixture
+        .authorities
+        .iter()
+        .cloned()
+        .map(AuthorityId::from)
+        .collect()
+}
+
+fn builder(fixture: &Fixture) -> CommitmentProofBuilder {
+    CommitmentProofBuilder::new(decode_commitment(fixture), &authorities(fixture)).unwrap()
+}
+
+#[test]
+fn authority_set_matches_fixture() {
+    with_keystore(|| {
+        for validators in [3, 37] {
+            let fixture = generate_bench_fixture(1, validators, 1, 1, &digest());
+            let builder = builder(&fixture);
+            assert_eq!(builder.authorities().addresses, fixture.addresses);
+            assert_eq!(
+                builder.authorities().validator_set(),
+                ValidatorSet::from(fixture.validator_set.clone())
+            );
+            for position in 0..validators {
+                assert_eq!(
+                    builder.authorities().address_proof(position),
+                    fixture.validator_set_proofs[position]
+                );
+            }
+        }
+    });
+}
+
+#[test]
+fn full_validator_proof_matches_fixture() {
+    with_keystore(|| {
+        let fixture = generate_bench_fixture(1, 37, 1, 1, &digest());
+        let signed_commitment = decode_commitment(&fixture);
+        let builder = builder(&fixture);
+
+        assert_eq!(
+            builder.initial_commitment(),
+            InitialCommitment {
+                commitment_hash: Keccak256::hash_of(&signed_commitment.commitment),
+                validator_set_id: 1,
+                validator_claims_bitfield: validator_claims_bitfield(
+                    &signed_commitment.signatures,
+                    37
+                ),
+            }
+        );
+        assert_eq!(
+            builder.full_validator_proof(),
+            Ok(full_validator_proof(
+                &fixture,
+                &signed_commitment.signatures
+            ))
+        );
+    });
+}
+
+#[test]
+fn signature_commitment_reveals_random_signatures() {
+    with_keystore(|| {
+        let fixture = generate_bench_fixture(1, 37, 3, 1, &digest());
+        let builder = builder(&fixture);
+        let initial_commitment = builder.initial_commitment();
+        let random_bitfield = random_bitfield(
+            H256::repeat_byte(2),
+            initial_commitment.commitment_hash,
+            &initial_commitment.validator_claims_bitfield,
+        );
+        assert_eq!(
+            random_bitfield.count_set_bits() as u32,
+            required_number_of_signatures(37)
+        );
+
+        let leaf = BeefyMMRLeaf::decode(&mut &fixture.leaf[..]).unwrap();
+        let args = builder
+            .signature_commitment(&random_bitfield, leaf.clone(), fixture.leaf_proof.clone())
+            .unwrap();
+        assert_eq!(&args.commitment, builder.commitment());
+        assert_eq!(args.latest_mmr_leaf, leaf);
+        assert_eq!(args.proof, LeafProof::from(fixture.leaf_proof.clone()));
+        let validator_proof = args.validator_proof;
+        assert_eq!(
+            validator_proof.validator_claims_bitfield,
+            initial_commitment.validator_claims_bitfield
+        );
+        assert_eq!(
+            validator_proof.positions.len(),
+            random_bitfield.count_set_bits()
+        );
+        for (i, position) in validator_proof.positions.iter().enumerate() {
+            let position = *position as usize;
+            assert!(random_bitfield.is_set(position));
+            assert_eq!(validator_proof.public_keys[i], fixture.addresses[position]);
+            assert_eq!(
+                validator_proof.public_key_merkle_proofs[i],
+                fixture.validator_set_proofs[position]
+            );
+            let signature: [u8; 65] = validator_proof.signatures[i].clone().try_into().unwrap();
+            let public = sp_io::crypto::secp256k1_ecdsa_recover(
+                &signature,
+                &initial_commitment.commitment_hash.0,
+            )
+            .unwrap();
+            assert_eq!(
+                EthAddress::from_slice(&keccak_256(&public)[12..]),
+                fixture.addresses[position]
+            );
+        }
+    });
+}
+
+#[test]
+fn invalid_inputs_rejected() {
+    with_keystore(|| {
+        let fixture = generate_bench_fixture(1, 3, 1, 1, &digest());
+        let authorities = authorities(&fixture);
+        assert_eq!(
+            CommitmentProofBuilder::new(decode_commitment(&fixture), &authorities[1..]).map(|_| ()),
+            Err(Error::AuthoritiesMismatch {
+                signatures: 3,
+                authorities: 2
+            })
+        );
+
+        let builder = builder(&fixture);
+        let leaf = BeefyMMRLeaf::decode(&mut &fixture.leaf[..]).unwrap();
+        assert_eq!(
+            builder.validator_proof(&BitField::with_zeroes(2)),
+            Err(Error::InvalidBitfieldLength(2))
+        );
+        assert_eq!(
+            builder
+                .signature_commitment(
+                    &builder.validator_claims_bitfield(),
+                    leaf,
+                    Proof {
+                        order: 0,
+                        items: vec![],
+                    },
+                )
+                .map(|_| ()),
+            Err(Error::InvalidMMRProof)
+        );
+
+        let mut signed_commitment = decode_commitment(&fixture);
+        signed_commitment.signatures[0] = None;
+        let builder = CommitmentProofBuilder::new(signed_commitment, &authorities).unwrap();
+        assert_eq!(
+            builder.validator_proof(&BitField::create_bitfield(&[0, 1], 3)),
+            Err(Error::MissingSignature(0))
+        );
+    });
+}
+
+#[test]
+fn message_proof_checks_digest() {
+    with_keystore(|| {
+        // Leaf is the only leaf of the MMR
+        let fixture = generate_bench_fixture(1, 3, 0, 1, &digest());
+        let leaf = BeefyMMRLeaf::decode(&mut &fixture.leaf[..]).unwrap();
+        let mmr_proof = |leaf_indices: Vec<u64>| sp_mmr_primitives::Proof {
+            leaf_indices,
+            leaf_count: 1,
+            items: vec![],
+        };
+
+        assert_eq!(
+            message_proof(leaf.clone(), mmr_proof(vec![0]), digest()),
+            Ok(VersionedMessageProof::V1(SubstrateBridgeMessageProof {
+                proof: fixture.leaf_proof.clone().into(),
+                leaf: leaf.clone(),
+                digest: digest(),
+            }))
+        );
+        assert_eq!(
+            message_proof(leaf.clone(), mmr_proof(vec![0]), Default::default()),
+            Err(Error::InvalidDigestHash)
+        );
+        assert_eq!(
+            message_proof(leaf.clone(), mmr_proof(vec![0, 0]), digest()),
+            Err(Error::InvalidLeafCount(2))
+        );
+        assert_eq!(
+            message_proof(leaf, mmr_proof(vec![1]), digest()),
+            Err(Error::MalformedMMRProof)
+        );
+    });
+}
+
+#[test]
+fn message_proof_converts_mmr_proof() {
+    with_keystore(|| {
+        let fixture = generate_bench_fixture(1, 3, 0, 1, &digest());
+        let leaf = BeefyMMRLeaf::decode(&mut &fixture.leaf[..]).unwrap();
+        // Leaf is the right leaf of the MMR with two leaves
+        let sibling = H256::repeat_byte(1);
+        let proof = message_proof(
+            leaf.clone(),
+            sp_mmr_primitives::Proof {
+                leaf_indices: vec![1],
+                leaf_count: 2,
+                items: vec![sibling],
+            },
+            digest(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            proof,
+            VersionedMessageProof::V1(SubstrateBridgeMessageProof {
+                proof: Proof {
+                    order: 1,
+                    items: vec![sibling],
+                },
+                leaf: leaf.clone(),
+                digest: digest(),
+            })
+        );
+        assert_eq!(
+            proof.mmr_root(),
+            Some(hasher(sibling, Keccak256::hash_of(&leaf)))
+        );
+    });
+}
+
+#[test]
+fn commitment_accepted_by_light_client() {
+    use beefy_light_client::mock::*;
+    use frame_support::assert_ok;
+
+    new_test_ext().execute_with(|| {
+        let relayer = 1;
+        let fixture = generate_bench_fixture(0, 37, 3, 1, &digest());
+        assert_ok!(BeefyLightClient::initialize(
+            RuntimeOrigin::root(),
+            SubNetworkId::Mainnet,
+            0,
+            fixture.validator_set.clone().into(),
+            fixture.next_validator_set.clone().into(),
+        ));
+        let builder = builder(&fixture);
+        let initial_commitment = builder.initial_commitment();
+        assert_ok!(BeefyLightClient::submit_initial_commitment(
+            RuntimeOrigin::signed(relayer),
+            SubNetworkId::Mainnet,
+            initial_commitment.commitment_hash,
+            initial_commitment.validator_set_id,
+            initial_commitment.validator_claims_bitfield.clone(),
+        ));
+        let ticket = BeefyLightClient::ticket(
+            SubNetworkId::Mainnet,
+            (relayer, initial_commitment.commitment_hash),
+        )
+        .unwrap();
+        run_to_block(ticket.randomness_block);
+
+        let seed = BeefyLightClient::ticket_seed(ticket.randomness_block)
+            .and_then(|ticket_seed| ticket_seed.seed)
+            .unwrap();
+        let random_bitfield = random_bitfield(
+            seed,
+            initial_commitment.commitment_hash,
+            &initial_commitment.validator_claims_bitfield,
+        );
+        assert_eq!(
+            BeefyLightClient::create_random_bit_field(
+                SubNetworkId::Mainnet,
+                relayer,
+                initial_commitment.commitment_hash
+            ),
+            Ok(random_bitfield.clone())
+        );
+
+        let leaf = BeefyMMRLeaf::decode(&mut &fixture.leaf[..]).unwrap();
+        let args = builder
+            .signature_commitment(&random_bitfield, leaf, fixture.leaf_proof.clone())
+            .unwrap();
+        assert_ok!(
+            BeefyLightClient::submit_signature_commitment_with_leaf_proof(
+                RuntimeOrigin::signed(relayer),
+                SubNetworkId::Mainnet,
+                args.commitment,
+                args.validator_proof,
+                args.latest_mmr_leaf,
+                args.proof,
+            )
+        );
+        assert_eq!(
+            BeefyLightClient::get_latest_mmr_root(SubNetworkId::Mainnet),
+            Some(fixture.mmr_root)
+        );
+    });
+}
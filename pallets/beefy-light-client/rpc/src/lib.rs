@@ -28,8 +28,10 @@
 // STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use beefy_light_client_runtime_api::SubNetworkId;
-use codec::Codec;
+use beefy_light_client_runtime_api::{
+    BeefyMMRLeaf, Commitment, LeafProof, SubNetworkId, ValidatorProof, ValidatorSet, H256,
+};
+use codec::{Codec, Decode};
 use jsonrpsee::{
     core::{Error as RpcError, RpcResult as Result},
     proc_macros::rpc,
@@ -37,22 +39,91 @@ use jsonrpsee::{
 };
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
 use sp_runtime::generic::BlockId;
 use sp_runtime::traits::Block as BlockT;
+use sp_runtime::DispatchError;
 use std::sync::Arc;
 
 pub use beefy_light_client_runtime_api::BeefyLightClientAPI as BeefyLightClientRuntimeAPI;
 
 #[rpc(client, server)]
-pub trait BeefyLightClientAPI<BHash, Bitfield> {
+pub trait BeefyLightClientAPI<BHash, Bitfield, AccountId> {
     #[method(name = "beefyLightClient_getRandomBitfield")]
     fn get_random_bitfield(
         &self,
         network_id: SubNetworkId,
-        prior: Bitfield,
-        num_of_validators: u32,
+        relayer: AccountId,
+        commitment_hash: H256,
+        at: Option<BHash>,
+    ) -> Result<Option<Bitfield>>;
+
+    #[method(name = "beefyLightClient_getMMRRoots")]
+    fn get_mmr_roots(
+        &self,
+        network_id: SubNetworkId,
         at: Option<BHash>,
-    ) -> Result<Bitfield>;
+    ) -> Result<Vec<(u64, H256)>>;
+
+    #[method(name = "beefyLightClient_getMMRRootBlock")]
+    fn get_mmr_root_block(
+        &self,
+        network_id: SubNetworkId,
+        root: H256,
+        at: Option<BHash>,
+    ) -> Result<Option<u64>>;
+
+    #[method(name = "beefyLightClient_getLatestBeefyBlock")]
+    fn get_latest_beefy_block(&self, network_id: SubNetworkId, at: Option<BHash>) -> Result<u64>;
+
+    #[method(name = "beefyLightClient_getCurrentValidatorSet")]
+    fn get_current_validator_set(
+        &self,
+        network_id: SubNetworkId,
+        at: Option<BHash>,
+    ) -> Result<Option<ValidatorSet>>;
+
+    #[method(name = "beefyLightClient_getNextValidatorSet")]
+    fn get_next_validator_set(
+        &self,
+        network_id: SubNetworkId,
+        at: Option<BHash>,
+    ) -> Result<Option<ValidatorSet>>;
+
+    #[method(name = "beefyLightClient_getRequiredNumberOfSignatures")]
+    fn get_required_number_of_signatures(
+        &self,
+        network_id: SubNetworkId,
+        validator_set_id: u64,
+        at: Option<BHash>,
+    ) -> Result<Option<u32>>;
+
+    #[method(name = "beefyLightClient_getThisNetworkId")]
+    fn get_this_network_id(&self, at: Option<BHash>) -> Result<SubNetworkId>;
+
+    /// Check the commitment without submitting it. Commitment, validator proof, leaf
+    /// and MMR proof are SCALE encoded.
+    #[method(name = "beefyLightClient_verifySignatureCommitment")]
+    fn verify_signature_commitment(
+        &self,
+        network_id: SubNetworkId,
+        relayer: AccountId,
+        commitment: Bytes,
+        validator_proof: Bytes,
+        latest_mmr_leaf: Bytes,
+        proof: Bytes,
+        at: Option<BHash>,
+    ) -> Result<std::result::Result<(), DispatchError>>;
+}
+
+fn decode_param<T: Decode>(name: &str, bytes: &Bytes) -> Result<T> {
+    T::decode(&mut &bytes[..]).map_err(|e| {
+        RpcError::Call(CallError::InvalidParams(anyhow::anyhow!(
+            "failed to decode {}: {}",
+            name,
+            e
+        )))
+    })
 }
 
 pub struct BeefyLightClientClient<C, B> {
@@ -70,28 +141,156 @@ impl<C, B> BeefyLightClientClient<C, B> {
     }
 }
 
-impl<C, B, Bitfield> BeefyLightClientAPIServer<<B as BlockT>::Hash, Bitfield>
+impl<C, B, Bitfield, AccountId> BeefyLightClientAPIServer<<B as BlockT>::Hash, Bitfield, AccountId>
     for BeefyLightClientClient<C, B>
 where
     C: Send + Sync + 'static,
     C: ProvideRuntimeApi<B> + HeaderBackend<B>,
-    C::Api: BeefyLightClientRuntimeAPI<B, Bitfield>,
+    C::Api: BeefyLightClientRuntimeAPI<B, Bitfield, AccountId>,
     B: BlockT,
     Bitfield: Codec,
+    AccountId: Codec,
 {
     fn get_random_bitfield(
         &self,
         network_id: SubNetworkId,
-        prior: Bitfield,
-        num_of_validators: u32,
+        relayer: AccountId,
+        commitment_hash: H256,
         at: Option<<B as BlockT>::Hash>,
-    ) -> Result<Bitfield> {
+    ) -> Result<Option<Bitfield>> {
         let api = self.client.runtime_api();
         let at = BlockId::hash(at.unwrap_or(
             // If the block hash is not supplied assume the best block.
             self.client.info().best_hash,
         ));
-        api.get_random_bitfield(&at, network_id, prior, num_of_validators)
+        api.get_random_bitfield(&at, network_id, relayer, commitment_hash)
             .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
     }
+
+    fn get_mmr_roots(
+        &self,
+        network_id: SubNetworkId,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> Result<Vec<(u64, H256)>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.get_mmr_roots(&at, network_id)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn get_mmr_root_block(
+        &self,
+        network_id: SubNetworkId,
+        root: H256,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> Result<Option<u64>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.get_mmr_root_block(&at, network_id, root)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn get_latest_beefy_block(
+        &self,
+        network_id: SubNetworkId,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> Result<u64> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.get_latest_beefy_block(&at, network_id)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn get_current_validator_set(
+        &self,
+        network_id: SubNetworkId,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> Result<Option<ValidatorSet>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.get_current_validator_set(&at, network_id)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn get_next_validator_set(
+        &self,
+        network_id: SubNetworkId,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> Result<Option<ValidatorSet>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.get_next_validator_set(&at, network_id)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn get_required_number_of_signatures(
+        &self,
+        network_id: SubNetworkId,
+        validator_set_id: u64,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> Result<Option<u32>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.get_required_number_of_signatures(&at, network_id, validator_set_id)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn get_this_network_id(&self, at: Option<<B as BlockT>::Hash>) -> Result<SubNetworkId> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.get_this_network_id(&at)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+
+    fn verify_signature_commitment(
+        &self,
+        network_id: SubNetworkId,
+        relayer: AccountId,
+        commitment: Bytes,
+        validator_proof: Bytes,
+        latest_mmr_leaf: Bytes,
+        proof: Bytes,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> Result<std::result::Result<(), DispatchError>> {
+        let commitment: Commitment = decode_param("commitment", &commitment)?;
+        let validator_proof: ValidatorProof = decode_param("validator proof", &validator_proof)?;
+        let latest_mmr_leaf: BeefyMMRLeaf = decode_param("latest MMR leaf", &latest_mmr_leaf)?;
+        let proof: LeafProof<H256> = decode_param("MMR proof", &proof)?;
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.verify_signature_commitment(
+            &at,
+            network_id,
+            relayer,
+            commitment,
+            validator_proof,
+            latest_mmr_leaf,
+            proof,
+        )
+        .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
 }
@@ -32,12 +32,48 @@
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::unnecessary_mut_passed)]
 
-pub use bridge_types::SubNetworkId;
+pub use bridge_common::beefy_types::{BeefyMMRLeaf, Commitment, ValidatorProof, ValidatorSet};
+pub use bridge_common::simplified_proof::{LeafProof, MMRLeafProof, Proof};
+pub use bridge_types::{SubNetworkId, H256};
 use codec::Codec;
+use sp_runtime::DispatchError;
+use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
-    pub trait BeefyLightClientAPI<Bitfield> where Bitfield: Codec
+    #[api_version(2)]
+    pub trait BeefyLightClientAPI<Bitfield, AccountId> where Bitfield: Codec, AccountId: Codec
     {
-        fn get_random_bitfield(network_id: SubNetworkId, prior: Bitfield, num_of_validators: u32) -> Bitfield;
+        /// Bitfield of the validator signatures which should be revealed for the ticket
+        /// submitted by `relayer`, `None` if the ticket randomness is not pinned yet.
+        fn get_random_bitfield(network_id: SubNetworkId, relayer: AccountId, commitment_hash: H256) -> Option<Bitfield>;
+
+        /// Known MMR roots with their BEEFY block numbers from the oldest to the latest.
+        fn get_mmr_roots(network_id: SubNetworkId) -> Vec<(u64, H256)>;
+
+        /// BEEFY block number of the known MMR root.
+        fn get_mmr_root_block(network_id: SubNetworkId, root: H256) -> Option<u64>;
+
+        fn get_latest_beefy_block(network_id: SubNetworkId) -> u64;
+
+        fn get_current_validator_set(network_id: SubNetworkId) -> Option<ValidatorSet>;
+
+        fn get_next_validator_set(network_id: SubNetworkId) -> Option<ValidatorSet>;
+
+        /// Number of the signatures required for the commitment signed by the validator set,
+        /// `None` if the validator set is neither current nor next.
+        fn get_required_number_of_signatures(network_id: SubNetworkId, validator_set_id: u64) -> Option<u32>;
+
+        fn get_this_network_id() -> SubNetworkId;
+
+        /// Check the commitment as `submit_signature_commitment` of `relayer` does,
+        /// without submitting it.
+        fn verify_signature_commitment(
+            network_id: SubNetworkId,
+            relayer: AccountId,
+            commitment: Commitment,
+            validator_proof: ValidatorProof,
+            latest_mmr_leaf: BeefyMMRLeaf,
+            proof: LeafProof<H256>,
+        ) -> Result<(), DispatchError>;
     }
 }
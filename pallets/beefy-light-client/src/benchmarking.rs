@@ -28,7 +28,347 @@
 // STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-// #[allow(unused)]
-// use crate::Pallet as BeefyLightClient;
-// use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
-// use frame_system::RawOrigin;
+//! BeefyLightClient pallet benchmarking
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use super::*;
+use crate::fixtures::{
+    full_validator_proof, generate_bench_fixture, generate_leaf_proof, validator_claims_bitfield,
+    validator_proof,
+};
+use crate::Pallet as BeefyLightClient;
+use bridge_types::traits::Verifier;
+use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_system::RawOrigin;
+use sp_std::prelude::*;
+
+const BASE_NETWORK_ID: SubNetworkId = SubNetworkId::Mainnet;
+
+/// Maximum number of validator signatures in the benchmarks.
+const MAX_SIGNATURES: u32 = 200;
+
+/// Maximum number of the MMR proof items accepted by the proof verification.
+const MAX_PROOF_ITEMS: u32 = 63;
+
+/// Maximum number of the auxiliary digest items in the benchmarks.
+const MAX_DIGEST_ITEMS: u32 = 100;
+
+/// Maximum number of the tickets expiring in one block in the benchmarks.
+const MAX_EXPIRED_TICKETS: u32 = 100;
+
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+    frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+/// Digest with the commitment and `count - 1` other commitments for this network.
+fn digest<T: Config>(commitment_hash: H256, count: u32) -> AuxiliaryDigest {
+    let this_network_id = GenericNetworkId::Sub(ThisNetworkId::<T>::get());
+    let mut logs = vec![AuxiliaryDigestItem::Commitment(
+        this_network_id,
+        commitment_hash,
+    )];
+    for i in 1..count {
+        logs.push(AuxiliaryDigestItem::Commitment(
+            this_network_id,
+            H256::from_low_u64_be(i as u64),
+        ));
+    }
+    AuxiliaryDigest { logs }
+}
+
+fn initialize_validator_sets<T: Config>() -> Result<(), &'static str> {
+    let validator_set = ValidatorSet {
+        id: 0,
+        len: 3,
+        root: H256::repeat_byte(1),
+    };
+    let next_validator_set = ValidatorSet {
+        id: 1,
+        len: 3,
+        root: H256::repeat_byte(2),
+    };
+    BeefyLightClient::<T>::initialize(
+        RawOrigin::Root.into(),
+        BASE_NETWORK_ID,
+        1,
+        validator_set,
+        next_validator_set,
+    )?;
+    Ok(())
+}
+
+/// Fill the past validator sets history, so the oldest set is removed on the next handoff.
+fn fill_validator_sets_history<T: Config>() {
+    let history = (0..T::ValidatorSetHistorySize::get())
+        .map(|i| ValidatorSet {
+            id: i as u64,
+            len: 3,
+            root: H256::from_low_u64_be(i as u64),
+        })
+        .collect();
+    ValidatorSetsHistory::<T>::insert(BASE_NETWORK_ID, history);
+}
+
+/// Move to the ticket randomness block and pin the randomness.
+fn pin_ticket_randomness<T: Config>(ticket: &Ticket<T::BlockNumber>) {
+    frame_system::Pallet::<T>::set_block_number(ticket.randomness_block);
+    BeefyLightClient::<T>::on_initialize(ticket.randomness_block);
+}
+
+benchmarks! {
+    initialize {
+        let validator_set = ValidatorSet {
+            id: 0,
+            len: 3,
+            root: H256::repeat_byte(1),
+        };
+        let next_validator_set = ValidatorSet {
+            id: 1,
+            len: 3,
+            root: H256::repeat_byte(2),
+        };
+    }: _(RawOrigin::Root, BASE_NETWORK_ID, 1, validator_set, next_validator_set)
+    verify {
+        assert_eq!(LatestBeefyBlock::<T>::get(BASE_NETWORK_ID), 1);
+    }
+
+    // Benchmark `submit_signature_commitment` with `a` validator signatures
+    // and `b` MMR proof items. Commitment is signed by the next validator set,
+    // so the validator sets are rotated on submit, and the relayer is rewarded.
+    submit_signature_commitment {
+        let a in 1 .. MAX_SIGNATURES;
+        let b in 1 .. MAX_PROOF_ITEMS;
+        // Required number of signatures for this number of validators is `a`
+        let validators = (a * THRESHOLD_DENOMINATOR / THRESHOLD_NUMERATOR) as usize;
+        // Fill the MMR roots history, so the oldest root is removed on submit
+        let history_size = T::MMRRootHistorySize::get();
+        for i in 1..=history_size {
+            BeefyLightClient::<T>::add_known_mmr_root(
+                BASE_NETWORK_ID,
+                i as u64,
+                H256::from_low_u64_be(i as u64),
+            );
+        }
+        let block_number = history_size + (T::RewardMinBlockGap::get() as u32).max(1);
+        let fixture = generate_bench_fixture(1, validators, b, block_number, &Default::default());
+        let validator_set = ValidatorSet {
+            id: 0,
+            len: 3,
+            root: H256::repeat_byte(1),
+        };
+        BeefyLightClient::<T>::initialize(
+            RawOrigin::Root.into(),
+            BASE_NETWORK_ID,
+            history_size as u64,
+            validator_set,
+            fixture.validator_set.clone().into(),
+        )?;
+        fill_validator_sets_history::<T>();
+        T::Currency::make_free_balance_be(
+            &T::TreasuryAccountId::get(),
+            T::Currency::minimum_balance().saturating_add(T::RelayerReward::get()),
+        );
+        let signed_commitment: sp_beefy::SignedCommitment<u32, sp_core::ecdsa::Signature> =
+            Decode::decode(&mut &fixture.commitment[..]).unwrap();
+        let commitment_hash = Keccak256::hash_of(&signed_commitment.commitment);
+        let caller = whitelisted_caller::<T::AccountId>();
+        BeefyLightClient::<T>::submit_initial_commitment(
+            RawOrigin::Signed(caller.clone()).into(),
+            BASE_NETWORK_ID,
+            commitment_hash,
+            signed_commitment.commitment.validator_set_id,
+            validator_claims_bitfield(&signed_commitment.signatures, validators),
+        )?;
+        let ticket = Tickets::<T>::get(BASE_NETWORK_ID, (caller.clone(), commitment_hash)).unwrap();
+        pin_ticket_randomness::<T>(&ticket);
+        let validator_proof = validator_proof::<T, _>(
+            BASE_NETWORK_ID,
+            caller.clone(),
+            commitment_hash,
+            &fixture,
+            &signed_commitment.signatures,
+            validators,
+        );
+        let leaf: BeefyMMRLeaf = Decode::decode(&mut &fixture.leaf[..]).unwrap();
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        BASE_NETWORK_ID,
+        signed_commitment.commitment.clone(),
+        validator_proof,
+        leaf,
+        fixture.leaf_proof.clone().into()
+    )
+    verify {
+        frame_system::Pallet::<T>::assert_has_event(Event::VerificationSuccessful(
+            BASE_NETWORK_ID,
+            caller,
+            signed_commitment.commitment.block_number,
+        ).into());
+        let next_validator_set: ValidatorSet = fixture.next_validator_set.clone().into();
+        assert_last_event::<T>(Event::ValidatorRegistryUpdated(
+            BASE_NETWORK_ID,
+            next_validator_set.root,
+            next_validator_set.len,
+            next_validator_set.id,
+        ).into());
+        assert_eq!(
+            BeefyLightClient::<T>::get_latest_mmr_root(BASE_NETWORK_ID),
+            Some(fixture.mmr_root)
+        );
+    }
+
+    // Benchmark `Verifier::verify` with `a` MMR proof items and `b` digest items.
+    // The MMR roots history is full.
+    verifier_verify {
+        let a in 1 .. MAX_PROOF_ITEMS;
+        let b in 1 .. MAX_DIGEST_ITEMS;
+        let commitment_hash = H256::repeat_byte(1);
+        let digest = digest::<T>(commitment_hash, b);
+        let (leaf, proof, root) = generate_leaf_proof(&digest, a, Default::default());
+        BeefyLightClient::<T>::add_known_mmr_root(BASE_NETWORK_ID, 1, root);
+        for i in 2..=T::MMRRootHistorySize::get() {
+            BeefyLightClient::<T>::add_known_mmr_root(
+                BASE_NETWORK_ID,
+                i as u64,
+                H256::from_low_u64_be(i as u64),
+            );
+        }
+        let proof = VersionedMessageProof::from(SubstrateBridgeMessageProof {
+            proof,
+            leaf,
+            digest,
+        });
+    }: {
+        BeefyLightClient::<T>::verify(BASE_NETWORK_ID.into(), commitment_hash, &proof)?;
+    }
+
+    submit_initial_commitment {
+        initialize_validator_sets::<T>()?;
+        let caller = whitelisted_caller::<T::AccountId>();
+        let commitment_hash = H256::repeat_byte(1);
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        BASE_NETWORK_ID,
+        commitment_hash,
+        0,
+        BitField::create_bitfield(&[0, 1, 2], 3)
+    )
+    verify {
+        assert!(Tickets::<T>::contains_key(BASE_NETWORK_ID, (caller, commitment_hash)));
+    }
+
+    // Benchmark `on_initialize` with `a` expiring tickets. The randomness is pinned
+    // in the same block.
+    on_initialize {
+        let a in 0 .. MAX_EXPIRED_TICKETS;
+        initialize_validator_sets::<T>()?;
+        let caller = whitelisted_caller::<T::AccountId>();
+        for i in 0..a {
+            BeefyLightClient::<T>::submit_initial_commitment(
+                RawOrigin::Signed(caller.clone()).into(),
+                BASE_NETWORK_ID,
+                H256::from_low_u64_be(i as u64),
+                0,
+                BitField::create_bitfield(&[0, 1, 2], 3),
+            )?;
+        }
+        let expires_at = frame_system::Pallet::<T>::block_number()
+            + T::CommitmentRevealDelay::get().max(One::one())
+            + T::TicketLifetime::get();
+        TicketSeeds::<T>::insert(expires_at, TicketSeed {
+            seed: None,
+            tickets: 1,
+            submitted_at: Zero::zero(),
+        });
+        frame_system::Pallet::<T>::set_block_number(expires_at);
+    }: {
+        BeefyLightClient::<T>::on_initialize(expires_at);
+    }
+    verify {
+        assert!(TicketSeeds::<T>::get(expires_at).unwrap().seed.is_some());
+        assert_eq!(Tickets::<T>::iter_prefix(BASE_NETWORK_ID).count(), 0);
+    }
+
+    // Benchmark `report_equivocation` with `a` validator signatures in both proofs.
+    // Commitments are signed by the oldest of the past validator sets.
+    report_equivocation {
+        let a in 2 .. MAX_SIGNATURES * 2;
+        let validators = (a / 2) as usize;
+        let first = generate_bench_fixture(0, validators, 1, 1, &Default::default());
+        let second = generate_bench_fixture(0, validators, 1, 1, &digest::<T>(H256::repeat_byte(1), 1));
+        let history_size = T::ValidatorSetHistorySize::get() as u64;
+        BeefyLightClient::<T>::initialize(
+            RawOrigin::Root.into(),
+            BASE_NETWORK_ID,
+            0,
+            ValidatorSet {
+                id: history_size,
+                len: 3,
+                root: H256::repeat_byte(1),
+            },
+            ValidatorSet {
+                id: history_size + 1,
+                len: 3,
+                root: H256::repeat_byte(2),
+            },
+        )?;
+        fill_validator_sets_history::<T>();
+        ValidatorSetsHistory::<T>::mutate(BASE_NETWORK_ID, |history| {
+            history.pop_front();
+            history.push_front(first.validator_set.clone().into());
+        });
+        let first_commitment: sp_beefy::SignedCommitment<u32, sp_core::ecdsa::Signature> =
+            Decode::decode(&mut &first.commitment[..]).unwrap();
+        let second_commitment: sp_beefy::SignedCommitment<u32, sp_core::ecdsa::Signature> =
+            Decode::decode(&mut &second.commitment[..]).unwrap();
+        let first_proof = full_validator_proof(&first, &first_commitment.signatures);
+        let second_proof = full_validator_proof(&second, &second_commitment.signatures);
+        let caller = whitelisted_caller::<T::AccountId>();
+    }: _(
+        RawOrigin::Signed(caller.clone()),
+        BASE_NETWORK_ID,
+        first_commitment.commitment,
+        first_proof,
+        second_commitment.commitment,
+        second_proof
+    )
+    verify {
+        assert_eq!(
+            Equivocations::<T>::get(BASE_NETWORK_ID, (0u64, 1u32)).map(|evidence| evidence.reporter),
+            Some(caller)
+        );
+    }
+
+    // Benchmark the rejection of the commitment submitted without the ticket,
+    // e.g. the duplicate one.
+    reject_signature_commitment {
+        let fixture = generate_bench_fixture(0, 3, 1, 2, &Default::default());
+        BeefyLightClient::<T>::initialize(
+            RawOrigin::Root.into(),
+            BASE_NETWORK_ID,
+            1,
+            fixture.validator_set.clone().into(),
+            fixture.next_validator_set.clone().into(),
+        )?;
+        let signed_commitment: sp_beefy::SignedCommitment<u32, sp_core::ecdsa::Signature> =
+            Decode::decode(&mut &fixture.commitment[..]).unwrap();
+        let validator_proof = full_validator_proof(&fixture, &signed_commitment.signatures);
+        let leaf: BeefyMMRLeaf = Decode::decode(&mut &fixture.leaf[..]).unwrap();
+        let caller = whitelisted_caller::<T::AccountId>();
+    }: {
+        assert!(BeefyLightClient::<T>::submit_signature_commitment(
+            RawOrigin::Signed(caller).into(),
+            BASE_NETWORK_ID,
+            signed_commitment.commitment,
+            validator_proof,
+            leaf,
+            fixture.leaf_proof.clone().into(),
+        ).is_err());
+    }
+    verify {
+        assert_eq!(LatestBeefyBlock::<T>::get(BASE_NETWORK_ID), 1);
+    }
+
+    impl_benchmark_test_suite!(BeefyLightClient, crate::mock::new_test_ext(), mock::Test)
+}
@@ -27,77 +27,65 @@
 // OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
 // STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
 #![allow(dead_code)]
 
-use bridge_types::{H160, H256};
+use crate::{Config, Pallet};
+use bridge_common::beefy_types::{BeefyMMRLeaf, ValidatorProof};
+use bridge_common::bitfield::BitField;
+use bridge_common::simplified_proof::{hasher, LeafProof, MMRLeafProof, Proof};
+use bridge_types::types::{AuxiliaryDigest, LeafExtraData};
+use bridge_types::{SubNetworkId, H160, H256};
+use codec::Encode;
+use core::fmt::Write;
+#[cfg(test)]
 use mmr_lib::MMRStore;
-#[cfg(feature = "std")]
+#[cfg(test)]
 use rand::prelude::*;
-use sp_core::{DeriveJunction, Pair};
-use sp_runtime::traits::{Convert, Hash};
+use sp_core::ecdsa;
+use sp_io::hashing::keccak_256;
+use sp_runtime::traits::{Hash, Keccak256};
+use sp_std::prelude::*;
+use sp_std::Writer;
 
+#[cfg(test)]
 use anyhow::Result as AnyResult;
-use codec::Encode;
 use log::debug;
 use serde::Serialize;
 
-use bridge_common::simplified_proof::Proof;
-
+/// Validator keys are stored in the keystore, so fixtures could be generated in the runtime.
 struct ValidatorSet {
-    validators: Vec<sp_core::ecdsa::Pair>,
+    validators: Vec<ecdsa::Public>,
     addresses: Vec<H160>,
     id: u64,
 }
 
 impl ValidatorSet {
-    #[cfg(feature = "std")]
-    fn generate(id: u64, count: usize) -> AnyResult<Self> {
-        let (initial_pair, _phrase, _seed) = sp_core::ecdsa::Pair::generate_with_phrase(None);
-        let mut validators = vec![];
-        for i in 0..count {
-            let (pair, _seed) = initial_pair
-                .derive(vec![DeriveJunction::hard(i as u64)].into_iter(), None)
-                .map_err(|_| anyhow::anyhow!("Failed to derive keypair"))?;
-            validators.push(pair);
-        }
-        let addresses = validators
-            .iter()
-            .map(|x| {
-                H160::from_slice(&pallet_beefy_mmr::BeefyEcdsaToEthereum::convert(
-                    x.public().into(),
-                ))
+    fn generate(id: u64, count: usize) -> Self {
+        let validators = (0..count)
+            .map(|i| {
+                let mut seed = Writer::default();
+                core::write!(seed, "//BeefyValidator//{}//{}", id, i).unwrap();
+                sp_io::crypto::ecdsa_generate(sp_beefy::KEY_TYPE, Some(seed.into_inner()))
             })
-            .collect();
-        Ok(Self {
+            .collect::<Vec<_>>();
+        let addresses = validators.iter().map(validator_address).collect();
+        Self {
             validators,
             id,
             addresses,
-        })
+        }
     }
 
-    #[cfg(feature = "std")]
-    fn sign_commitment<R: Rng>(
+    fn sign_commitment(
         &self,
-        rng: &mut R,
         commitment: sp_beefy::Commitment<u32>,
-        count: Option<usize>,
-    ) -> sp_beefy::SignedCommitment<u32, sp_core::ecdsa::Signature> {
-        let commitment_hash = sp_runtime::traits::Keccak256::hash_of(&commitment);
-        let validators_threshold = threshold(self.validators.len());
-        let signed_count = count.unwrap_or_else(|| {
-            (validators_threshold..=self.validators.len())
-                .choose(rng)
-                .unwrap()
-        });
-        let signers = self
-            .validators
-            .iter()
-            .enumerate()
-            .choose_multiple(rng, signed_count);
+        signers: &[usize],
+    ) -> sp_beefy::SignedCommitment<u32, ecdsa::Signature> {
+        let commitment_hash = Keccak256::hash_of(&commitment);
         let mut signatures = vec![None; self.validators.len()];
-        for (i, signer) in signers {
-            let signature = signer.sign_prehashed(&commitment_hash.0);
-            signatures[i] = Some(signature);
+        for i in signers.iter().cloned() {
+            signatures[i] = Some(sign_prehashed(&self.validators[i], &commitment_hash));
         }
 
         sp_beefy::SignedCommitment {
@@ -107,11 +95,8 @@ impl ValidatorSet {
     }
 
     pub fn validator_pubkey_proof(&self, pos: usize) -> Vec<H256> {
-        let proof = beefy_merkle_tree::merkle_proof::<sp_runtime::traits::Keccak256, _, _>(
-            self.addresses.clone(),
-            pos,
-        )
-        .proof;
+        let proof =
+            beefy_merkle_tree::merkle_proof::<Keccak256, _, _>(self.addresses.clone(), pos).proof;
         debug!("Validator {} proof: {}", pos, proof.len());
         proof
     }
@@ -131,7 +116,7 @@ impl ValidatorSet {
     }
 
     pub fn root(&self) -> H256 {
-        beefy_merkle_tree::merkle_root::<sp_runtime::traits::Keccak256, _>(self.addresses.clone())
+        beefy_merkle_tree::merkle_root::<Keccak256, _>(self.addresses.clone())
     }
 
     fn authority_set(&self) -> sp_beefy::mmr::BeefyAuthoritySet<H256> {
@@ -143,15 +128,31 @@ impl ValidatorSet {
     }
 }
 
-pub type MMRLeaf =
-    sp_beefy::mmr::MmrLeaf<u32, H256, H256, bridge_types::types::LeafExtraData<H256, H256>>;
+fn sign_prehashed(public: &ecdsa::Public, hash: &H256) -> ecdsa::Signature {
+    sp_io::crypto::ecdsa_sign_prehashed(sp_beefy::KEY_TYPE, public, &hash.0)
+        .expect("validator key is in the keystore")
+}
+
+/// Ethereum address of the validator, public key is recovered from the signature
+/// since the keystore returns the compressed one.
+fn validator_address(public: &ecdsa::Public) -> H160 {
+    let hash = H256::zero();
+    let signature = sign_prehashed(public, &hash);
+    let pubkey =
+        sp_io::crypto::secp256k1_ecdsa_recover(&signature.0, &hash.0).expect("signature is valid");
+    H160::from_slice(&keccak_256(&pubkey)[12..])
+}
+
+pub type MMRLeaf = BeefyMMRLeaf;
 
+#[cfg(test)]
 struct FakeMMR {
     // leaves: BTreeMap<u64, MMRLeaf>,
     mem: mmr_lib::util::MemStore<MMRNode>,
     size: u64,
 }
 
+#[cfg(test)]
 impl FakeMMR {
     fn new() -> Self {
         Self {
@@ -187,6 +188,18 @@ impl FakeMMR {
         })
     }
 
+    fn generate_mmr_proof(&self, leaf: u64, at: u64) -> AnyResult<MMRLeafProof<H256>> {
+        let size = Self::size(at);
+        let pos = mmr_lib::leaf_index_to_pos(leaf);
+        let mmr = mmr_lib::MMR::<MMRNode, MMRNode, _>::new(size, &self.mem);
+        let proof = mmr.gen_proof(vec![pos])?;
+        Ok(MMRLeafProof {
+            leaf_index: leaf,
+            leaf_count: at,
+            items: proof.proof_items().iter().map(|x| x.hash()).collect(),
+        })
+    }
+
     fn leaf(&self, leaf: u64) -> MMRLeaf {
         let pos = mmr_lib::leaf_index_to_pos(leaf);
         let elem = (&self.mem).get_elem(pos).unwrap().unwrap();
@@ -223,21 +236,39 @@ impl From<MMRProof> for Proof<H256> {
     }
 }
 
+impl From<MMRProof> for LeafProof<H256> {
+    fn from(proof: MMRProof) -> Self {
+        LeafProof::Simplified(proof.into())
+    }
+}
+
+impl From<Proof<H256>> for MMRProof {
+    fn from(proof: Proof<H256>) -> Self {
+        MMRProof {
+            items: proof.items,
+            order: proof.order,
+        }
+    }
+}
+
+#[cfg(test)]
 #[derive(Debug, Clone, PartialEq, Eq, Encode)]
 enum MMRNode {
     Leaf(MMRLeaf),
     Hash(H256),
 }
 
+#[cfg(test)]
 impl MMRNode {
     fn hash(&self) -> H256 {
         match self {
-            MMRNode::Leaf(leaf) => sp_runtime::traits::Keccak256::hash_of(leaf),
+            MMRNode::Leaf(leaf) => Keccak256::hash_of(leaf),
             MMRNode::Hash(hash) => *hash,
         }
     }
 }
 
+#[cfg(test)]
 impl mmr_lib::Merge for MMRNode {
     type Item = MMRNode;
 
@@ -245,10 +276,7 @@ impl mmr_lib::Merge for MMRNode {
         left: &Self::Item,
         right: &Self::Item,
     ) -> core::result::Result<Self::Item, mmr_lib::Error> {
-        let res = MMRNode::Hash(sp_runtime::traits::Keccak256::hash_of(&(
-            left.hash(),
-            right.hash(),
-        )));
+        let res = MMRNode::Hash(Keccak256::hash_of(&(left.hash(), right.hash())));
         Ok(res)
     }
 }
@@ -277,27 +305,34 @@ impl From<FixtureValidatorSet> for bridge_common::beefy_types::ValidatorSet {
 
 #[derive(Debug, Clone, Serialize, Encode)]
 pub struct Fixture {
+    /// Public keys of the validators of the signing set.
+    #[serde(skip)]
+    pub authorities: Vec<ecdsa::Public>,
     pub addresses: Vec<H160>,
     pub validator_set: FixtureValidatorSet,
     pub next_validator_set: FixtureValidatorSet,
     pub validator_set_proofs: Vec<Vec<H256>>,
     pub commitment: Vec<u8>,
+    pub mmr_root: H256,
     pub leaf_proof: MMRProof,
+    /// Same leaf proof in the standard MMR format, only available for the test fixtures.
+    #[serde(skip)]
+    pub leaf_mmr_proof: Option<MMRLeafProof<H256>>,
     pub leaf: Vec<u8>,
 }
 
-#[cfg(feature = "std")]
+#[cfg(test)]
 pub fn generate_fixture(validators: usize, tree_size: u32) -> AnyResult<Fixture> {
     let mut rng = thread_rng();
-    let validator_set = ValidatorSet::generate(0, validators)?;
-    let next_validator_set = ValidatorSet::generate(1, validators)?;
+    let validator_set = ValidatorSet::generate(0, validators);
+    let next_validator_set = ValidatorSet::generate(1, validators);
     let mut mmr = FakeMMR::new();
     for i in 0..tree_size + 1 {
         mmr.add_leaf(MMRLeaf {
             version: sp_beefy::mmr::MmrLeafVersion::new(0, 0),
             parent_number_and_hash: (i, H256::random_using(&mut rng)),
             beefy_next_authority_set: next_validator_set.authority_set(),
-            leaf_extra: bridge_types::types::LeafExtraData {
+            leaf_extra: LeafExtraData {
                 digest_hash: H256::random_using(&mut rng),
                 random_seed: H256::random_using(&mut rng),
             },
@@ -313,19 +348,164 @@ pub fn generate_fixture(validators: usize, tree_size: u32) -> AnyResult<Fixture>
         block_number: tree_size,
         validator_set_id: validator_set.id,
     };
-    let signed_commitment = validator_set.sign_commitment(&mut rng, commitment, None);
+    let signed_count = (threshold(validators)..=validators)
+        .choose(&mut rng)
+        .unwrap();
+    let signers = (0..validators).choose_multiple(&mut rng, signed_count);
+    let signed_commitment = validator_set.sign_commitment(commitment, &signers);
     let leaf = mmr.leaf(tree_size as u64 - 1);
     let leaf_proof = mmr.generate_proof(tree_size as u64 - 1, tree_size as u64)?;
+    let leaf_mmr_proof = mmr.generate_mmr_proof(tree_size as u64 - 1, tree_size as u64)?;
 
     let fixture = Fixture {
+        authorities: validator_set.validators.clone(),
         addresses: validator_set.addresses.clone(),
         validator_set: validator_set.fixture(),
         next_validator_set: next_validator_set.fixture(),
         validator_set_proofs: validator_set.proofs(),
         commitment: signed_commitment.encode(),
+        mmr_root,
         leaf_proof,
+        leaf_mmr_proof: Some(leaf_mmr_proof),
         leaf: leaf.encode(),
     };
 
     Ok(fixture)
 }
+
+/// Generate the MMR leaf with given digest and the proof of its inclusion with
+/// `proof_items` items. Returns the leaf, the proof and the MMR root.
+///
+/// Root is computed from the proof, so the MMR itself is not built.
+pub fn generate_leaf_proof(
+    digest: &AuxiliaryDigest,
+    proof_items: u32,
+    beefy_next_authority_set: sp_beefy::mmr::BeefyAuthoritySet<H256>,
+) -> (MMRLeaf, Proof<H256>, H256) {
+    let leaf = MMRLeaf {
+        version: sp_beefy::mmr::MmrLeafVersion::new(0, 0),
+        parent_number_and_hash: (proof_items, Keccak256::hash_of(&proof_items)),
+        beefy_next_authority_set,
+        leaf_extra: LeafExtraData {
+            digest_hash: digest.using_encoded(keccak_256).into(),
+            random_seed: Keccak256::hash_of(&(proof_items, 1u8)),
+        },
+    };
+    let proof = Proof {
+        order: 0,
+        items: (0..proof_items)
+            .map(|i| Keccak256::hash_of(&(i, 2u8)))
+            .collect(),
+    };
+    let root = proof.root(hasher, Keccak256::hash_of(&leaf));
+    (leaf, proof, root)
+}
+
+/// Generate the fixture for the commitment at `block_number` signed by all validators
+/// of the set `validator_set_id`, which could be used in the runtime.
+///
+/// Leaf announces the set `validator_set_id + 1`, so the validator sets are rotated on submit
+/// when the signing set is the next one.
+pub fn generate_bench_fixture(
+    validator_set_id: u64,
+    validators: usize,
+    proof_items: u32,
+    block_number: u32,
+    digest: &AuxiliaryDigest,
+) -> Fixture {
+    let validator_set = ValidatorSet::generate(validator_set_id, validators);
+    let next_validator_set = ValidatorSet::generate(validator_set_id + 1, validators);
+    let beefy_next_authority_set = next_validator_set.authority_set();
+    let (leaf, leaf_proof, mmr_root) =
+        generate_leaf_proof(digest, proof_items, beefy_next_authority_set);
+
+    let commitment = sp_beefy::Commitment::<u32> {
+        payload: sp_beefy::Payload::from_single_entry(
+            sp_beefy::known_payloads::MMR_ROOT_ID,
+            mmr_root.encode(),
+        ),
+        block_number,
+        validator_set_id: validator_set.id,
+    };
+    let signers = (0..validators).collect::<Vec<_>>();
+    let signed_commitment = validator_set.sign_commitment(commitment, &signers);
+
+    Fixture {
+        authorities: validator_set.validators.clone(),
+        addresses: validator_set.addresses.clone(),
+        validator_set: validator_set.fixture(),
+        next_validator_set: next_validator_set.fixture(),
+        validator_set_proofs: validator_set.proofs(),
+        commitment: signed_commitment.encode(),
+        mmr_root,
+        leaf_proof: leaf_proof.into(),
+        leaf_mmr_proof: None,
+        leaf: leaf.encode(),
+    }
+}
+
+/// Bitfield of the first `count` validators which signed the commitment.
+pub fn validator_claims_bitfield<S>(signatures: &[Option<S>], count: usize) -> BitField {
+    let bits_to_set = signatures
+        .iter()
+        .enumerate()
+        .filter_map(|(i, x)| x.as_ref().map(|_| i as u32))
+        .take(count)
+        .collect::<Vec<_>>();
+    BitField::create_bitfield(&bits_to_set, signatures.len())
+}
+
+/// Validator proof for the ticket of the `relayer`, which should be already submitted
+/// with [`validator_claims_bitfield`] and have its randomness pinned.
+pub fn validator_proof<T: Config, S: AsRef<[u8]>>(
+    network_id: SubNetworkId,
+    relayer: T::AccountId,
+    commitment_hash: H256,
+    fixture: &Fixture,
+    signatures: &[Option<S>],
+    count: usize,
+) -> ValidatorProof {
+    let initial_bitfield = validator_claims_bitfield(signatures, count);
+    let random_bitfield =
+        Pallet::<T>::create_random_bit_field(network_id, relayer, commitment_hash).unwrap();
+    reveal_signatures(fixture, signatures, &random_bitfield, initial_bitfield)
+}
+
+/// Validator proof with the signatures of all validators which signed the commitment.
+pub fn full_validator_proof<S: AsRef<[u8]>>(
+    fixture: &Fixture,
+    signatures: &[Option<S>],
+) -> ValidatorProof {
+    let bitfield = validator_claims_bitfield(signatures, signatures.len());
+    reveal_signatures(fixture, signatures, &bitfield, bitfield.clone())
+}
+
+fn reveal_signatures<S: AsRef<[u8]>>(
+    fixture: &Fixture,
+    signatures: &[Option<S>],
+    reveal_bitfield: &BitField,
+    validator_claims_bitfield: BitField,
+) -> ValidatorProof {
+    let mut positions = vec![];
+    let mut proof_signatures = vec![];
+    let mut public_keys = vec![];
+    let mut public_key_merkle_proofs = vec![];
+    for i in 0..reveal_bitfield.len() {
+        let bit = reveal_bitfield.is_set(i);
+        if bit {
+            positions.push(i as u128);
+            let mut signature = signatures[i].as_ref().unwrap().as_ref().to_vec();
+            signature[64] += 27;
+            proof_signatures.push(signature);
+            public_keys.push(fixture.addresses[i]);
+            public_key_merkle_proofs.push(fixture.validator_set_proofs[i].clone());
+        }
+    }
+    ValidatorProof {
+        signatures: proof_signatures,
+        positions,
+        public_keys,
+        public_key_merkle_proofs,
+        validator_claims_bitfield,
+    }
+}
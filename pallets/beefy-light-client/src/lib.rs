@@ -31,17 +31,22 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use bridge_common::simplified_proof::*;
-use bridge_common::{beefy_types::*, bitfield, simplified_proof::Proof};
+use bridge_common::{
+    beefy_types::*,
+    bitfield,
+    simplified_proof::{LeafProof, Proof},
+};
 use bridge_types::types::AuxiliaryDigest;
 use bridge_types::types::AuxiliaryDigestItem;
 use bridge_types::{GenericNetworkId, SubNetworkId};
 use codec::Decode;
 use codec::Encode;
+use frame_support::dispatch::WithPostDispatchInfo;
 use frame_support::ensure;
 use frame_support::fail;
 use frame_support::log;
 use frame_support::pallet_prelude::*;
-use frame_support::traits::Randomness;
+use frame_support::traits::{Currency, ExistenceRequirement, Randomness};
 use frame_system::pallet_prelude::*;
 pub use pallet::*;
 use scale_info::prelude::vec::Vec;
@@ -50,27 +55,35 @@ use sp_core::{Get, RuntimeDebug};
 use sp_io::hashing::keccak_256;
 use sp_runtime::traits::Hash;
 use sp_runtime::traits::Keccak256;
+use sp_runtime::traits::One;
+use sp_runtime::traits::Saturating;
+use sp_runtime::traits::Zero;
 use sp_std::collections::vec_deque::VecDeque;
 
-pub const MMR_ROOT_HISTORY_SIZE: usize = 30;
 pub const THRESHOLD_NUMERATOR: u32 = 22;
 pub const THRESHOLD_DENOMINATOR: u32 = 59;
 pub const RANDOMNESS_SUBJECT: &[u8] = b"beefy-light-client";
 
 pub use bitfield::BitField;
 
-#[cfg(test)]
-mod mock;
+/// Mock runtime for the tests, also used to test the relayer crate.
+#[cfg(any(feature = "test", test))]
+pub mod mock;
 
 #[cfg(test)]
 mod tests;
 
-#[cfg(test)]
-mod fixtures;
+/// Fixtures for the tests and benchmarks, also used to test the relayer crate.
+#[cfg(any(test, feature = "runtime-benchmarks"))]
+pub mod fixtures;
 
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+pub mod weights;
+pub use weights::WeightInfo;
+
 #[derive(Clone, RuntimeDebug, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
 pub struct SubstrateBridgeMessageProof {
     pub proof: Proof<H256>,
@@ -78,6 +91,69 @@ pub struct SubstrateBridgeMessageProof {
     pub digest: AuxiliaryDigest,
 }
 
+/// Same as [`SubstrateBridgeMessageProof`], but the MMR leaf proof could be either
+/// in the simplified or in the standard MMR format.
+#[derive(Clone, RuntimeDebug, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
+pub struct SubstrateBridgeMessageLeafProof {
+    pub proof: LeafProof<H256>,
+    pub leaf: BeefyMMRLeaf,
+    pub digest: AuxiliaryDigest,
+}
+
+/// Message proof accepted by the [`bridge_types::traits::Verifier`] implementation.
+///
+/// `V1` keeps the encoding of [`SubstrateBridgeMessageProof`].
+#[derive(Clone, RuntimeDebug, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
+pub enum VersionedMessageProof {
+    V1(SubstrateBridgeMessageProof),
+    V2(SubstrateBridgeMessageLeafProof),
+}
+
+impl VersionedMessageProof {
+    pub fn leaf(&self) -> &BeefyMMRLeaf {
+        match self {
+            VersionedMessageProof::V1(proof) => &proof.leaf,
+            VersionedMessageProof::V2(proof) => &proof.leaf,
+        }
+    }
+
+    pub fn digest(&self) -> &AuxiliaryDigest {
+        match self {
+            VersionedMessageProof::V1(proof) => &proof.digest,
+            VersionedMessageProof::V2(proof) => &proof.digest,
+        }
+    }
+
+    /// Number of the MMR leaf proof items.
+    pub fn proof_len(&self) -> usize {
+        match self {
+            VersionedMessageProof::V1(proof) => proof.proof.items.len(),
+            VersionedMessageProof::V2(proof) => proof.proof.len(),
+        }
+    }
+
+    /// MMR root calculated for the leaf. Returns `None` for malformed proof.
+    pub fn mmr_root(&self) -> Option<H256> {
+        let leaf_hash = Keccak256::hash_of(self.leaf());
+        match self {
+            VersionedMessageProof::V1(proof) => Some(proof.proof.root(hasher, leaf_hash)),
+            VersionedMessageProof::V2(proof) => proof.proof.root(hasher, leaf_hash),
+        }
+    }
+}
+
+impl From<SubstrateBridgeMessageProof> for VersionedMessageProof {
+    fn from(proof: SubstrateBridgeMessageProof) -> Self {
+        VersionedMessageProof::V1(proof)
+    }
+}
+
+impl From<SubstrateBridgeMessageLeafProof> for VersionedMessageProof {
+    fn from(proof: SubstrateBridgeMessageLeafProof) -> Self {
+        VersionedMessageProof::V2(proof)
+    }
+}
+
 fn recover_signature(sig: &[u8; 65], msg_hash: &H256) -> Option<EthAddress> {
     use sp_io::crypto::secp256k1_ecdsa_recover;
 
@@ -86,6 +162,64 @@ fn recover_signature(sig: &[u8; 65], msg_hash: &H256) -> Option<EthAddress> {
         .ok()
 }
 
+/// Number of the validator signatures checked for the commitment signed by the validator set
+/// of `num_validators` validators.
+pub fn required_number_of_signatures(num_validators: u32) -> u32 {
+    (num_validators * THRESHOLD_NUMERATOR + THRESHOLD_DENOMINATOR - 1) / THRESHOLD_DENOMINATOR
+}
+
+/// Random `n` of the `length` validators claimed in `prior`, sampled with the pinned ticket
+/// randomness `seed` for the commitment.
+pub fn random_n_bits_with_prior_check<Hash: Encode>(
+    seed: Hash,
+    commitment_hash: H256,
+    prior: &BitField,
+    n: u32,
+    length: u32,
+) -> BitField {
+    let seed = (seed, commitment_hash).using_encoded(sp_io::hashing::blake2_128);
+    BitField::create_random_bitfield(prior, n, length, u128::from_be_bytes(seed))
+}
+
+/// Initial commitment claims of the relayer, which are revealed after the randomness
+/// used to sample the validator signatures becomes available.
+#[derive(Clone, RuntimeDebug, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
+pub struct Ticket<BlockNumber> {
+    pub validator_set_id: u64,
+    pub validator_claims_bitfield: BitField,
+    /// Block at which the randomness for the signatures sampling is pinned.
+    pub randomness_block: BlockNumber,
+    /// Block at which the ticket is removed if it was not revealed.
+    pub expires_at: BlockNumber,
+}
+
+/// Randomness pinned for the tickets, shared by all tickets with the same randomness block.
+#[derive(Clone, RuntimeDebug, Encode, Decode, PartialEq, Eq, Default, scale_info::TypeInfo)]
+pub struct TicketSeed<Hash, BlockNumber> {
+    /// Randomness pinned at the start of the randomness block or later, `None` until
+    /// the randomness which could not be known at the tickets submission is available.
+    pub seed: Option<Hash>,
+    /// Number of the tickets which are using this randomness.
+    pub tickets: u32,
+    /// Latest block in which the tickets were submitted.
+    pub submitted_at: BlockNumber,
+}
+
+/// Evidence of the validator set signing two different MMR roots for one block.
+#[derive(Clone, RuntimeDebug, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
+pub struct EquivocationEvidence<AccountId> {
+    /// MMR roots from the conflicting commitments.
+    pub mmr_roots: (H256, H256),
+    /// Hashes of the conflicting commitments.
+    pub commitment_hashes: (H256, H256),
+    pub reporter: AccountId,
+}
+
+/// Randomness from the latest MMR leaf of the sidechain `N`.
+///
+/// It is updated only when the commitments are submitted by the relayers and the returned
+/// block number is the sidechain block number, so it is not a valid [`Config::Randomness`]
+/// for this pallet.
 pub struct SidechainRandomness<T, N>(sp_std::marker::PhantomData<(T, N)>);
 
 impl<T: Config, N: Get<SubNetworkId>> Randomness<sp_core::H256, T::BlockNumber>
@@ -112,24 +246,78 @@ pub mod pallet {
     use bridge_types::SubNetworkId;
     use frame_support::dispatch::DispatchResultWithPostInfo;
     use frame_support::pallet_prelude::OptionQuery;
+    use frame_support::traits::StorageVersion;
     use frame_support::{fail, Twox64Concat};
 
+    pub type BalanceOf<T> =
+        <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Source of the randomness used to sample the validator signatures. The returned
+        /// block number should be the block of this chain after which the randomness is
+        /// known, so the source should be updated every block, like BABE randomness.
         type Randomness: frame_support::traits::Randomness<Self::Hash, Self::BlockNumber>;
+        type WeightInfo: WeightInfo;
+        /// Number of blocks between the initial commitment and the block whose randomness
+        /// is used to sample the validator signatures. Should be large enough for the
+        /// randomness to be unknown at the moment of the initial commitment.
+        #[pallet::constant]
+        type CommitmentRevealDelay: Get<Self::BlockNumber>;
+        /// Number of blocks the ticket can be revealed after the randomness is pinned.
+        #[pallet::constant]
+        type TicketLifetime: Get<Self::BlockNumber>;
+        /// Number of the latest MMR roots which are kept for the proofs verification.
+        #[pallet::constant]
+        type MMRRootHistorySize: Get<u32>;
+        /// Whether the verification for the network is frozen until `initialize`
+        /// when the equivocation is reported.
+        #[pallet::constant]
+        type FreezeOnEquivocation: Get<bool>;
+        /// Number of the past validator sets which are kept for the late proofs verification.
+        #[pallet::constant]
+        type ValidatorSetHistorySize: Get<u32>;
+        /// Currency of the relayer rewards.
+        type Currency: Currency<Self::AccountId>;
+        /// Account which pays the relayer rewards.
+        type TreasuryAccountId: Get<Self::AccountId>;
+        /// Reward paid to the relayer for the accepted commitment.
+        #[pallet::constant]
+        type RelayerReward: Get<BalanceOf<Self>>;
+        /// Minimum number of BEEFY blocks the commitment should advance `LatestBeefyBlock` by
+        /// to be rewarded.
+        #[pallet::constant]
+        type RewardMinBlockGap: Get<u64>;
     }
 
+    /// The current storage version.
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     #[pallet::without_storage_info]
     pub struct Pallet<T>(_);
 
     // The pallet's runtime storage items.
+    /// Known MMR roots by BEEFY block number.
+    #[pallet::storage]
+    #[pallet::getter(fn mmr_root)]
+    pub type MMRRoots<T> =
+        StorageDoubleMap<_, Twox64Concat, SubNetworkId, Twox64Concat, u64, H256, OptionQuery>;
+
+    /// BEEFY block numbers of the known MMR roots.
+    #[pallet::storage]
+    #[pallet::getter(fn mmr_root_block)]
+    pub type MMRRootBlocks<T> =
+        StorageDoubleMap<_, Twox64Concat, SubNetworkId, Identity, H256, u64, OptionQuery>;
+
+    /// BEEFY block numbers of the known MMR roots from the oldest to the latest.
     #[pallet::storage]
-    #[pallet::getter(fn latest_mmr_roots)]
-    pub type LatestMMRRoots<T> =
-        StorageMap<_, Twox64Concat, SubNetworkId, VecDeque<H256>, ValueQuery>;
+    #[pallet::getter(fn mmr_roots_history)]
+    pub type MMRRootsHistory<T> =
+        StorageMap<_, Twox64Concat, SubNetworkId, VecDeque<u64>, ValueQuery>;
 
     #[pallet::storage]
     #[pallet::getter(fn latest_beefy_block)]
@@ -155,6 +343,12 @@ pub mod pallet {
     pub type NextValidatorSet<T> =
         StorageMap<_, Twox64Concat, SubNetworkId, ValidatorSet, OptionQuery>;
 
+    /// Past validator sets from the oldest to the latest.
+    #[pallet::storage]
+    #[pallet::getter(fn validator_sets_history)]
+    pub type ValidatorSetsHistory<T> =
+        StorageMap<_, Twox64Concat, SubNetworkId, VecDeque<ValidatorSet>, ValueQuery>;
+
     #[pallet::type_value]
     pub fn DefaultForThisNetworkId() -> SubNetworkId {
         SubNetworkId::Mainnet
@@ -164,12 +358,78 @@ pub mod pallet {
     #[pallet::getter(fn this_network_id)]
     pub type ThisNetworkId<T> = StorageValue<_, SubNetworkId, ValueQuery, DefaultForThisNetworkId>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn ticket)]
+    pub type Tickets<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        SubNetworkId,
+        Blake2_128Concat,
+        (T::AccountId, H256),
+        Ticket<T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn ticket_seed)]
+    pub type TicketSeeds<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::BlockNumber,
+        TicketSeed<T::Hash, T::BlockNumber>,
+        OptionQuery,
+    >;
+
+    /// Randomness blocks which are reached, but the seed is not pinned yet because the
+    /// randomness is not newer than the tickets submission.
+    #[pallet::storage]
+    #[pallet::getter(fn pending_ticket_seeds)]
+    pub type PendingTicketSeeds<T: Config> = StorageValue<_, Vec<T::BlockNumber>, ValueQuery>;
+
+    #[pallet::storage]
+    pub type TicketsExpiration<T: Config> = StorageMap<
+        _,
+        Twox64Concat,
+        T::BlockNumber,
+        Vec<(SubNetworkId, (T::AccountId, H256))>,
+        ValueQuery,
+    >;
+
+    /// Reported equivocations by validator set id and BEEFY block number.
+    #[pallet::storage]
+    #[pallet::getter(fn equivocation)]
+    pub type Equivocations<T: Config> = StorageDoubleMap<
+        _,
+        Twox64Concat,
+        SubNetworkId,
+        Twox64Concat,
+        (u64, u32),
+        EquivocationEvidence<T::AccountId>,
+        OptionQuery,
+    >;
+
+    /// Networks with the verification frozen after the equivocation.
+    #[pallet::storage]
+    #[pallet::getter(fn is_frozen)]
+    pub type Frozen<T> = StorageMap<_, Twox64Concat, SubNetworkId, bool, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         VerificationSuccessful(SubNetworkId, T::AccountId, u32),
         NewMMRRoot(SubNetworkId, H256, u64),
+        /// Next validator set is updated. [network_id, root, len, id]
         ValidatorRegistryUpdated(SubNetworkId, H256, u32, u64),
+        /// Initial commitment submitted. [network_id, relayer, commitment_hash, randomness_block]
+        InitialCommitmentSubmitted(SubNetworkId, T::AccountId, H256, T::BlockNumber),
+        /// Ticket was not revealed in time. [network_id, relayer, commitment_hash]
+        TicketExpired(SubNetworkId, T::AccountId, H256),
+        /// Equivocation reported. [network_id, validator_set_id, block_number, reporter]
+        EquivocationReported(SubNetworkId, u64, u32, T::AccountId),
+        /// Verification is frozen until the light client is initialized again. [network_id]
+        VerificationFrozen(SubNetworkId),
+        /// Relayer is rewarded for the accepted commitment. [network_id, relayer, reward]
+        RelayerRewarded(SubNetworkId, T::AccountId, BalanceOf<T>),
     }
 
     #[pallet::error]
@@ -194,10 +454,40 @@ pub mod pallet {
         CommitmentNotFoundInDigest,
         MMRPayloadNotFound,
         InvalidNetworkId,
+        TicketAlreadyExists,
+        TicketNotFound,
+        TicketRandomnessNotReady,
+        InvalidValidatorClaimsBitfield,
+        CommitmentsNotConflicting,
+        EquivocationAlreadyReported,
+        NetworkFrozen,
+        /// Commitment is signed by the validator set after the next one. Commitments of the
+        /// mandatory blocks of the previous sets should be submitted first.
+        ValidatorSetIdTooFarAhead,
     }
 
     #[pallet::hooks]
-    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let pinned_weight = Self::pin_ticket_seeds(now);
+            let expired = TicketsExpiration::<T>::take(now);
+            let expired_count = expired.len() as u32;
+            for (network_id, key) in expired {
+                // Ticket with the same key could be submitted again after the reveal
+                let Some(ticket) = Tickets::<T>::get(network_id, &key) else {
+                    continue;
+                };
+                if ticket.expires_at != now {
+                    continue;
+                }
+                Tickets::<T>::remove(network_id, &key);
+                Self::release_ticket_seed(&ticket);
+                let (relayer, commitment_hash) = key;
+                Self::deposit_event(Event::TicketExpired(network_id, relayer, commitment_hash));
+            }
+            <T as Config>::WeightInfo::on_initialize(expired_count).saturating_add(pinned_weight)
+        }
+    }
 
     // Dispatchable functions allows users to interact with the pallet and invoke state changes.
     // These functions materialize as "extrinsics", which are often compared to transactions.
@@ -205,7 +495,7 @@ pub mod pallet {
     #[pallet::call]
     impl<T: Config> Pallet<T> {
         #[pallet::call_index(0)]
-        #[pallet::weight(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::initialize())]
         pub fn initialize(
             origin: OriginFor<T>,
             network_id: SubNetworkId,
@@ -217,11 +507,22 @@ pub mod pallet {
             LatestBeefyBlock::<T>::set(network_id, latest_beefy_block);
             CurrentValidatorSet::<T>::set(network_id, Some(validator_set));
             NextValidatorSet::<T>::set(network_id, Some(next_validator_set));
+            ValidatorSetsHistory::<T>::remove(network_id);
+            Frozen::<T>::remove(network_id);
             Ok(().into())
         }
 
+        /// Second phase of the commitment verification. Outdated commitments, e.g. when another
+        /// relayer submitted the same one first, are rejected before the signature checks
+        /// and charged only for the performed checks.
+        ///
+        /// MMR leaf proof is in the simplified format, use
+        /// `submit_signature_commitment_with_leaf_proof` for the standard MMR proofs.
         #[pallet::call_index(1)]
-        #[pallet::weight(0)]
+        #[pallet::weight(<T as Config>::WeightInfo::submit_signature_commitment(
+            validator_proof.signatures.len() as u32,
+            proof.items.len() as u32,
+        ))]
         #[frame_support::transactional]
         pub fn submit_signature_commitment(
             origin: OriginFor<T>,
@@ -232,61 +533,160 @@ pub mod pallet {
             proof: Proof<H256>,
         ) -> DispatchResultWithPostInfo {
             let signer = ensure_signed(origin)?;
-            log::debug!(
-                "BeefyLightClient: submit_signature_commitment: {:?}",
-                commitment
-            );
-            log::debug!(
-                "BeefyLightClient: submit_signature_commitment validator proof: {:?}",
-                validator_proof
+            Self::do_submit_signature_commitment(
+                signer,
+                network_id,
+                commitment,
+                validator_proof,
+                latest_mmr_leaf,
+                proof.into(),
+            )
+        }
+
+        /// First phase of the commitment verification. Claims the validators which signed
+        /// the commitment and pins the randomness of the block `CommitmentRevealDelay` blocks
+        /// ahead, which is used to sample the signatures checked by `submit_signature_commitment`.
+        /// If the randomness source is not updated after the submission by then, the randomness
+        /// is pinned in the first block it is.
+        #[pallet::call_index(2)]
+        #[pallet::weight(<T as Config>::WeightInfo::submit_initial_commitment())]
+        pub fn submit_initial_commitment(
+            origin: OriginFor<T>,
+            network_id: SubNetworkId,
+            commitment_hash: H256,
+            validator_set_id: u64,
+            validator_claims_bitfield: BitField,
+        ) -> DispatchResultWithPostInfo {
+            let signer = ensure_signed(origin)?;
+            ensure!(!Self::is_frozen(network_id), Error::<T>::NetworkFrozen);
+            let vset = Self::validator_set(network_id, validator_set_id)?;
+            Self::check_commitment_signatures_threshold(vset.len, &validator_claims_bitfield)?;
+            let key = (signer.clone(), commitment_hash);
+            ensure!(
+                !Tickets::<T>::contains_key(network_id, &key),
+                Error::<T>::TicketAlreadyExists
             );
-            log::debug!(
-                "BeefyLightClient: submit_signature_commitment latest_mmr_leaf: {:?}",
-                latest_mmr_leaf
+            let now = <frame_system::Pallet<T>>::block_number();
+            // Randomness is pinned at the start of the block, so it can't be the current one
+            let randomness_block =
+                now.saturating_add(T::CommitmentRevealDelay::get().max(One::one()));
+            let expires_at = randomness_block.saturating_add(T::TicketLifetime::get());
+            Tickets::<T>::insert(
+                network_id,
+                &key,
+                Ticket {
+                    validator_set_id,
+                    validator_claims_bitfield,
+                    randomness_block,
+                    expires_at,
+                },
             );
-            log::debug!(
-                "BeefyLightClient: submit_signature_commitment proof: {:?}",
-                proof
+            TicketSeeds::<T>::mutate(randomness_block, |ticket_seed| {
+                let ticket_seed = ticket_seed.get_or_insert_with(Default::default);
+                ticket_seed.tickets += 1;
+                ticket_seed.submitted_at = ticket_seed.submitted_at.max(now);
+            });
+            TicketsExpiration::<T>::append(expires_at, (network_id, key));
+            Self::deposit_event(Event::InitialCommitmentSubmitted(
+                network_id,
+                signer,
+                commitment_hash,
+                randomness_block,
+            ));
+            Ok(().into())
+        }
+
+        /// Report the validator set signing two different MMR roots for one block number.
+        /// Proofs should contain the signatures of all claimed validators.
+        #[pallet::call_index(3)]
+        #[pallet::weight(<T as Config>::WeightInfo::report_equivocation(
+            first_proof.signatures.len().saturating_add(second_proof.signatures.len()) as u32,
+        ))]
+        pub fn report_equivocation(
+            origin: OriginFor<T>,
+            network_id: SubNetworkId,
+            first_commitment: Commitment,
+            first_proof: ValidatorProof,
+            second_commitment: Commitment,
+            second_proof: ValidatorProof,
+        ) -> DispatchResultWithPostInfo {
+            let reporter = ensure_signed(origin)?;
+            ensure!(
+                first_commitment.validator_set_id == second_commitment.validator_set_id
+                    && first_commitment.block_number == second_commitment.block_number,
+                Error::<T>::CommitmentsNotConflicting
             );
-            let current_validator_set = match Self::current_validator_set(network_id) {
-                None => fail!(Error::<T>::PalletNotInitialized),
-                Some(x) => x,
-            };
-            let next_validator_set = match Self::next_validator_set(network_id) {
-                None => fail!(Error::<T>::PalletNotInitialized),
-                Some(x) => x,
-            };
-            let vset = match (commitment.validator_set_id) == current_validator_set.id {
-                true => current_validator_set,
-                false => match (commitment.validator_set_id) == next_validator_set.id {
-                    true => next_validator_set,
-                    false => fail!(Error::<T>::InvalidValidatorSetId),
-                },
-            };
-            Self::verify_commitment(network_id, &commitment, &validator_proof, vset)?;
-            let payload = commitment
+            let first_root = first_commitment
                 .payload
                 .get_decoded::<H256>(&sp_beefy::known_payloads::MMR_ROOT_ID)
                 .ok_or(Error::<T>::MMRPayloadNotFound)?;
-            Self::verify_newest_mmr_leaf(&latest_mmr_leaf, &payload, &proof)?;
-            Self::process_payload(network_id, payload, commitment.block_number.into())?;
+            let second_root = second_commitment
+                .payload
+                .get_decoded::<H256>(&sp_beefy::known_payloads::MMR_ROOT_ID)
+                .ok_or(Error::<T>::MMRPayloadNotFound)?;
+            ensure!(
+                first_root != second_root,
+                Error::<T>::CommitmentsNotConflicting
+            );
+            let key = (
+                first_commitment.validator_set_id,
+                first_commitment.block_number,
+            );
+            ensure!(
+                !Equivocations::<T>::contains_key(network_id, key),
+                Error::<T>::EquivocationAlreadyReported
+            );
+            // Equivocation could be reported after the validator set is changed
+            let vset = Self::known_validator_set(network_id, first_commitment.validator_set_id)?;
+            let first_hash =
+                Self::verify_all_commitment_signatures(&first_commitment, &first_proof, &vset)?;
+            let second_hash =
+                Self::verify_all_commitment_signatures(&second_commitment, &second_proof, &vset)?;
 
-            let block_number = <frame_system::Pallet<T>>::block_number();
-            LatestRandomSeed::<T>::set(
+            Equivocations::<T>::insert(
                 network_id,
-                (latest_mmr_leaf.leaf_extra.random_seed, block_number),
+                key,
+                EquivocationEvidence {
+                    mmr_roots: (first_root, second_root),
+                    commitment_hashes: (first_hash, second_hash),
+                    reporter: reporter.clone(),
+                },
             );
+            Self::deposit_event(Event::EquivocationReported(
+                network_id, key.0, key.1, reporter,
+            ));
+            if T::FreezeOnEquivocation::get() {
+                Frozen::<T>::insert(network_id, true);
+                Self::deposit_event(Event::VerificationFrozen(network_id));
+            }
+            Ok(().into())
+        }
 
-            Self::deposit_event(Event::VerificationSuccessful(
-                network_id,
+        /// Same as `submit_signature_commitment`, but the MMR leaf proof could be either
+        /// in the simplified or in the standard MMR format.
+        #[pallet::call_index(4)]
+        #[pallet::weight(<T as Config>::WeightInfo::submit_signature_commitment(
+            validator_proof.signatures.len() as u32,
+            proof.len() as u32,
+        ))]
+        #[frame_support::transactional]
+        pub fn submit_signature_commitment_with_leaf_proof(
+            origin: OriginFor<T>,
+            network_id: SubNetworkId,
+            commitment: Commitment,
+            validator_proof: ValidatorProof,
+            latest_mmr_leaf: BeefyMMRLeaf,
+            proof: LeafProof<H256>,
+        ) -> DispatchResultWithPostInfo {
+            let signer = ensure_signed(origin)?;
+            Self::do_submit_signature_commitment(
                 signer,
-                commitment.block_number,
-            ));
-            Self::apply_validator_set_changes(
                 network_id,
-                latest_mmr_leaf.beefy_next_authority_set,
-            )?;
-            Ok(().into())
+                commitment,
+                validator_proof,
+                latest_mmr_leaf,
+                proof,
+            )
         }
     }
 
@@ -314,22 +714,27 @@ pub mod pallet {
 }
 
 impl<T: Config> bridge_types::traits::Verifier for Pallet<T> {
-    type Proof = SubstrateBridgeMessageProof;
+    type Proof = VersionedMessageProof;
     fn verify(
         network_id: GenericNetworkId,
         commitment_hash: H256,
-        proof: &SubstrateBridgeMessageProof,
+        proof: &VersionedMessageProof,
     ) -> DispatchResult {
         let network_id = network_id.sub().ok_or(Error::<T>::InvalidNetworkId)?;
+        ensure!(!Self::is_frozen(network_id), Error::<T>::NetworkFrozen);
         let this_network_id = ThisNetworkId::<T>::get();
-        Self::verify_mmr_leaf(network_id, &proof.leaf, &proof.proof)?;
-        let digest_hash = proof.digest.using_encoded(keccak_256);
+        let root = proof.mmr_root().ok_or(Error::<T>::InvalidMMRProof)?;
         ensure!(
-            digest_hash == proof.leaf.leaf_extra.digest_hash.0,
+            Self::is_known_root(network_id, root),
+            Error::<T>::InvalidMMRProof
+        );
+        let digest_hash = proof.digest().using_encoded(keccak_256);
+        ensure!(
+            digest_hash == proof.leaf().leaf_extra.digest_hash.0,
             Error::<T>::InvalidMMRProof
         );
         let count = proof
-            .digest
+            .digest()
             .logs
             .iter()
             .filter(|x| {
@@ -346,59 +751,115 @@ impl<T: Config> bridge_types::traits::Verifier for Pallet<T> {
         Ok(())
     }
 
-    fn verify_weight(_proof: &Self::Proof) -> Weight {
-        Default::default()
+    fn verify_weight(proof: &Self::Proof) -> Weight {
+        <T as Config>::WeightInfo::verifier_verify(
+            proof.proof_len() as u32,
+            proof.digest().logs.len() as u32,
+        )
     }
 
     #[cfg(feature = "runtime-benchmarks")]
-    fn valid_proof() -> Option<Self::Proof> {
-        None
+    fn valid_proof(network_id: GenericNetworkId, commitment_hash: H256) -> Option<Self::Proof> {
+        let network_id = network_id.sub()?;
+        let digest = AuxiliaryDigest {
+            logs: sp_std::vec![AuxiliaryDigestItem::Commitment(
+                GenericNetworkId::Sub(ThisNetworkId::<T>::get()),
+                commitment_hash,
+            )],
+        };
+        let (leaf, proof, root) = fixtures::generate_leaf_proof(&digest, 1, Default::default());
+        let block_number = Self::mmr_roots_history(network_id)
+            .back()
+            .copied()
+            .unwrap_or_default()
+            .max(Self::latest_beefy_block(network_id))
+            + 1;
+        Self::add_known_mmr_root(network_id, block_number, root);
+        Some(
+            SubstrateBridgeMessageProof {
+                proof,
+                leaf,
+                digest,
+            }
+            .into(),
+        )
     }
 }
 
 impl<T: Config> Pallet<T> {
-    pub fn add_known_mmr_root(network_id: SubNetworkId, root: H256) {
-        let mut mmr_roots = LatestMMRRoots::<T>::get(network_id);
-        // Add new root to the front of the list to check it first
-        mmr_roots.push_front(root);
-        if mmr_roots.len() > MMR_ROOT_HISTORY_SIZE {
-            mmr_roots.pop_back();
+    pub fn add_known_mmr_root(network_id: SubNetworkId, block_number: u64, root: H256) {
+        let mut history = MMRRootsHistory::<T>::get(network_id);
+        history.push_back(block_number);
+        MMRRoots::<T>::insert(network_id, block_number, root);
+        MMRRootBlocks::<T>::insert(network_id, root, block_number);
+        // History size could be decreased, so more than one root could be removed
+        while history.len() > T::MMRRootHistorySize::get() as usize {
+            let Some(oldest) = history.pop_front() else {
+                break;
+            };
+            let Some(oldest_root) = MMRRoots::<T>::take(network_id, oldest) else {
+                continue;
+            };
+            // The same root could be added again later
+            if Self::mmr_root_block(network_id, oldest_root) == Some(oldest) {
+                MMRRootBlocks::<T>::remove(network_id, oldest_root);
+            }
         }
-        LatestMMRRoots::<T>::insert(network_id, mmr_roots);
+        MMRRootsHistory::<T>::insert(network_id, history);
     }
 
+    #[inline]
     pub fn is_known_root(network_id: SubNetworkId, root: H256) -> bool {
-        let mmr_roots = LatestMMRRoots::<T>::get(network_id);
-        mmr_roots.contains(&root)
+        MMRRootBlocks::<T>::contains_key(network_id, root)
     }
 
-    #[inline]
     pub fn get_latest_mmr_root(network_id: SubNetworkId) -> Option<H256> {
-        LatestMMRRoots::<T>::get(network_id).back().cloned()
+        let block_number = Self::mmr_roots_history(network_id).back().copied()?;
+        Self::mmr_root(network_id, block_number)
+    }
+
+    /// Known MMR roots with their BEEFY block numbers from the oldest to the latest.
+    pub fn get_mmr_roots(network_id: SubNetworkId) -> Vec<(u64, H256)> {
+        Self::mmr_roots_history(network_id)
+            .into_iter()
+            .filter_map(|block_number| {
+                Self::mmr_root(network_id, block_number).map(|root| (block_number, root))
+            })
+            .collect()
     }
 
     #[inline]
     pub fn verify_beefy_merkle_leaf(
         network_id: SubNetworkId,
         beefy_mmr_leaf: H256,
-        proof: &Proof<H256>,
+        proof: &LeafProof<H256>,
     ) -> bool {
-        let proof_root = proof.root(hasher, beefy_mmr_leaf);
-        Self::is_known_root(network_id, proof_root)
+        proof
+            .root(hasher, beefy_mmr_leaf)
+            .map_or(false, |proof_root| {
+                Self::is_known_root(network_id, proof_root)
+            })
     }
 
-    #[inline]
+    /// Bitfield of the validator signatures which should be revealed for the ticket.
     pub fn create_random_bit_field(
         network_id: SubNetworkId,
-        validator_claims_bitfield: BitField,
-        number_of_validators: u32,
-    ) -> Result<BitField, Error<T>> {
-        Self::random_n_bits_with_prior_check(
-            network_id,
-            &validator_claims_bitfield,
-            Self::get_required_number_of_signatures(number_of_validators),
-            number_of_validators,
-        )
+        relayer: T::AccountId,
+        commitment_hash: H256,
+    ) -> Result<BitField, DispatchError> {
+        let ticket = Self::ticket(network_id, (relayer, commitment_hash))
+            .ok_or(Error::<T>::TicketNotFound)?;
+        let seed = Self::ticket_seed(ticket.randomness_block)
+            .and_then(|ticket_seed| ticket_seed.seed)
+            .ok_or(Error::<T>::TicketRandomnessNotReady)?;
+        let vset = Self::validator_set(network_id, ticket.validator_set_id)?;
+        Ok(Self::random_n_bits_with_prior_check(
+            seed,
+            commitment_hash,
+            &ticket.validator_claims_bitfield,
+            Self::get_required_number_of_signatures(vset.len),
+            vset.len,
+        ))
     }
 
     #[inline]
@@ -411,33 +872,275 @@ impl<T: Config> Pallet<T> {
         Self::get_required_number_of_signatures(vset.len)
     }
 
+    /// Check the commitment as `submit_signature_commitment` does, without submitting it.
+    pub fn verify_signature_commitment(
+        network_id: SubNetworkId,
+        relayer: &T::AccountId,
+        commitment: &Commitment,
+        validator_proof: &ValidatorProof,
+        latest_mmr_leaf: &BeefyMMRLeaf,
+        proof: &LeafProof<H256>,
+    ) -> DispatchResult {
+        Self::check_signature_commitment(
+            network_id,
+            relayer,
+            commitment,
+            validator_proof,
+            latest_mmr_leaf,
+            proof,
+        )?;
+        Ok(())
+    }
+
+    /// Number of the signatures required for the commitment signed by the validator set.
+    pub fn required_number_of_signatures_for_set(
+        network_id: SubNetworkId,
+        validator_set_id: u64,
+    ) -> Option<u32> {
+        Self::validator_set(network_id, validator_set_id)
+            .ok()
+            .map(|vset| Self::required_number_of_signatures(&vset))
+    }
+
+    /// Current or next validator set with the given id.
+    pub fn validator_set(
+        network_id: SubNetworkId,
+        validator_set_id: u64,
+    ) -> Result<ValidatorSet, DispatchError> {
+        let current_validator_set = match Self::current_validator_set(network_id) {
+            None => fail!(Error::<T>::PalletNotInitialized),
+            Some(x) => x,
+        };
+        let next_validator_set = match Self::next_validator_set(network_id) {
+            None => fail!(Error::<T>::PalletNotInitialized),
+            Some(x) => x,
+        };
+        ensure!(
+            validator_set_id <= next_validator_set.id,
+            Error::<T>::ValidatorSetIdTooFarAhead
+        );
+        let vset = match validator_set_id == current_validator_set.id {
+            true => current_validator_set,
+            false => match validator_set_id == next_validator_set.id {
+                true => next_validator_set,
+                false => fail!(Error::<T>::InvalidValidatorSetId),
+            },
+        };
+        Ok(vset)
+    }
+
+    /// Current, next or one of the past validator sets with the given id.
+    pub fn known_validator_set(
+        network_id: SubNetworkId,
+        validator_set_id: u64,
+    ) -> Result<ValidatorSet, DispatchError> {
+        match Self::validator_set(network_id, validator_set_id) {
+            Err(err) if err == Error::<T>::InvalidValidatorSetId.into() => {
+                Self::validator_sets_history(network_id)
+                    .into_iter()
+                    .find(|vset| vset.id == validator_set_id)
+                    .ok_or_else(|| Error::<T>::InvalidValidatorSetId.into())
+            }
+            res => res,
+        }
+    }
+
     /* Private Functions */
 
-    fn verify_newest_mmr_leaf(
-        leaf: &BeefyMMRLeaf,
-        root: &H256,
-        proof: &Proof<H256>,
+    fn do_submit_signature_commitment(
+        signer: T::AccountId,
+        network_id: SubNetworkId,
+        commitment: Commitment,
+        validator_proof: ValidatorProof,
+        latest_mmr_leaf: BeefyMMRLeaf,
+        proof: LeafProof<H256>,
     ) -> DispatchResultWithPostInfo {
-        let hash_leaf = Keccak256::hash_of(&leaf);
+        log::debug!(
+            "BeefyLightClient: submit_signature_commitment: {:?}",
+            commitment
+        );
+        log::debug!(
+            "BeefyLightClient: submit_signature_commitment validator proof: {:?}",
+            validator_proof
+        );
+        log::debug!(
+            "BeefyLightClient: submit_signature_commitment latest_mmr_leaf: {:?}",
+            latest_mmr_leaf
+        );
+        log::debug!(
+            "BeefyLightClient: submit_signature_commitment proof: {:?}",
+            proof
+        );
+        let reject_weight = <T as Config>::WeightInfo::reject_signature_commitment();
         ensure!(
-            verify_inclusion_proof(*root, hash_leaf, proof),
-            Error::<T>::InvalidMMRProof
+            !Self::is_frozen(network_id),
+            Error::<T>::NetworkFrozen.with_weight(reject_weight)
+        );
+        let latest_beefy_block = Self::latest_beefy_block(network_id);
+        ensure!(
+            u64::from(commitment.block_number) > latest_beefy_block,
+            Error::<T>::PayloadBlocknumberTooOld.with_weight(reject_weight)
+        );
+        ensure!(
+            Tickets::<T>::contains_key(
+                network_id,
+                (signer.clone(), Keccak256::hash_of(&commitment))
+            ),
+            Error::<T>::TicketNotFound.with_weight(reject_weight)
+        );
+        let (ticket, payload) = Self::check_signature_commitment(
+            network_id,
+            &signer,
+            &commitment,
+            &validator_proof,
+            &latest_mmr_leaf,
+            &proof,
+        )?;
+        Tickets::<T>::remove(
+            network_id,
+            (signer.clone(), Keccak256::hash_of(&commitment)),
+        );
+        Self::release_ticket_seed(&ticket);
+        Self::process_payload(network_id, payload, commitment.block_number.into())?;
+
+        let block_number = <frame_system::Pallet<T>>::block_number();
+        LatestRandomSeed::<T>::set(
+            network_id,
+            (latest_mmr_leaf.leaf_extra.random_seed, block_number),
         );
+
+        Self::deposit_event(Event::VerificationSuccessful(
+            network_id,
+            signer.clone(),
+            commitment.block_number,
+        ));
+        if u64::from(commitment.block_number).saturating_sub(latest_beefy_block)
+            >= T::RewardMinBlockGap::get()
+        {
+            Self::reward_relayer(network_id, &signer);
+        }
+        Self::apply_validator_set_changes(
+            network_id,
+            commitment.validator_set_id,
+            latest_mmr_leaf.beefy_next_authority_set,
+        )?;
         Ok(().into())
     }
 
-    fn verify_mmr_leaf(
+    /// Returns the ticket of the commitment and the MMR root from the commitment payload.
+    fn check_signature_commitment(
         network_id: SubNetworkId,
+        relayer: &T::AccountId,
+        commitment: &Commitment,
+        validator_proof: &ValidatorProof,
+        latest_mmr_leaf: &BeefyMMRLeaf,
+        proof: &LeafProof<H256>,
+    ) -> Result<(Ticket<T::BlockNumber>, H256), DispatchError> {
+        ensure!(!Self::is_frozen(network_id), Error::<T>::NetworkFrozen);
+        ensure!(
+            u64::from(commitment.block_number) > Self::latest_beefy_block(network_id),
+            Error::<T>::PayloadBlocknumberTooOld
+        );
+        let commitment_hash = Keccak256::hash_of(commitment);
+        let ticket = Tickets::<T>::get(network_id, (relayer.clone(), commitment_hash))
+            .ok_or(Error::<T>::TicketNotFound)?;
+        ensure!(
+            ticket.validator_set_id == commitment.validator_set_id,
+            Error::<T>::InvalidValidatorSetId
+        );
+        ensure!(
+            ticket.validator_claims_bitfield == validator_proof.validator_claims_bitfield,
+            Error::<T>::InvalidValidatorClaimsBitfield
+        );
+        let seed = Self::ticket_seed(ticket.randomness_block)
+            .and_then(|ticket_seed| ticket_seed.seed)
+            .ok_or(Error::<T>::TicketRandomnessNotReady)?;
+        let vset = Self::validator_set(network_id, commitment.validator_set_id)?;
+        Self::verify_commitment(commitment, validator_proof, vset, seed).map_err(|e| e.error)?;
+        let payload = commitment
+            .payload
+            .get_decoded::<H256>(&sp_beefy::known_payloads::MMR_ROOT_ID)
+            .ok_or(Error::<T>::MMRPayloadNotFound)?;
+        Self::verify_newest_mmr_leaf(latest_mmr_leaf, &payload, proof).map_err(|e| e.error)?;
+        Ok((ticket, payload))
+    }
+
+    /// Pay the reward from the treasury. Submission is not failed if the treasury can't pay.
+    fn reward_relayer(network_id: SubNetworkId, relayer: &T::AccountId) {
+        let reward = T::RelayerReward::get();
+        if reward.is_zero() {
+            return;
+        }
+        match T::Currency::transfer(
+            &T::TreasuryAccountId::get(),
+            relayer,
+            reward,
+            ExistenceRequirement::KeepAlive,
+        ) {
+            Ok(()) => {
+                Self::deposit_event(Event::RelayerRewarded(network_id, relayer.clone(), reward))
+            }
+            Err(err) => log::warn!("BeefyLightClient: failed to reward relayer: {:?}", err),
+        }
+    }
+
+    /// Pin the randomness for the tickets with the randomness block `now` and for the
+    /// previously reached randomness blocks which are still waiting for it.
+    ///
+    /// Randomness is pinned only if it is returned for a block after the tickets submission,
+    /// otherwise it could be known to the relayer before the initial commitment.
+    fn pin_ticket_seeds(now: T::BlockNumber) -> Weight {
+        let mut pending = PendingTicketSeeds::<T>::get();
+        let mut weight = T::DbWeight::get().reads(2);
+        if !TicketSeeds::<T>::contains_key(now) && pending.is_empty() {
+            return weight;
+        }
+        pending.push(now);
+        let (seed, randomness_block) = T::Randomness::random(RANDOMNESS_SUBJECT);
+        weight.saturating_accrue(
+            T::DbWeight::get().reads_writes(pending.len() as u64 + 1, pending.len() as u64 + 1),
+        );
+        pending.retain(|block| {
+            TicketSeeds::<T>::mutate(block, |ticket_seed| match ticket_seed {
+                Some(ticket_seed) if randomness_block > ticket_seed.submitted_at => {
+                    ticket_seed.seed = Some(seed);
+                    false
+                }
+                Some(_) => true,
+                // All tickets are revealed or expired
+                None => false,
+            })
+        });
+        if pending.is_empty() {
+            PendingTicketSeeds::<T>::kill();
+        } else {
+            PendingTicketSeeds::<T>::put(pending);
+        }
+        weight
+    }
+
+    fn release_ticket_seed(ticket: &Ticket<T::BlockNumber>) {
+        TicketSeeds::<T>::mutate_exists(ticket.randomness_block, |ticket_seed| {
+            if let Some(seed) = ticket_seed {
+                seed.tickets = seed.tickets.saturating_sub(1);
+                if seed.tickets == 0 {
+                    *ticket_seed = None;
+                }
+            }
+        });
+    }
+
+    fn verify_newest_mmr_leaf(
         leaf: &BeefyMMRLeaf,
-        proof: &Proof<H256>,
-    ) -> DispatchResult {
+        root: &H256,
+        proof: &LeafProof<H256>,
+    ) -> DispatchResultWithPostInfo {
         let hash_leaf = Keccak256::hash_of(&leaf);
-        let root = proof.root(hasher, hash_leaf);
         ensure!(
-            Self::is_known_root(network_id, root),
+            verify_leaf_proof(*root, hash_leaf, proof),
             Error::<T>::InvalidMMRProof
         );
-        Ok(())
+        Ok(().into())
     }
 
     fn process_payload(
@@ -449,29 +1152,58 @@ impl<T: Config> Pallet<T> {
             block_number > Self::latest_beefy_block(network_id,),
             Error::<T>::PayloadBlocknumberTooOld
         );
-        Self::add_known_mmr_root(network_id, payload);
+        Self::add_known_mmr_root(network_id, block_number, payload);
         LatestBeefyBlock::<T>::set(network_id, block_number);
         Self::deposit_event(Event::NewMMRRoot(network_id, payload, block_number));
         Ok(().into())
     }
 
+    /// Hand off the validator sets after the commitment signed by `signer_set_id` is accepted.
+    /// The commitment signed by the next set makes it current, so the chain of the mandatory
+    /// block commitments catches up several sets. The set announced by the leaf becomes next.
     fn apply_validator_set_changes(
         network_id: SubNetworkId,
+        signer_set_id: u64,
         new_vset: ValidatorSet,
     ) -> DispatchResultWithPostInfo {
+        let current_validator_set = match Self::current_validator_set(network_id) {
+            None => fail!(Error::<T>::PalletNotInitialized),
+            Some(x) => x,
+        };
         let next_validator_set = match Self::next_validator_set(network_id) {
             None => fail!(Error::<T>::PalletNotInitialized),
             Some(x) => x,
         };
+        if signer_set_id == next_validator_set.id
+            && next_validator_set.id > current_validator_set.id
+        {
+            Self::add_past_validator_set(network_id, current_validator_set);
+            CurrentValidatorSet::<T>::set(network_id, Some(next_validator_set.clone()));
+        }
         if new_vset.id > next_validator_set.id {
-            CurrentValidatorSet::<T>::set(network_id, Some(next_validator_set));
+            Self::deposit_event(Event::ValidatorRegistryUpdated(
+                network_id,
+                new_vset.root,
+                new_vset.len,
+                new_vset.id,
+            ));
             NextValidatorSet::<T>::set(network_id, Some(new_vset));
         }
         Ok(().into())
     }
 
+    fn add_past_validator_set(network_id: SubNetworkId, vset: ValidatorSet) {
+        ValidatorSetsHistory::<T>::mutate(network_id, |history| {
+            history.push_back(vset);
+            // History size could be decreased, so more than one set could be removed
+            while history.len() > T::ValidatorSetHistorySize::get() as usize {
+                history.pop_front();
+            }
+        });
+    }
+
     fn get_required_number_of_signatures(num_validators: u32) -> u32 {
-        (num_validators * THRESHOLD_NUMERATOR + THRESHOLD_DENOMINATOR - 1) / THRESHOLD_DENOMINATOR
+        required_number_of_signatures(num_validators)
     }
 
     /*
@@ -488,10 +1220,10 @@ impl<T: Config> Pallet<T> {
     }
 
     fn verify_commitment(
-        network_id: SubNetworkId,
         commitment: &Commitment,
         proof: &ValidatorProof,
         vset: ValidatorSet,
+        seed: T::Hash,
     ) -> DispatchResultWithPostInfo {
         let number_of_validators = vset.len;
         let required_num_of_signatures =
@@ -500,12 +1232,14 @@ impl<T: Config> Pallet<T> {
             number_of_validators,
             &proof.validator_claims_bitfield,
         )?;
+        let commitment_hash = Keccak256::hash_of(&commitment);
         let random_bitfield = Self::random_n_bits_with_prior_check(
-            network_id,
+            seed,
+            commitment_hash,
             &proof.validator_claims_bitfield,
             required_num_of_signatures,
             number_of_validators,
-        )?;
+        );
         log::debug!("BeefyLightClient verify_commitment proof: {:?}", proof);
         log::debug!(
             "BeefyLightClient verify_commitment validator_claims_bitfield: {:?}",
@@ -516,7 +1250,6 @@ impl<T: Config> Pallet<T> {
             random_bitfield
         );
         Self::verify_validator_proof_lengths(required_num_of_signatures, proof)?;
-        let commitment_hash = Keccak256::hash_of(&commitment);
         Self::verify_validator_proof_signatures(
             &vset,
             random_bitfield,
@@ -527,6 +1260,29 @@ impl<T: Config> Pallet<T> {
         Ok(().into())
     }
 
+    /// Verify the signatures of all validators claimed in the proof bitfield.
+    /// Returns the commitment hash.
+    fn verify_all_commitment_signatures(
+        commitment: &Commitment,
+        proof: &ValidatorProof,
+        vset: &ValidatorSet,
+    ) -> Result<H256, DispatchError> {
+        Self::check_commitment_signatures_threshold(vset.len, &proof.validator_claims_bitfield)
+            .map_err(|e| e.error)?;
+        let num_of_signatures = proof.validator_claims_bitfield.count_set_bits() as u32;
+        Self::verify_validator_proof_lengths(num_of_signatures, proof).map_err(|e| e.error)?;
+        let commitment_hash = Keccak256::hash_of(commitment);
+        Self::verify_validator_proof_signatures(
+            vset,
+            proof.validator_claims_bitfield.clone(),
+            proof,
+            num_of_signatures,
+            commitment_hash,
+        )
+        .map_err(|e| e.error)?;
+        Ok(commitment_hash)
+    }
+
     fn verify_validator_proof_lengths(
         required_num_of_signatures: u32,
         proof: &ValidatorProof,
@@ -621,22 +1377,12 @@ impl<T: Config> Pallet<T> {
     }
 
     pub fn random_n_bits_with_prior_check(
-        network_id: SubNetworkId,
+        raw_seed: T::Hash,
+        commitment_hash: H256,
         prior: &BitField,
         n: u32,
         length: u32,
-    ) -> Result<BitField, Error<T>> {
-        let (raw_seed, _block) = T::Randomness::random(RANDOMNESS_SUBJECT);
-        let latest_beefy_block = Self::latest_beefy_block(network_id);
-        let seed = codec::Encode::using_encoded(
-            &(raw_seed, latest_beefy_block),
-            sp_io::hashing::blake2_128,
-        );
-        Ok(BitField::create_random_bitfield(
-            prior,
-            n,
-            length,
-            u128::from_be_bytes(seed),
-        ))
+    ) -> BitField {
+        crate::random_n_bits_with_prior_check(raw_seed, commitment_hash, prior, n, length)
     }
 }
@@ -0,0 +1,120 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Storage migrations, which should be added to the runtime `Executive` migrations.
+
+use crate::{Config, LatestBeefyBlock, Pallet};
+use bridge_types::SubNetworkId;
+use frame_support::pallet_prelude::*;
+use frame_support::traits::OnRuntimeUpgrade;
+use sp_core::H256;
+use sp_std::collections::vec_deque::VecDeque;
+use sp_std::prelude::*;
+
+pub mod v1 {
+    use super::*;
+
+    /// MMR roots from the latest to the oldest.
+    #[frame_support::storage_alias]
+    pub type LatestMMRRoots<T: Config> =
+        StorageMap<Pallet<T>, Twox64Concat, SubNetworkId, VecDeque<H256>, ValueQuery>;
+
+    /// Move the latest MMR root from `LatestMMRRoots` to `MMRRoots`, `MMRRootBlocks`
+    /// and `MMRRootsHistory`, keyed under `LatestBeefyBlock` of the network.
+    ///
+    /// BEEFY block numbers of the older roots were not stored, so they are dropped and
+    /// the messages should be proven against the latest root.
+    pub struct MigrateToV1<T>(PhantomData<T>);
+
+    impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+        fn on_runtime_upgrade() -> Weight {
+            if StorageVersion::get::<Pallet<T>>() != 0 {
+                return T::DbWeight::get().reads(1);
+            }
+            let mut reads = 1;
+            let mut writes = 1;
+            for (network_id, roots) in LatestMMRRoots::<T>::drain() {
+                reads += 1;
+                writes += 1;
+                let Some(root) = roots.front().copied() else {
+                    continue;
+                };
+                let block_number = LatestBeefyBlock::<T>::get(network_id);
+                Pallet::<T>::add_known_mmr_root(network_id, block_number, root);
+                reads += 2;
+                writes += 3;
+            }
+            StorageVersion::new(1).put::<Pallet<T>>();
+            T::DbWeight::get().reads_writes(reads, writes)
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+            if StorageVersion::get::<Pallet<T>>() != 0 {
+                return Ok(Vec::new());
+            }
+            let latest_roots = LatestMMRRoots::<T>::iter()
+                .filter_map(|(network_id, roots)| {
+                    let root = roots.front().copied()?;
+                    Some((network_id, LatestBeefyBlock::<T>::get(network_id), root))
+                })
+                .collect::<Vec<_>>();
+            Ok(latest_roots.encode())
+        }
+
+        #[cfg(feature = "try-runtime")]
+        fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+            ensure!(
+                StorageVersion::get::<Pallet<T>>() == 1,
+                "storage version is not updated"
+            );
+            ensure!(
+                LatestMMRRoots::<T>::iter().next().is_none(),
+                "old MMR roots are not removed"
+            );
+            if state.is_empty() {
+                return Ok(());
+            }
+            let latest_roots = Vec::<(SubNetworkId, u64, H256)>::decode(&mut &state[..])
+                .map_err(|_| "failed to decode the pre-upgrade state")?;
+            for (network_id, block_number, root) in latest_roots {
+                ensure!(
+                    Pallet::<T>::get_mmr_roots(network_id) == [(block_number, root)],
+                    "latest MMR root is not migrated"
+                );
+                ensure!(
+                    Pallet::<T>::mmr_root_block(network_id, root) == Some(block_number),
+                    "latest MMR root block is not migrated"
+                );
+            }
+            Ok(())
+        }
+    }
+}
@@ -29,13 +29,15 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate as beefy_light_client;
-use bridge_types::SubNetworkId;
-use frame_support::{parameter_types, traits::Everything};
+use frame_support::{
+    parameter_types,
+    traits::{Everything, GenesisBuild, Hooks, Randomness},
+};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
     testing::Header,
-    traits::{BlakeTwo256, IdentityLookup},
+    traits::{BlakeTwo256, Hash, IdentityLookup},
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -49,6 +51,7 @@ frame_support::construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+        Balances: pallet_balances::{Pallet, Call, Storage, Event<T>},
         BeefyLightClient: beefy_light_client::{Pallet, Call, Storage, Event<T>},
     }
 );
@@ -56,7 +59,25 @@ frame_support::construct_runtime!(
 parameter_types! {
     pub const BlockHashCount: u64 = 250;
     pub const SS58Prefix: u8 = 42;
-    pub const SidechainRandomnessNetwork: SubNetworkId = SubNetworkId::Mainnet;
+    pub const CommitmentRevealDelay: u64 = 3;
+    pub const TicketLifetime: u64 = 5;
+    pub static MMRRootHistorySize: u32 = 5;
+    pub static FreezeOnEquivocation: bool = true;
+    pub static ValidatorSetHistorySize: u32 = 2;
+    pub const ExistentialDeposit: u64 = 1;
+    pub const TreasuryAccountId: u64 = 100;
+    pub static RelayerReward: u64 = 10;
+    pub static RewardMinBlockGap: u64 = 3;
+    pub static RandomnessLag: u64 = 0;
+}
+
+/// Randomness which is updated every block, `RandomnessLag` blocks behind the current one.
+pub struct TestRandomness;
+impl Randomness<H256, u64> for TestRandomness {
+    fn random(subject: &[u8]) -> (H256, u64) {
+        let block = System::block_number().saturating_sub(RandomnessLag::get());
+        (BlakeTwo256::hash_of(&(subject, block)), block)
+    }
 }
 
 impl system::Config for Test {
@@ -77,7 +98,7 @@ impl system::Config for Test {
     type BlockHashCount = BlockHashCount;
     type Version = ();
     type PalletInfo = PalletInfo;
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<u64>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
@@ -86,15 +107,54 @@ impl system::Config for Test {
     type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+    type Balance = u64;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+    type MaxLocks = ();
+    type MaxReserves = ();
+    type ReserveIdentifier = ();
+}
+
 impl beefy_light_client::Config for Test {
     type RuntimeEvent = RuntimeEvent;
-    type Randomness = beefy_light_client::SidechainRandomness<Test, SidechainRandomnessNetwork>;
+    type Randomness = TestRandomness;
+    type WeightInfo = ();
+    type CommitmentRevealDelay = CommitmentRevealDelay;
+    type TicketLifetime = TicketLifetime;
+    type MMRRootHistorySize = MMRRootHistorySize;
+    type FreezeOnEquivocation = FreezeOnEquivocation;
+    type ValidatorSetHistorySize = ValidatorSetHistorySize;
+    type Currency = Balances;
+    type TreasuryAccountId = TreasuryAccountId;
+    type RelayerReward = RelayerReward;
+    type RewardMinBlockGap = RewardMinBlockGap;
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-    system::GenesisConfig::default()
+    let mut storage = system::GenesisConfig::default()
         .build_storage::<Test>()
-        .unwrap()
-        .into()
+        .unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: vec![(TreasuryAccountId::get(), 1_000)],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+    let mut ext: sp_io::TestExternalities = storage.into();
+    ext.register_extension(sp_keystore::KeystoreExt(std::sync::Arc::new(
+        sp_keystore::testing::KeyStore::new(),
+    )));
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+pub fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        System::set_block_number(System::block_number() + 1);
+        BeefyLightClient::on_initialize(System::block_number());
+    }
 }
@@ -29,62 +29,63 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use crate::mock::*;
+use crate::{
+    migrations, EquivocationEvidence, Error, LatestBeefyBlock, SubstrateBridgeMessageLeafProof,
+    SubstrateBridgeMessageProof, TicketSeed, VersionedMessageProof,
+};
 use bridge_common::beefy_types::BeefyMMRLeaf;
 use bridge_common::beefy_types::ValidatorProof;
 use bridge_common::beefy_types::ValidatorSet;
-use bridge_common::bitfield::BitField;
+use bridge_common::simplified_proof::{MMRLeafProof, Proof};
+use bridge_types::traits::Verifier;
 use bridge_types::SubNetworkId;
 
-use crate::fixtures::{generate_fixture, Fixture};
-use codec::Decode;
-use frame_support::assert_ok;
+use crate::fixtures::{
+    full_validator_proof, generate_bench_fixture, generate_fixture, generate_leaf_proof,
+    validator_claims_bitfield, validator_proof, Fixture,
+};
+use crate::WeightInfo;
+use codec::{Decode, Encode};
+use frame_support::dispatch::{DispatchResultWithPostInfo, WithPostDispatchInfo};
+use frame_support::traits::{OnRuntimeUpgrade, StorageVersion};
+use frame_support::{assert_err, assert_noop, assert_ok};
 use hex_literal::hex;
+use sp_core::H256;
+use sp_runtime::traits::{Hash, Keccak256};
+use sp_std::collections::vec_deque::VecDeque;
 use test_case::test_case;
 
 fn alice<T: crate::Config>() -> T::AccountId {
     T::AccountId::decode(&mut [0u8; 32].as_slice()).unwrap()
 }
 
-fn validator_proof(
+fn initialize_with_fixture(fixture: &Fixture) {
+    assert_ok!(BeefyLightClient::initialize(
+        RuntimeOrigin::root(),
+        SubNetworkId::Mainnet,
+        0,
+        fixture.validator_set.clone().into(),
+        fixture.next_validator_set.clone().into(),
+    ));
+}
+
+fn decode_commitment(
     fixture: &Fixture,
-    signatures: Vec<Option<sp_beefy::crypto::Signature>>,
-    count: usize,
-) -> ValidatorProof {
-    let bits_to_set = signatures
-        .iter()
-        .enumerate()
-        .filter_map(|(i, x)| x.clone().map(|_| i as u32))
-        .take(count)
-        .collect::<Vec<_>>();
-    let initial_bitfield = BitField::create_bitfield(&bits_to_set, signatures.len());
-    let random_bitfield = BeefyLightClient::create_random_bit_field(
+) -> sp_beefy::SignedCommitment<u32, sp_beefy::crypto::Signature> {
+    Decode::decode(&mut &fixture.commitment[..]).unwrap()
+}
+
+fn submit_initial_commitment(
+    commitment: &sp_beefy::SignedCommitment<u32, sp_beefy::crypto::Signature>,
+    validators: usize,
+) -> DispatchResultWithPostInfo {
+    BeefyLightClient::submit_initial_commitment(
+        RuntimeOrigin::signed(alice::<Test>()),
         SubNetworkId::Mainnet,
-        initial_bitfield.clone(),
-        signatures.len() as u32,
+        Keccak256::hash_of(&commitment.commitment),
+        commitment.commitment.validator_set_id,
+        validator_claims_bitfield(&commitment.signatures, validators),
     )
-    .unwrap();
-    let mut positions = vec![];
-    let mut proof_signatures = vec![];
-    let mut public_keys = vec![];
-    let mut public_key_merkle_proofs = vec![];
-    for i in 0..random_bitfield.len() {
-        let bit = random_bitfield.is_set(i);
-        if bit {
-            positions.push(i as u128);
-            let mut signature = signatures.get(i).unwrap().clone().unwrap().to_vec();
-            signature[64] += 27;
-            proof_signatures.push(signature);
-            public_keys.push(fixture.addresses[i]);
-            public_key_merkle_proofs.push(fixture.validator_set_proofs[i].clone());
-        }
-    }
-    ValidatorProof {
-        signatures: proof_signatures,
-        positions,
-        public_keys,
-        public_key_merkle_proofs,
-        validator_claims_bitfield: initial_bitfield,
-    }
 }
 
 #[test_case(3, 5; "3 validators, 5 leaves")]
@@ -97,33 +98,359 @@ fn validator_proof(
 fn submit_fixture_success(validators: usize, tree_size: u32) {
     new_test_ext().execute_with(|| {
         let fixture = generate_fixture(validators, tree_size).expect("error generating fixture");
-        let validator_set = fixture.validator_set.clone().into();
-        let next_validator_set = fixture.next_validator_set.clone().into();
-        assert_ok!(BeefyLightClient::initialize(
-            RuntimeOrigin::root(),
-            SubNetworkId::Mainnet,
-            0,
-            validator_set,
-            next_validator_set
-        ));
+        initialize_with_fixture(&fixture);
 
-        let signed_commitment: sp_beefy::SignedCommitment<u32, sp_beefy::crypto::Signature> =
-            Decode::decode(&mut &fixture.commitment[..]).unwrap();
+        let signed_commitment = decode_commitment(&fixture);
         let commitment = signed_commitment.commitment.clone();
-        let validator_proof = validator_proof(&fixture, signed_commitment.signatures, validators);
+        let commitment_hash = Keccak256::hash_of(&commitment);
+        assert_ok!(submit_initial_commitment(&signed_commitment, validators));
+        run_to_block(1 + CommitmentRevealDelay::get());
+
+        let validator_proof = validator_proof::<Test, _>(
+            SubNetworkId::Mainnet,
+            alice::<Test>(),
+            commitment_hash,
+            &fixture,
+            &signed_commitment.signatures,
+            validators,
+        );
         let leaf: BeefyMMRLeaf = Decode::decode(&mut &fixture.leaf[..]).unwrap();
+        let leaf_proof: Proof<H256> = fixture.leaf_proof.clone().into();
 
+        assert_ok!(BeefyLightClient::verify_signature_commitment(
+            SubNetworkId::Mainnet,
+            &alice::<Test>(),
+            &commitment,
+            &validator_proof,
+            &leaf,
+            &leaf_proof.clone().into(),
+        ));
         assert_ok!(BeefyLightClient::submit_signature_commitment(
             RuntimeOrigin::signed(alice::<Test>()),
             SubNetworkId::Mainnet,
             commitment,
             validator_proof,
             leaf,
-            fixture.leaf_proof.into(),
+            leaf_proof,
+        ));
+        assert_eq!(
+            BeefyLightClient::ticket(SubNetworkId::Mainnet, (alice::<Test>(), commitment_hash)),
+            None
+        );
+        assert_eq!(
+            BeefyLightClient::ticket_seed(1 + CommitmentRevealDelay::get()),
+            None
+        );
+        assert_eq!(
+            BeefyLightClient::get_latest_mmr_root(SubNetworkId::Mainnet),
+            Some(fixture.mmr_root)
+        );
+    });
+}
+
+#[test_case(3, 5; "3 validators, 5 leaves")]
+#[test_case(37, 5000; "37 validators, 5000 leaves")]
+fn submit_fixture_with_mmr_proof_success(validators: usize, tree_size: u32) {
+    new_test_ext().execute_with(|| {
+        let fixture = generate_fixture(validators, tree_size).expect("error generating fixture");
+        initialize_with_fixture(&fixture);
+
+        let signed_commitment = decode_commitment(&fixture);
+        let commitment = signed_commitment.commitment.clone();
+        let commitment_hash = Keccak256::hash_of(&commitment);
+        assert_ok!(submit_initial_commitment(&signed_commitment, validators));
+        run_to_block(1 + CommitmentRevealDelay::get());
+
+        let validator_proof = validator_proof::<Test, _>(
+            SubNetworkId::Mainnet,
+            alice::<Test>(),
+            commitment_hash,
+            &fixture,
+            &signed_commitment.signatures,
+            validators,
+        );
+        let leaf: BeefyMMRLeaf = Decode::decode(&mut &fixture.leaf[..]).unwrap();
+        let leaf_mmr_proof = fixture.leaf_mmr_proof.clone().unwrap();
+        let mut invalid_leaf_mmr_proof = leaf_mmr_proof.clone();
+        invalid_leaf_mmr_proof.leaf_index = leaf_mmr_proof.leaf_count;
+
+        assert_noop!(
+            BeefyLightClient::verify_signature_commitment(
+                SubNetworkId::Mainnet,
+                &alice::<Test>(),
+                &commitment,
+                &validator_proof,
+                &leaf,
+                &invalid_leaf_mmr_proof.into(),
+            ),
+            Error::<Test>::InvalidMMRProof
+        );
+        assert_ok!(
+            BeefyLightClient::submit_signature_commitment_with_leaf_proof(
+                RuntimeOrigin::signed(alice::<Test>()),
+                SubNetworkId::Mainnet,
+                commitment,
+                validator_proof,
+                leaf.clone(),
+                leaf_mmr_proof.clone().into(),
+            )
+        );
+        assert_eq!(
+            BeefyLightClient::get_latest_mmr_root(SubNetworkId::Mainnet),
+            Some(fixture.mmr_root)
+        );
+        assert!(BeefyLightClient::verify_beefy_merkle_leaf(
+            SubNetworkId::Mainnet,
+            Keccak256::hash_of(&leaf),
+            &leaf_mmr_proof.into(),
+        ));
+    });
+}
+
+#[test]
+fn message_proof_keeps_simplified_proof_encoding() {
+    let fixture = generate_fixture(3, 5).expect("error generating fixture");
+    let proof: Proof<H256> = fixture.leaf_proof.clone().into();
+    let leaf: BeefyMMRLeaf = Decode::decode(&mut &fixture.leaf[..]).unwrap();
+    let digest = bridge_types::types::AuxiliaryDigest::default();
+    let encoded = (proof.clone(), leaf.clone(), digest.clone()).encode();
+    let message_proof = SubstrateBridgeMessageProof {
+        proof,
+        leaf,
+        digest,
+    };
+    assert_eq!(
+        SubstrateBridgeMessageProof::decode(&mut &encoded[..]),
+        Ok(message_proof.clone())
+    );
+    assert_eq!(
+        VersionedMessageProof::decode(&mut &[&[0u8][..], &encoded[..]].concat()[..]),
+        Ok(VersionedMessageProof::V1(message_proof))
+    );
+}
+
+#[test]
+fn message_proof_versions_are_verified() {
+    new_test_ext().execute_with(|| {
+        let network_id = SubNetworkId::Mainnet;
+        let commitment_hash = H256::repeat_byte(1);
+        let digest = bridge_types::types::AuxiliaryDigest {
+            logs: vec![bridge_types::types::AuxiliaryDigestItem::Commitment(
+                crate::ThisNetworkId::<Test>::get().into(),
+                commitment_hash,
+            )],
+        };
+        let verify = |proof: VersionedMessageProof| {
+            <BeefyLightClient as Verifier>::verify(network_id.into(), commitment_hash, &proof)
+        };
+
+        let (leaf, proof, root) = generate_leaf_proof(&digest, 3, Default::default());
+        let v1 = VersionedMessageProof::from(SubstrateBridgeMessageProof {
+            proof: proof.clone(),
+            leaf: leaf.clone(),
+            digest: digest.clone(),
+        });
+        let v2 = VersionedMessageProof::from(SubstrateBridgeMessageLeafProof {
+            proof: proof.into(),
+            leaf: leaf.clone(),
+            digest: digest.clone(),
+        });
+        assert_err!(verify(v1.clone()), Error::<Test>::InvalidMMRProof);
+        BeefyLightClient::add_known_mmr_root(network_id, 1, root);
+        assert_ok!(verify(v1));
+        assert_ok!(verify(v2));
+
+        // MMR of the single leaf, which is the root itself
+        let mmr_proof = |leaf_index| {
+            VersionedMessageProof::from(SubstrateBridgeMessageLeafProof {
+                proof: MMRLeafProof {
+                    leaf_index,
+                    leaf_count: 1,
+                    items: vec![],
+                }
+                .into(),
+                leaf: leaf.clone(),
+                digest: digest.clone(),
+            })
+        };
+        BeefyLightClient::add_known_mmr_root(network_id, 2, Keccak256::hash_of(&leaf));
+        assert_ok!(verify(mmr_proof(0)));
+        assert_err!(verify(mmr_proof(1)), Error::<Test>::InvalidMMRProof);
+    });
+}
+
+#[test]
+fn submit_signature_commitment_without_ticket_fails() {
+    new_test_ext().execute_with(|| {
+        let fixture = generate_fixture(3, 5).expect("error generating fixture");
+        initialize_with_fixture(&fixture);
+        let signed_commitment = decode_commitment(&fixture);
+        let leaf: BeefyMMRLeaf = Decode::decode(&mut &fixture.leaf[..]).unwrap();
+        let validator_proof = ValidatorProof {
+            validator_claims_bitfield: validator_claims_bitfield(&signed_commitment.signatures, 3),
+            signatures: vec![],
+            positions: vec![],
+            public_keys: vec![],
+            public_key_merkle_proofs: vec![],
+        };
+
+        assert_noop!(
+            BeefyLightClient::submit_signature_commitment(
+                RuntimeOrigin::signed(alice::<Test>()),
+                SubNetworkId::Mainnet,
+                signed_commitment.commitment,
+                validator_proof,
+                leaf,
+                fixture.leaf_proof.into(),
+            ),
+            Error::<Test>::TicketNotFound
+                .with_weight(<() as WeightInfo>::reject_signature_commitment())
+        );
+    });
+}
+
+#[test]
+fn submit_initial_commitment_checks_claims() {
+    new_test_ext().execute_with(|| {
+        let fixture = generate_fixture(3, 5).expect("error generating fixture");
+        initialize_with_fixture(&fixture);
+        let signed_commitment = decode_commitment(&fixture);
+
+        assert_noop!(
+            submit_initial_commitment(&signed_commitment, 1),
+            Error::<Test>::NotEnoughValidatorSignatures
+        );
+        assert_ok!(submit_initial_commitment(&signed_commitment, 3));
+        assert_noop!(
+            submit_initial_commitment(&signed_commitment, 3),
+            Error::<Test>::TicketAlreadyExists
+        );
+    });
+}
+
+#[test]
+fn ticket_randomness_is_pinned_after_delay() {
+    new_test_ext().execute_with(|| {
+        let fixture = generate_fixture(3, 5).expect("error generating fixture");
+        initialize_with_fixture(&fixture);
+        let signed_commitment = decode_commitment(&fixture);
+        let commitment_hash = Keccak256::hash_of(&signed_commitment.commitment);
+        assert_ok!(submit_initial_commitment(&signed_commitment, 3));
+
+        let randomness_block = 1 + CommitmentRevealDelay::get();
+        run_to_block(randomness_block - 1);
+        assert_err!(
+            BeefyLightClient::create_random_bit_field(
+                SubNetworkId::Mainnet,
+                alice::<Test>(),
+                commitment_hash
+            ),
+            Error::<Test>::TicketRandomnessNotReady
+        );
+        assert_eq!(
+            BeefyLightClient::ticket_seed(randomness_block),
+            Some(TicketSeed {
+                seed: None,
+                tickets: 1,
+                submitted_at: 1,
+            })
+        );
+
+        run_to_block(randomness_block);
+        assert!(BeefyLightClient::ticket_seed(randomness_block)
+            .unwrap()
+            .seed
+            .is_some());
+        assert_ok!(BeefyLightClient::create_random_bit_field(
+            SubNetworkId::Mainnet,
+            alice::<Test>(),
+            commitment_hash
+        ));
+    });
+}
+
+#[test]
+fn ticket_randomness_is_not_pinned_before_submission() {
+    new_test_ext().execute_with(|| {
+        // Randomness source is not updated after the ticket submission by the randomness block
+        RandomnessLag::set(CommitmentRevealDelay::get());
+        let fixture = generate_fixture(3, 5).expect("error generating fixture");
+        initialize_with_fixture(&fixture);
+        let signed_commitment = decode_commitment(&fixture);
+        let commitment_hash = Keccak256::hash_of(&signed_commitment.commitment);
+        assert_ok!(submit_initial_commitment(&signed_commitment, 3));
+
+        let randomness_block = 1 + CommitmentRevealDelay::get();
+        run_to_block(randomness_block);
+        assert_eq!(
+            BeefyLightClient::ticket_seed(randomness_block),
+            Some(TicketSeed {
+                seed: None,
+                tickets: 1,
+                submitted_at: 1,
+            })
+        );
+        assert_eq!(
+            BeefyLightClient::pending_ticket_seeds(),
+            vec![randomness_block]
+        );
+        assert_err!(
+            BeefyLightClient::create_random_bit_field(
+                SubNetworkId::Mainnet,
+                alice::<Test>(),
+                commitment_hash
+            ),
+            Error::<Test>::TicketRandomnessNotReady
+        );
+
+        run_to_block(randomness_block + 1);
+        assert!(BeefyLightClient::ticket_seed(randomness_block)
+            .unwrap()
+            .seed
+            .is_some());
+        assert!(BeefyLightClient::pending_ticket_seeds().is_empty());
+        assert_ok!(BeefyLightClient::create_random_bit_field(
+            SubNetworkId::Mainnet,
+            alice::<Test>(),
+            commitment_hash
         ));
     });
 }
 
+#[test]
+fn ticket_expires() {
+    new_test_ext().execute_with(|| {
+        let fixture = generate_fixture(3, 5).expect("error generating fixture");
+        initialize_with_fixture(&fixture);
+        let signed_commitment = decode_commitment(&fixture);
+        let commitment_hash = Keccak256::hash_of(&signed_commitment.commitment);
+        assert_ok!(submit_initial_commitment(&signed_commitment, 3));
+
+        let randomness_block = 1 + CommitmentRevealDelay::get();
+        let expires_at = randomness_block + TicketLifetime::get();
+        run_to_block(expires_at - 1);
+        assert!(BeefyLightClient::ticket(
+            SubNetworkId::Mainnet,
+            (alice::<Test>(), commitment_hash)
+        )
+        .is_some());
+
+        run_to_block(expires_at);
+        assert_eq!(
+            BeefyLightClient::ticket(SubNetworkId::Mainnet, (alice::<Test>(), commitment_hash)),
+            None
+        );
+        assert_eq!(BeefyLightClient::ticket_seed(randomness_block), None);
+        System::assert_last_event(
+            crate::Event::TicketExpired(SubNetworkId::Mainnet, alice::<Test>(), commitment_hash)
+                .into(),
+        );
+
+        // Ticket can be claimed again after expiration
+        assert_ok!(submit_initial_commitment(&signed_commitment, 3));
+    });
+}
+
 #[test]
 fn it_works_initialize_pallet() {
     new_test_ext().execute_with(|| {
@@ -148,3 +475,463 @@ fn it_works_initialize_pallet() {
         )
     });
 }
+
+#[test]
+fn mmr_roots_history_is_bounded() {
+    new_test_ext().execute_with(|| {
+        let network_id = SubNetworkId::Mainnet;
+        let root = |i: u64| H256::from_low_u64_be(i);
+        assert_eq!(BeefyLightClient::get_latest_mmr_root(network_id), None);
+
+        for i in 1..=7 {
+            BeefyLightClient::add_known_mmr_root(network_id, i * 10, root(i));
+        }
+        assert_eq!(
+            BeefyLightClient::get_mmr_roots(network_id),
+            (3..=7).map(|i| (i * 10, root(i))).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            BeefyLightClient::get_latest_mmr_root(network_id),
+            Some(root(7))
+        );
+        assert!(!BeefyLightClient::is_known_root(network_id, root(2)));
+        assert_eq!(BeefyLightClient::mmr_root(network_id, 20), None);
+        assert_eq!(
+            BeefyLightClient::mmr_root_block(network_id, root(3)),
+            Some(30)
+        );
+
+        // Root added again is kept after the older entry is removed
+        BeefyLightClient::add_known_mmr_root(network_id, 80, root(3));
+        assert!(BeefyLightClient::is_known_root(network_id, root(3)));
+        assert_eq!(
+            BeefyLightClient::mmr_root_block(network_id, root(3)),
+            Some(80)
+        );
+
+        // Decreased history size removes all exceeding roots
+        MMRRootHistorySize::set(2);
+        BeefyLightClient::add_known_mmr_root(network_id, 90, root(9));
+        assert_eq!(
+            BeefyLightClient::get_mmr_roots(network_id),
+            vec![(80, root(3)), (90, root(9))]
+        );
+        assert!(!BeefyLightClient::is_known_root(network_id, root(7)));
+    });
+}
+
+#[test]
+fn mmr_roots_are_migrated_to_v1() {
+    new_test_ext().execute_with(|| {
+        let network_id = SubNetworkId::Mainnet;
+        let root = |i: u64| H256::from_low_u64_be(i);
+        StorageVersion::new(0).put::<BeefyLightClient>();
+        LatestBeefyBlock::<Test>::insert(network_id, 100);
+        // Roots are stored from the latest
+        migrations::v1::LatestMMRRoots::<Test>::insert(
+            network_id,
+            (1..=6).rev().map(root).collect::<VecDeque<_>>(),
+        );
+
+        migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+        assert_eq!(StorageVersion::get::<BeefyLightClient>(), 1);
+        assert!(!migrations::v1::LatestMMRRoots::<Test>::contains_key(
+            network_id
+        ));
+        // Only the latest root has a known BEEFY block number
+        assert_eq!(
+            BeefyLightClient::get_mmr_roots(network_id),
+            vec![(100, root(6))]
+        );
+        assert_eq!(
+            BeefyLightClient::mmr_root_block(network_id, root(6)),
+            Some(100)
+        );
+        assert!(!BeefyLightClient::is_known_root(network_id, root(5)));
+
+        // Migration is applied only once
+        migrations::v1::LatestMMRRoots::<Test>::insert(network_id, VecDeque::from([root(7)]));
+        migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+        assert!(!BeefyLightClient::is_known_root(network_id, root(7)));
+    });
+}
+
+#[test]
+fn verify_signature_commitment_returns_error() {
+    new_test_ext().execute_with(|| {
+        let fixture = generate_fixture(3, 5).expect("error generating fixture");
+        initialize_with_fixture(&fixture);
+        let signed_commitment = decode_commitment(&fixture);
+        let commitment = signed_commitment.commitment.clone();
+        let commitment_hash = Keccak256::hash_of(&commitment);
+        assert_ok!(submit_initial_commitment(&signed_commitment, 3));
+        run_to_block(1 + CommitmentRevealDelay::get());
+
+        let mut validator_proof = validator_proof::<Test, _>(
+            SubNetworkId::Mainnet,
+            alice::<Test>(),
+            commitment_hash,
+            &fixture,
+            &signed_commitment.signatures,
+            3,
+        );
+        validator_proof.signatures[0][0] ^= 1;
+        let leaf: BeefyMMRLeaf = Decode::decode(&mut &fixture.leaf[..]).unwrap();
+
+        assert_noop!(
+            BeefyLightClient::verify_signature_commitment(
+                SubNetworkId::Mainnet,
+                &alice::<Test>(),
+                &commitment,
+                &validator_proof,
+                &leaf,
+                &fixture.leaf_proof.into(),
+            ),
+            Error::<Test>::InvalidSignature
+        );
+    });
+}
+
+fn report_equivocation(first: &Fixture, second: &Fixture) -> DispatchResultWithPostInfo {
+    let first_commitment = decode_commitment(first);
+    let second_commitment = decode_commitment(second);
+    BeefyLightClient::report_equivocation(
+        RuntimeOrigin::signed(alice::<Test>()),
+        SubNetworkId::Mainnet,
+        first_commitment.commitment.clone(),
+        full_validator_proof(first, &first_commitment.signatures),
+        second_commitment.commitment.clone(),
+        full_validator_proof(second, &second_commitment.signatures),
+    )
+}
+
+#[test]
+fn report_equivocation_freezes_verification() {
+    new_test_ext().execute_with(|| {
+        let first = generate_fixture(3, 5).expect("error generating fixture");
+        let second = generate_fixture(3, 5).expect("error generating fixture");
+        initialize_with_fixture(&first);
+
+        assert_ok!(report_equivocation(&first, &second));
+        let first_commitment = decode_commitment(&first).commitment;
+        let second_commitment = decode_commitment(&second).commitment;
+        assert_eq!(
+            BeefyLightClient::equivocation(SubNetworkId::Mainnet, (0u64, 5u32)),
+            Some(EquivocationEvidence {
+                mmr_roots: (first.mmr_root, second.mmr_root),
+                commitment_hashes: (
+                    Keccak256::hash_of(&first_commitment),
+                    Keccak256::hash_of(&second_commitment)
+                ),
+                reporter: alice::<Test>(),
+            })
+        );
+        assert!(BeefyLightClient::is_frozen(SubNetworkId::Mainnet));
+        System::assert_has_event(
+            crate::Event::EquivocationReported(SubNetworkId::Mainnet, 0, 5, alice::<Test>()).into(),
+        );
+        System::assert_last_event(crate::Event::VerificationFrozen(SubNetworkId::Mainnet).into());
+
+        assert_noop!(
+            submit_initial_commitment(&decode_commitment(&first), 3),
+            Error::<Test>::NetworkFrozen
+        );
+        assert_err!(
+            <BeefyLightClient as Verifier>::verify(
+                SubNetworkId::Mainnet.into(),
+                H256::zero(),
+                &SubstrateBridgeMessageProof {
+                    proof: first.leaf_proof.clone().into(),
+                    leaf: Decode::decode(&mut &first.leaf[..]).unwrap(),
+                    digest: Default::default(),
+                }
+                .into(),
+            ),
+            Error::<Test>::NetworkFrozen
+        );
+
+        initialize_with_fixture(&first);
+        assert!(!BeefyLightClient::is_frozen(SubNetworkId::Mainnet));
+        assert_ok!(submit_initial_commitment(&decode_commitment(&first), 3));
+    });
+}
+
+#[test]
+fn report_equivocation_without_freeze() {
+    new_test_ext().execute_with(|| {
+        FreezeOnEquivocation::set(false);
+        let first = generate_fixture(3, 5).expect("error generating fixture");
+        let second = generate_fixture(3, 5).expect("error generating fixture");
+        initialize_with_fixture(&first);
+
+        assert_ok!(report_equivocation(&first, &second));
+        assert!(BeefyLightClient::equivocation(SubNetworkId::Mainnet, (0u64, 5u32)).is_some());
+        assert!(!BeefyLightClient::is_frozen(SubNetworkId::Mainnet));
+        assert_noop!(
+            report_equivocation(&second, &first),
+            Error::<Test>::EquivocationAlreadyReported
+        );
+    });
+}
+
+#[test]
+fn report_equivocation_checks_commitments() {
+    new_test_ext().execute_with(|| {
+        let first = generate_fixture(3, 5).expect("error generating fixture");
+        let second = generate_fixture(3, 5).expect("error generating fixture");
+        let other_block = generate_fixture(3, 6).expect("error generating fixture");
+        initialize_with_fixture(&first);
+
+        assert_noop!(
+            report_equivocation(&first, &first),
+            Error::<Test>::CommitmentsNotConflicting
+        );
+        assert_noop!(
+            report_equivocation(&first, &other_block),
+            Error::<Test>::CommitmentsNotConflicting
+        );
+
+        let first_commitment = decode_commitment(&first);
+        let second_commitment = decode_commitment(&second);
+        let mut second_proof = full_validator_proof(&second, &second_commitment.signatures);
+        second_proof.signatures[0][0] ^= 1;
+        assert_noop!(
+            BeefyLightClient::report_equivocation(
+                RuntimeOrigin::signed(alice::<Test>()),
+                SubNetworkId::Mainnet,
+                first_commitment.commitment.clone(),
+                full_validator_proof(&first, &first_commitment.signatures),
+                second_commitment.commitment,
+                second_proof,
+            ),
+            Error::<Test>::InvalidSignature
+        );
+    });
+}
+
+/// Submit the commitment of the fixture signed by all 3 validators in both phases.
+fn submit_commitment(fixture: &Fixture) -> DispatchResultWithPostInfo {
+    let signed_commitment = decode_commitment(fixture);
+    submit_initial_commitment(&signed_commitment, 3)?;
+    run_to_block(System::block_number() + CommitmentRevealDelay::get());
+    reveal_commitment(fixture, alice::<Test>())
+}
+
+/// Second phase of the submission of the fixture signed by all 3 validators.
+fn reveal_commitment(fixture: &Fixture, relayer: u64) -> DispatchResultWithPostInfo {
+    let signed_commitment = decode_commitment(fixture);
+    let commitment_hash = Keccak256::hash_of(&signed_commitment.commitment);
+    let validator_proof = validator_proof::<Test, _>(
+        SubNetworkId::Mainnet,
+        relayer,
+        commitment_hash,
+        fixture,
+        &signed_commitment.signatures,
+        3,
+    );
+    BeefyLightClient::submit_signature_commitment(
+        RuntimeOrigin::signed(relayer),
+        SubNetworkId::Mainnet,
+        signed_commitment.commitment,
+        validator_proof,
+        Decode::decode(&mut &fixture.leaf[..]).unwrap(),
+        fixture.leaf_proof.clone().into(),
+    )
+}
+
+#[test]
+fn validator_sets_catch_up_with_mandatory_commitments() {
+    new_test_ext().execute_with(|| {
+        let fixtures = (1..=4)
+            .map(|id| generate_bench_fixture(id, 3, 1, id as u32, &Default::default()))
+            .collect::<Vec<_>>();
+        let initial_validator_set = ValidatorSet {
+            id: 0,
+            len: 3,
+            root: H256::repeat_byte(1),
+        };
+        assert_ok!(BeefyLightClient::initialize(
+            RuntimeOrigin::root(),
+            SubNetworkId::Mainnet,
+            0,
+            initial_validator_set.clone(),
+            fixtures[0].validator_set.clone().into(),
+        ));
+
+        assert_noop!(
+            submit_commitment(&fixtures[1]),
+            Error::<Test>::ValidatorSetIdTooFarAhead
+        );
+        for fixture in fixtures.iter() {
+            assert_ok!(submit_commitment(fixture));
+            let validator_set: ValidatorSet = fixture.validator_set.clone().into();
+            let next_validator_set: ValidatorSet = fixture.next_validator_set.clone().into();
+            assert_eq!(
+                BeefyLightClient::current_validator_set(SubNetworkId::Mainnet),
+                Some(validator_set)
+            );
+            assert_eq!(
+                BeefyLightClient::next_validator_set(SubNetworkId::Mainnet),
+                Some(next_validator_set.clone())
+            );
+            System::assert_last_event(
+                crate::Event::ValidatorRegistryUpdated(
+                    SubNetworkId::Mainnet,
+                    next_validator_set.root,
+                    next_validator_set.len,
+                    next_validator_set.id,
+                )
+                .into(),
+            );
+        }
+        assert_eq!(
+            BeefyLightClient::validator_sets_history(SubNetworkId::Mainnet)
+                .into_iter()
+                .map(|vset| vset.id)
+                .collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(
+            BeefyLightClient::known_validator_set(SubNetworkId::Mainnet, 2),
+            Ok(fixtures[1].validator_set.clone().into())
+        );
+        assert_eq!(
+            BeefyLightClient::known_validator_set(SubNetworkId::Mainnet, 1),
+            Err(Error::<Test>::InvalidValidatorSetId.into())
+        );
+        assert_eq!(
+            BeefyLightClient::validator_set(SubNetworkId::Mainnet, 2),
+            Err(Error::<Test>::InvalidValidatorSetId.into())
+        );
+
+        initialize_with_fixture(&fixtures[0]);
+        assert!(BeefyLightClient::validator_sets_history(SubNetworkId::Mainnet).is_empty());
+    });
+}
+
+#[test]
+fn report_equivocation_of_past_validator_set() {
+    new_test_ext().execute_with(|| {
+        let first = generate_bench_fixture(1, 3, 1, 1, &Default::default());
+        let second = generate_bench_fixture(
+            1,
+            3,
+            1,
+            1,
+            &bridge_types::types::AuxiliaryDigest {
+                logs: vec![bridge_types::types::AuxiliaryDigestItem::Commitment(
+                    SubNetworkId::Mainnet.into(),
+                    H256::repeat_byte(1),
+                )],
+            },
+        );
+        let handoff = generate_bench_fixture(2, 3, 1, 2, &Default::default());
+        initialize_with_fixture(&first);
+        assert_ok!(submit_commitment(&handoff));
+        assert_eq!(
+            BeefyLightClient::validator_sets_history(SubNetworkId::Mainnet)
+                .into_iter()
+                .map(|vset| vset.id)
+                .collect::<Vec<_>>(),
+            vec![1]
+        );
+
+        assert_ok!(report_equivocation(&first, &second));
+        System::assert_has_event(
+            crate::Event::EquivocationReported(SubNetworkId::Mainnet, 1, 1, alice::<Test>()).into(),
+        );
+    });
+}
+
+#[test]
+fn relayer_rewarded_for_commitment() {
+    new_test_ext().execute_with(|| {
+        let fixture = generate_fixture(3, 5).expect("error generating fixture");
+        initialize_with_fixture(&fixture);
+        assert_ok!(submit_commitment(&fixture));
+
+        assert_eq!(
+            Balances::free_balance(alice::<Test>()),
+            RelayerReward::get()
+        );
+        assert_eq!(
+            Balances::free_balance(TreasuryAccountId::get()),
+            1_000 - RelayerReward::get()
+        );
+        System::assert_has_event(
+            crate::Event::RelayerRewarded(
+                SubNetworkId::Mainnet,
+                alice::<Test>(),
+                RelayerReward::get(),
+            )
+            .into(),
+        );
+    });
+}
+
+#[test]
+fn relayer_not_rewarded_below_min_block_gap() {
+    new_test_ext().execute_with(|| {
+        let fixture = generate_fixture(3, 5).expect("error generating fixture");
+        assert_ok!(BeefyLightClient::initialize(
+            RuntimeOrigin::root(),
+            SubNetworkId::Mainnet,
+            5 - RewardMinBlockGap::get() + 1,
+            fixture.validator_set.clone().into(),
+            fixture.next_validator_set.clone().into(),
+        ));
+        assert_ok!(submit_commitment(&fixture));
+
+        assert_eq!(
+            BeefyLightClient::latest_beefy_block(SubNetworkId::Mainnet),
+            5
+        );
+        assert_eq!(Balances::free_balance(alice::<Test>()), 0);
+        assert_eq!(Balances::free_balance(TreasuryAccountId::get()), 1_000);
+    });
+}
+
+#[test]
+fn commitment_accepted_when_treasury_cannot_pay() {
+    new_test_ext().execute_with(|| {
+        RelayerReward::set(1_000);
+        let fixture = generate_fixture(3, 5).expect("error generating fixture");
+        initialize_with_fixture(&fixture);
+        assert_ok!(submit_commitment(&fixture));
+
+        assert_eq!(
+            BeefyLightClient::latest_beefy_block(SubNetworkId::Mainnet),
+            5
+        );
+        assert_eq!(Balances::free_balance(alice::<Test>()), 0);
+    });
+}
+
+#[test]
+fn racing_commitment_rejected_before_signature_checks() {
+    new_test_ext().execute_with(|| {
+        let bob = 1;
+        let fixture = generate_fixture(3, 5).expect("error generating fixture");
+        initialize_with_fixture(&fixture);
+        let signed_commitment = decode_commitment(&fixture);
+        for relayer in [alice::<Test>(), bob] {
+            assert_ok!(BeefyLightClient::submit_initial_commitment(
+                RuntimeOrigin::signed(relayer),
+                SubNetworkId::Mainnet,
+                Keccak256::hash_of(&signed_commitment.commitment),
+                signed_commitment.commitment.validator_set_id,
+                validator_claims_bitfield(&signed_commitment.signatures, 3),
+            ));
+        }
+        run_to_block(1 + CommitmentRevealDelay::get());
+
+        assert_ok!(reveal_commitment(&fixture, alice::<Test>()));
+        assert_noop!(
+            reveal_commitment(&fixture, bob),
+            Error::<Test>::PayloadBlocknumberTooOld
+                .with_weight(<() as WeightInfo>::reject_signature_commitment())
+        );
+        assert_eq!(Balances::free_balance(bob), 0);
+    });
+}
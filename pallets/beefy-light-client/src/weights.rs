@@ -0,0 +1,172 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Weights for beefy_light_client
+//!
+//! There is no benchmark run for this pallet yet, so the values are derived by hand:
+//! a signature costs about twice the ECDSA recovery measured for `multisig_verifier`
+//! (39_360_417 per signature), storage access counts are taken from `benchmarking.rs`.
+//! This file must be replaced with the output of the command below.
+
+// Command to generate:
+// ./target/release/framenode
+// benchmark
+// pallet
+// --chain=local
+// --steps=50
+// --repeat=20
+// --pallet=beefy_light_client
+// --extrinsic=*
+// --header=./misc/file_header.txt
+// --template=./misc/pallet-weight-template.hbs
+// --output=./beefy-light-client.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use core::marker::PhantomData;
+
+/// Weight functions needed for beefy_light_client.
+pub trait WeightInfo {
+	fn initialize() -> Weight;
+	fn submit_signature_commitment(a: u32, b: u32, ) -> Weight;
+	fn verifier_verify(a: u32, b: u32, ) -> Weight;
+	fn submit_initial_commitment() -> Weight;
+	fn on_initialize(a: u32, ) -> Weight;
+	fn report_equivocation(a: u32, ) -> Weight;
+	fn reject_signature_commitment() -> Weight;
+}
+
+/// Weights for beefy_light_client using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	fn initialize() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// `a` is the number of signatures checked and `b` the number of validator proof items.
+	/// Until the `submit_signature_commitment` benchmark is run, a signature is charged 81_500_000.
+	fn submit_signature_commitment(a: u32, b: u32, ) -> Weight {
+		Weight::from_parts(68_000_000, 14475)
+			.saturating_add(Weight::from_parts(81_500_000, 0).saturating_mul(a.into()))
+			.saturating_add(Weight::from_parts(3_200_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(13_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+	}
+	/// `a` is the number of leaf proof items and `b` the number of digest items.
+	/// Until the `verifier_verify` benchmark is run, every item is charged as a Keccak hash.
+	fn verifier_verify(a: u32, b: u32, ) -> Weight {
+		Weight::from_parts(25_000_000, 5229)
+			.saturating_add(Weight::from_parts(3_100_000, 0).saturating_mul(a.into()))
+			.saturating_add(Weight::from_parts(1_500_000, 0).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+	}
+	fn submit_initial_commitment() -> Weight {
+		Weight::from_parts(34_000_000, 14305)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// `a` is the number of expired tickets removed.
+	fn on_initialize(a: u32, ) -> Weight {
+		Weight::from_parts(14_600_000, 8331)
+			.saturating_add(Weight::from_parts(9_800_000, 2598).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(a.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(a.into())))
+	}
+	/// `a` is the number of validator signatures checked in both commitments.
+	/// Until the `report_equivocation` benchmark is run, a signature is charged the same
+	/// as in `submit_signature_commitment`.
+	fn report_equivocation(a: u32, ) -> Weight {
+		Weight::from_parts(41_300_000, 8211)
+			.saturating_add(Weight::from_parts(81_500_000, 0).saturating_mul(a.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	fn reject_signature_commitment() -> Weight {
+		Weight::from_parts(19_000_000, 5067)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn initialize() -> Weight {
+		Weight::from_parts(15_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// `a` is the number of signatures checked and `b` the number of validator proof items.
+	/// Until the `submit_signature_commitment` benchmark is run, a signature is charged 81_500_000.
+	fn submit_signature_commitment(a: u32, b: u32, ) -> Weight {
+		Weight::from_parts(68_000_000, 14475)
+			.saturating_add(Weight::from_parts(81_500_000, 0).saturating_mul(a.into()))
+			.saturating_add(Weight::from_parts(3_200_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(13_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+	}
+	/// `a` is the number of leaf proof items and `b` the number of digest items.
+	/// Until the `verifier_verify` benchmark is run, every item is charged as a Keccak hash.
+	fn verifier_verify(a: u32, b: u32, ) -> Weight {
+		Weight::from_parts(25_000_000, 5229)
+			.saturating_add(Weight::from_parts(3_100_000, 0).saturating_mul(a.into()))
+			.saturating_add(Weight::from_parts(1_500_000, 0).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+	}
+	fn submit_initial_commitment() -> Weight {
+		Weight::from_parts(34_000_000, 14305)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// `a` is the number of expired tickets removed.
+	fn on_initialize(a: u32, ) -> Weight {
+		Weight::from_parts(14_600_000, 8331)
+			.saturating_add(Weight::from_parts(9_800_000, 2598).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(a.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(a.into())))
+	}
+	/// `a` is the number of validator signatures checked in both commitments.
+	/// Until the `report_equivocation` benchmark is run, a signature is charged the same
+	/// as in `submit_signature_commitment`.
+	fn report_equivocation(a: u32, ) -> Weight {
+		Weight::from_parts(41_300_000, 8211)
+			.saturating_add(Weight::from_parts(81_500_000, 0).saturating_mul(a.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	fn reject_signature_commitment() -> Weight {
+		Weight::from_parts(19_000_000, 5067)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+	}
+}
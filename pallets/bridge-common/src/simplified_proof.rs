@@ -58,6 +58,129 @@ impl<T: Clone> Proof<T> {
     }
 }
 
+/// Maximum number of the leaves in the MMR, which keeps the MMR positions in `u64`.
+pub const MAX_MMR_LEAF_COUNT: u64 = 1 << 62;
+
+/// Maximum number of the [`MMRLeafProof`] items.
+pub const MAX_MMR_PROOF_ITEMS: usize = 64;
+
+/// MMR leaf proof in the standard format, as generated by `sp_mmr_primitives`.
+#[derive(
+    Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, PartialOrd, Ord, scale_info::TypeInfo,
+)]
+pub struct MMRLeafProof<T> {
+    pub leaf_index: u64,
+    pub leaf_count: u64,
+    /// Left peaks, merkle path of the leaf in its peak and bagged right peaks.
+    pub items: Vec<T>,
+}
+
+impl<T: Clone> MMRLeafProof<T> {
+    /// Calculate the MMR root by bagging the peaks. Returns `None` for malformed proof.
+    pub fn root(&self, hash: impl Fn(T, T) -> T, leaf_hash: T) -> Option<T> {
+        if self.leaf_index >= self.leaf_count
+            || self.leaf_count >= MAX_MMR_LEAF_COUNT
+            || self.items.len() > MAX_MMR_PROOF_ITEMS
+        {
+            return None;
+        }
+        let leaf_pos = leaf_index_to_pos(self.leaf_index);
+        let mut items = self.items.iter().cloned();
+        let mut peak_hashes = vec![];
+        for peak_pos in get_peaks(checked_leaf_count_to_mmr_size(self.leaf_count)?) {
+            if peak_pos < leaf_pos {
+                peak_hashes.push(items.next()?);
+                continue;
+            }
+            peak_hashes.push(Self::peak_root(
+                &hash, leaf_hash, leaf_pos, peak_pos, &mut items,
+            )?);
+            // Right peaks are bagged into one item
+            if let Some(right_peaks) = items.next() {
+                peak_hashes.push(right_peaks);
+            }
+            break;
+        }
+        if items.next().is_some() {
+            return None;
+        }
+        // Peaks are bagged from right to left via hash(right, left)
+        let mut root = peak_hashes.pop()?;
+        while let Some(left_peak) = peak_hashes.pop() {
+            root = hash(root, left_peak);
+        }
+        Some(root)
+    }
+
+    fn peak_root(
+        hash: &impl Fn(T, T) -> T,
+        leaf_hash: T,
+        leaf_pos: u64,
+        peak_pos: u64,
+        items: &mut impl Iterator<Item = T>,
+    ) -> Option<T> {
+        let mut pos = leaf_pos;
+        let mut height = 0;
+        let mut current_hash = leaf_hash;
+        while pos < peak_pos {
+            let sibling = items.next()?;
+            if pos_height_in_tree(pos + 1) > height {
+                // Current node is the right child
+                current_hash = hash(sibling, current_hash);
+                pos += 1;
+            } else {
+                current_hash = hash(current_hash, sibling);
+                pos = pos.checked_add(checked_parent_offset(height)?)?;
+            }
+            height += 1;
+        }
+        (pos == peak_pos).then_some(current_hash)
+    }
+}
+
+/// MMR leaf proof in any of the supported formats.
+#[derive(
+    Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, PartialOrd, Ord, scale_info::TypeInfo,
+)]
+pub enum LeafProof<T> {
+    Simplified(Proof<T>),
+    MMR(MMRLeafProof<T>),
+}
+
+impl<T: Clone> LeafProof<T> {
+    /// Calculate the MMR root. Returns `None` for malformed proof.
+    pub fn root(&self, hash: impl Fn(T, T) -> T, leaf_hash: T) -> Option<T> {
+        match self {
+            LeafProof::Simplified(proof) => Some(proof.root(hash, leaf_hash)),
+            LeafProof::MMR(proof) => proof.root(hash, leaf_hash),
+        }
+    }
+
+    /// Number of the proof items.
+    pub fn len(&self) -> usize {
+        match self {
+            LeafProof::Simplified(proof) => proof.items.len(),
+            LeafProof::MMR(proof) => proof.items.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> From<Proof<T>> for LeafProof<T> {
+    fn from(proof: Proof<T>) -> Self {
+        LeafProof::Simplified(proof)
+    }
+}
+
+impl<T> From<MMRLeafProof<T>> for LeafProof<T> {
+    fn from(proof: MMRLeafProof<T>) -> Self {
+        LeafProof::MMR(proof)
+    }
+}
+
 pub fn leaf_index_to_pos(index: u64) -> u64 {
     leaf_index_to_mmr_size(index) - (index + 1).trailing_zeros() as u64 - 1
 }
@@ -67,6 +190,12 @@ pub fn leaf_count_to_mmr_size(leaves_count: u64) -> u64 {
     2 * leaves_count - peak_count
 }
 
+/// Same as [`leaf_count_to_mmr_size`], but returns `None` on overflow.
+pub fn checked_leaf_count_to_mmr_size(leaves_count: u64) -> Option<u64> {
+    let peak_count = leaves_count.count_ones() as u64;
+    leaves_count.checked_mul(2)?.checked_sub(peak_count)
+}
+
 fn leaf_index_to_mmr_size(index: u64) -> u64 {
     leaf_count_to_mmr_size(index + 1)
 }
@@ -93,6 +222,11 @@ pub fn parent_offset(height: u32) -> u64 {
     2 << height
 }
 
+/// Same as [`parent_offset`], but returns `None` on overflow.
+pub fn checked_parent_offset(height: u32) -> Option<u64> {
+    1u64.checked_shl(height.checked_add(1)?)
+}
+
 pub fn sibling_offset(height: u32) -> u64 {
     (2 << height) - 1
 }
@@ -159,7 +293,6 @@ fn calculate_merkle_proof_order<T>(leave: u64, proof: &Vec<T>) -> u64 {
     order
 }
 
-#[allow(dead_code)]
 pub fn convert_to_simplified_mmr_proof<T: Clone>(
     leaf_index: u64,
     leaf_count: u64,
@@ -233,6 +366,13 @@ pub fn verify_inclusion_proof(root: H256, leaf_node_hash: H256, proof: &Proof<H2
     root == proof.root(hasher, leaf_node_hash)
 }
 
+pub fn verify_leaf_proof(root: H256, leaf_node_hash: H256, proof: &LeafProof<H256>) -> bool {
+    match proof {
+        LeafProof::Simplified(proof) => verify_inclusion_proof(root, leaf_node_hash, proof),
+        LeafProof::MMR(proof) => proof.root(hasher, leaf_node_hash) == Some(root),
+    }
+}
+
 pub fn bit(self_val: u64, index: u64) -> bool {
     ((self_val >> index) & 1) as u8 == 1
 }
@@ -244,12 +384,131 @@ pub fn hasher(a: H256, b: H256) -> H256 {
     let res = [a.as_bytes(), b.as_bytes()].concat();
     Keccak256::hash(&res)
 }
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use bridge_types::H256;
+    use mmr_lib::{util::MemStore, MMR};
+    use proptest::prelude::*;
     use serde::{Deserialize, Serialize};
 
+    struct KeccakMerge;
+
+    impl mmr_lib::Merge for KeccakMerge {
+        type Item = H256;
+
+        fn merge(left: &H256, right: &H256) -> core::result::Result<H256, mmr_lib::Error> {
+            Ok(hasher(*left, *right))
+        }
+    }
+
+    /// Build MMR with `leaf_count` leaves and return its root, the leaf and native proof items.
+    fn generate_mmr_proof(seed: u64, leaf_count: u64, leaf_index: u64) -> (H256, H256, Vec<H256>) {
+        let store = MemStore::default();
+        let mut mmr = MMR::<H256, KeccakMerge, _>::new(0, &store);
+        let leaves = (0..leaf_count)
+            .map(|i| hasher(H256::from_low_u64_be(seed), H256::from_low_u64_be(i)))
+            .collect::<Vec<_>>();
+        for leaf in leaves.iter() {
+            mmr.push(*leaf).unwrap();
+        }
+        let root = mmr.get_root().unwrap();
+        let items = mmr
+            .gen_proof(vec![mmr_lib::leaf_index_to_pos(leaf_index)])
+            .unwrap()
+            .proof_items()
+            .to_vec();
+        (root, leaves[leaf_index as usize], items)
+    }
+
+    proptest! {
+        #[test]
+        fn mmr_and_simplified_proofs_match(
+            seed in any::<u64>(),
+            leaf_count in 1u64..300,
+            index_seed in any::<u64>(),
+        ) {
+            let leaf_index = index_seed % leaf_count;
+            let (root, leaf, items) = generate_mmr_proof(seed, leaf_count, leaf_index);
+            let mmr_proof = MMRLeafProof {
+                leaf_index,
+                leaf_count,
+                items: items.clone(),
+            };
+            let simplified_proof = convert_to_simplified_mmr_proof(leaf_index, leaf_count, &items);
+            prop_assert_eq!(mmr_proof.root(hasher, leaf), Some(root));
+            prop_assert_eq!(simplified_proof.root(hasher, leaf), root);
+            prop_assert!(verify_leaf_proof(root, leaf, &mmr_proof.clone().into()));
+            prop_assert!(verify_leaf_proof(root, leaf, &simplified_proof.into()));
+            prop_assert!(!verify_leaf_proof(root, H256::repeat_byte(1), &mmr_proof.into()));
+        }
+
+        #[test]
+        fn malformed_mmr_proof_rejected(
+            seed in any::<u64>(),
+            leaf_count in 1u64..300,
+            index_seed in any::<u64>(),
+        ) {
+            let leaf_index = index_seed % leaf_count;
+            let (root, leaf, items) = generate_mmr_proof(seed, leaf_count, leaf_index);
+            let mut extra_items = items.clone();
+            extra_items.push(H256::repeat_byte(1));
+            let extra_item_proof = MMRLeafProof {
+                leaf_index,
+                leaf_count,
+                items: extra_items,
+            };
+            prop_assert_ne!(extra_item_proof.root(hasher, leaf), Some(root));
+            if !items.is_empty() {
+                let missing_item_proof = MMRLeafProof {
+                    leaf_index,
+                    leaf_count,
+                    items: items[..items.len() - 1].to_vec(),
+                };
+                prop_assert_ne!(missing_item_proof.root(hasher, leaf), Some(root));
+            }
+            let out_of_range_proof = MMRLeafProof {
+                leaf_index: leaf_count,
+                leaf_count,
+                items,
+            };
+            prop_assert_eq!(out_of_range_proof.root(hasher, leaf), None);
+        }
+    }
+
+    #[test]
+    fn oversized_mmr_proof_rejected() {
+        let leaf = H256::repeat_byte(1);
+        for leaf_count in [MAX_MMR_LEAF_COUNT, u64::MAX] {
+            let proof = MMRLeafProof {
+                leaf_index: leaf_count - 1,
+                leaf_count,
+                items: vec![H256::repeat_byte(2); 8],
+            };
+            assert_eq!(proof.root(hasher, leaf), None);
+        }
+        let proof = MMRLeafProof {
+            leaf_index: 0,
+            leaf_count: 1,
+            items: vec![H256::repeat_byte(2); MAX_MMR_PROOF_ITEMS + 1],
+        };
+        assert_eq!(proof.root(hasher, leaf), None);
+
+        // The largest MMR is handled without overflow
+        let proof = MMRLeafProof {
+            leaf_index: 0,
+            leaf_count: MAX_MMR_LEAF_COUNT - 1,
+            items: vec![H256::repeat_byte(2); MAX_MMR_PROOF_ITEMS],
+        };
+        assert_eq!(proof.root(hasher, leaf), None);
+
+        assert_eq!(checked_leaf_count_to_mmr_size(u64::MAX), None);
+        assert_eq!(checked_leaf_count_to_mmr_size(5), Some(8));
+        assert_eq!(checked_parent_offset(62), Some(parent_offset(62)));
+        assert_eq!(checked_parent_offset(63), None);
+    }
+
     #[derive(Clone, Serialize, Deserialize)]
     struct SimplifiedProofTestData {
         #[serde(rename = "LeafIndex")]
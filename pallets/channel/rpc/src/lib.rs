@@ -0,0 +1,109 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+use codec::Codec;
+use jsonrpsee::{
+    core::{Error as RpcError, RpcResult as Result},
+    proc_macros::rpc,
+    types::error::CallError,
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::offchain::{storage::OffchainDb, OffchainDbExt, OffchainStorage};
+use sp_core::Get;
+use sp_runtime::generic::BlockId;
+use sp_runtime::traits::{Block as BlockT, NumberFor};
+use std::sync::Arc;
+
+pub use bridge_channel_runtime_api::BridgeChannelAPI as BridgeChannelRuntimeAPI;
+use bridge_channel_runtime_api::{GenericCommitmentWithBlock, GenericNetworkId};
+
+#[rpc(client, server)]
+pub trait BridgeChannelAPI<BlockHash, Commitment> {
+    #[method(name = "bridgeChannel_commitment")]
+    fn commitment(
+        &self,
+        network_id: GenericNetworkId,
+        batch_nonce: u64,
+        at: Option<BlockHash>,
+    ) -> Result<Option<Commitment>>;
+}
+
+pub struct BridgeChannelClient<C, B, S> {
+    client: Arc<C>,
+    offchain_db: OffchainDb<S>,
+    _marker: std::marker::PhantomData<B>,
+}
+
+impl<C, B, S> BridgeChannelClient<C, B, S>
+where
+    S: OffchainStorage,
+{
+    /// Construct default `BridgeChannelClient`.
+    pub fn new(client: Arc<C>, offchain_storage: S) -> Self {
+        Self {
+            client,
+            offchain_db: OffchainDb::new(offchain_storage),
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<C, B, S, MaxMessages, MaxPayload>
+    BridgeChannelAPIServer<
+        <B as BlockT>::Hash,
+        GenericCommitmentWithBlock<NumberFor<B>, MaxMessages, MaxPayload>,
+    > for BridgeChannelClient<C, B, S>
+where
+    C: Send + Sync + 'static,
+    C: ProvideRuntimeApi<B> + HeaderBackend<B>,
+    C::Api: BridgeChannelRuntimeAPI<B, NumberFor<B>, MaxMessages, MaxPayload>,
+    B: BlockT,
+    NumberFor<B>: Codec,
+    S: OffchainStorage + 'static,
+    MaxMessages: Get<u32> + Send + Sync + 'static,
+    MaxPayload: Get<u32> + Send + Sync + 'static,
+{
+    fn commitment(
+        &self,
+        network_id: GenericNetworkId,
+        batch_nonce: u64,
+        at: Option<<B as BlockT>::Hash>,
+    ) -> Result<Option<GenericCommitmentWithBlock<NumberFor<B>, MaxMessages, MaxPayload>>> {
+        let mut api = self.client.runtime_api();
+        api.register_extension(OffchainDbExt::new(self.offchain_db.clone()));
+        let at = BlockId::hash(at.unwrap_or(
+            // If the block hash is not supplied assume the best block.
+            self.client.info().best_hash,
+        ));
+        api.commitment(&at, network_id, batch_nonce)
+            .map_err(|e| RpcError::Call(CallError::Failed(e.into())))
+    }
+}
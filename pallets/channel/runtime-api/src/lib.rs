@@ -0,0 +1,73 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+pub use bridge_types::types::GenericCommitmentWithBlock;
+pub use bridge_types::GenericNetworkId;
+use codec::Codec;
+use sp_core::{Get, U256};
+use sp_runtime::DispatchError;
+
+sp_api::decl_runtime_apis! {
+    pub trait BridgeChannelAPI<BlockNumber, MaxMessages, MaxPayload>
+    where
+        BlockNumber: Codec,
+        MaxMessages: Get<u32>,
+        MaxPayload: Get<u32>,
+    {
+        /// Get outbound commitment with given batch nonce.
+        ///
+        /// Past commitments are read from the offchain storage, so the caller
+        /// should register `OffchainDbExt` extension.
+        fn commitment(
+            network_id: GenericNetworkId,
+            batch_nonce: u64,
+        ) -> Option<GenericCommitmentWithBlock<BlockNumber, MaxMessages, MaxPayload>>;
+    }
+
+    pub trait BridgeChannelFeeAPI<AssetId, Balance>
+    where
+        AssetId: Codec,
+        Balance: Codec,
+    {
+        /// Get fee paid by the sender for the outbound message, `None` if messages are free.
+        ///
+        /// `max_gas` is charged only for EVM networks, pass zero for the apps
+        /// which charge the gas themselves.
+        fn message_fee(
+            network_id: GenericNetworkId,
+            payload_size: u32,
+            max_gas: U256,
+        ) -> Result<Option<(AssetId, Balance)>, DispatchError>;
+    }
+}
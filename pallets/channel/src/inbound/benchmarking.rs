@@ -60,11 +60,46 @@ benchmarks! {
                 nonce: 1u64,
             }
         );
-        let proof = T::Verifier::valid_proof().unwrap();
+        let proof = T::Verifier::valid_proof(BASE_NETWORK_ID, commitment.hash()).unwrap();
     }: _(RawOrigin::None, BASE_NETWORK_ID, commitment, proof)
     verify {
         assert_eq!(1, <ChannelNonces<T>>::get(BASE_NETWORK_ID));
     }
+
+    // Benchmark `submit` extrinsic with EVM status report for `r` messages
+    submit_status_report {
+        let r in 1 .. T::MaxMessagesPerCommit::get();
+        let chain_id = EVMChainId::from_low_u64_be(1);
+        let channel = H160::repeat_byte(1);
+        EVMChannelAddresses::<T>::insert(chain_id, channel);
+        let commitment = bridge_types::GenericCommitment::EVM(
+            bridge_types::evm::Commitment::StatusReport(bridge_types::evm::StatusReport {
+                channel,
+                block_number: 1,
+                relayer: H160::repeat_byte(2),
+                nonce: 1,
+                results: vec![true; r as usize].try_into().unwrap(),
+                gas_spent: 1_000_000u64.into(),
+                base_fee: 1_000u64.into(),
+            })
+        );
+        let proof = T::Verifier::valid_proof(GenericNetworkId::EVM(chain_id), commitment.hash()).unwrap();
+    }: submit(RawOrigin::None, GenericNetworkId::EVM(chain_id), commitment, proof)
+    verify {
+        assert_eq!(1, <ReportedChannelNonces<T>>::get(GenericNetworkId::EVM(chain_id)));
+    }
+
+    // Benchmark `submit` extrinsic with EVM base fee update
+    submit_base_fee_update {
+        let chain_id = EVMChainId::from_low_u64_be(1);
+        let commitment = bridge_types::GenericCommitment::EVM(
+            bridge_types::evm::Commitment::BaseFeeUpdate(bridge_types::evm::BaseFeeUpdate {
+                new_base_fee: 1_000u64.into(),
+                evm_block_number: 1,
+            })
+        );
+        let proof = T::Verifier::valid_proof(GenericNetworkId::EVM(chain_id), commitment.hash()).unwrap();
+    }: submit(RawOrigin::None, GenericNetworkId::EVM(chain_id), commitment, proof)
 }
 
 impl_benchmark_test_suite!(
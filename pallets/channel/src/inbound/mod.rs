@@ -32,7 +32,8 @@
 
 use bridge_types::evm::AdditionalEVMOutboundData;
 use bridge_types::traits::{
-    AppRegistry, EVMFeeHandler, MessageDispatch, MessageStatusNotifier, OutboundChannel, Verifier,
+    AppRegistry, CancelledMessagesProvider, EVMFeeHandler, MessageDispatch, MessageStatusNotifier,
+    OutboundChannel, Verifier,
 };
 use bridge_types::types::MessageId;
 use bridge_types::SubNetworkId;
@@ -49,6 +50,9 @@ pub use weights::WeightInfo;
 
 pub const EVM_GAS_OVERHEAD: u64 = 20000;
 
+/// Number of nonces above the last in-order nonce which can be processed out of order.
+pub const NONCE_WINDOW_SIZE: u64 = u128::BITS as u64;
+
 #[cfg(test)]
 mod test;
 
@@ -102,6 +106,9 @@ pub mod pallet {
 
         type EVMFeeHandler: EVMFeeHandler<Self::AssetId>;
 
+        /// Messages cancelled in the outbound channel, which status reports are ignored.
+        type CancelledMessagesProvider: CancelledMessagesProvider;
+
         /// A configuration for base priority of unsigned transactions.
         #[pallet::constant]
         type UnsignedPriority: Get<TransactionPriority>;
@@ -124,6 +131,9 @@ pub mod pallet {
         #[pallet::constant]
         type EVMPriorityFee: Get<u128>;
 
+        /// Origin which can halt and resume networks.
+        type HaltOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
         /// Weight information for extrinsics in this pallet
         type WeightInfo: WeightInfo;
     }
@@ -131,10 +141,26 @@ pub mod pallet {
     #[pallet::storage]
     pub type ChannelNonces<T: Config> = StorageMap<_, Identity, GenericNetworkId, u64, ValueQuery>;
 
+    /// Networks which accept commitments inside the nonce window in any order.
+    #[pallet::storage]
+    pub type NonceWindowEnabled<T: Config> =
+        StorageMap<_, Identity, GenericNetworkId, bool, ValueQuery>;
+
+    /// Nonces processed ahead of [`ChannelNonces`] for networks with enabled nonce window.
+    /// Bit `i` is set if nonce `ChannelNonces + 1 + i` was processed.
+    #[pallet::storage]
+    pub type ProcessedNonceWindow<T: Config> =
+        StorageMap<_, Identity, GenericNetworkId, u128, ValueQuery>;
+
     #[pallet::storage]
     pub type ReportedChannelNonces<T: Config> =
         StorageMap<_, Identity, GenericNetworkId, u64, ValueQuery>;
 
+    /// Networks which commitments are rejected until resumed.
+    #[pallet::storage]
+    pub type HaltedNetworks<T: Config> =
+        StorageMap<_, Identity, GenericNetworkId, bool, ValueQuery>;
+
     #[pallet::storage]
     pub type EVMChannelAddresses<T: Config> =
         StorageMap<_, Identity, EVMChainId, H160, OptionQuery>;
@@ -156,9 +182,13 @@ pub mod pallet {
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
 
     #[pallet::event]
-    // #[pallet::generate_deposit(pub(super) fn deposit_event)]
-    // This pallet don't have events
-    pub enum Event<T: Config> {}
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// Commitments from the network are rejected.
+        NetworkHalted { network_id: GenericNetworkId },
+        /// Commitments from the network are accepted again.
+        NetworkResumed { network_id: GenericNetworkId },
+    }
 
     #[pallet::error]
     pub enum Error<T> {
@@ -178,9 +208,38 @@ pub mod pallet {
         CallEncodeFailed,
         /// Invalid base fee update.
         InvalidBaseFeeUpdate,
+        /// Nonce window has processed nonces ahead of the channel nonce.
+        NonceWindowNotEmpty,
+        /// Network is halted.
+        NetworkHalted,
     }
 
     impl<T: Config> Pallet<T> {
+        /// Weight of `submit` without the message dispatch and proof verification.
+        fn submit_base_weight(
+            commitment: &bridge_types::GenericCommitment<
+                T::MaxMessagesPerCommit,
+                T::MaxMessagePayloadSize,
+            >,
+        ) -> Weight {
+            match commitment {
+                bridge_types::GenericCommitment::EVM(
+                    bridge_types::evm::Commitment::StatusReport(status_report),
+                ) => <T as Config>::WeightInfo::submit_status_report(
+                    status_report.results.len() as u32
+                ),
+                bridge_types::GenericCommitment::TON(
+                    bridge_types::ton::Commitment::StatusReport(status_report),
+                ) => <T as Config>::WeightInfo::submit_status_report(
+                    status_report.results.len() as u32
+                ),
+                bridge_types::GenericCommitment::EVM(
+                    bridge_types::evm::Commitment::BaseFeeUpdate(_),
+                ) => <T as Config>::WeightInfo::submit_base_fee_update(),
+                _ => <T as Config>::WeightInfo::submit(),
+            }
+        }
+
         fn submit_weight(
             commitment: &bridge_types::GenericCommitment<
                 T::MaxMessagesPerCommit,
@@ -188,34 +247,38 @@ pub mod pallet {
             >,
             proof: &<T::Verifier as Verifier>::Proof,
         ) -> Weight {
-            let commitment_weight = match commitment {
-                bridge_types::GenericCommitment::EVM(commitment) => match commitment {
-                    bridge_types::evm::Commitment::Outbound(_) => {
-                        <T as frame_system::Config>::BlockWeights::get().max_block
-                    }
-                    bridge_types::evm::Commitment::Inbound(commitment) => {
-                        T::MessageDispatch::dispatch_weight(&commitment.payload)
-                    }
-                    bridge_types::evm::Commitment::StatusReport(_) => Default::default(),
-                    bridge_types::evm::Commitment::BaseFeeUpdate(_) => Default::default(),
-                },
+            let dispatch_weight = match commitment {
+                bridge_types::GenericCommitment::EVM(bridge_types::evm::Commitment::Inbound(
+                    commitment,
+                )) => T::MessageDispatch::dispatch_weight(&commitment.payload),
                 bridge_types::GenericCommitment::TON(bridge_types::ton::Commitment::Inbound(
                     commitment,
                 )) => T::MessageDispatch::dispatch_weight(&commitment.payload),
                 bridge_types::GenericCommitment::Sub(commitment) => commitment
                     .messages
                     .iter()
+                    .filter(|m| !m.is_tombstone())
                     .map(|m| T::MessageDispatch::dispatch_weight(&m.payload))
                     .fold(Weight::zero(), |acc, w| acc.saturating_add(w)),
+                // Outbound commitments are rejected before any work is done
+                _ => Weight::zero(),
             };
 
             let proof_weight = T::Verifier::verify_weight(proof);
 
-            <T as Config>::WeightInfo::submit()
-                .saturating_add(commitment_weight)
+            Self::submit_base_weight(commitment)
+                .saturating_add(dispatch_weight)
                 .saturating_add(proof_weight)
         }
 
+        fn ensure_network_not_halted(network_id: GenericNetworkId) -> DispatchResult {
+            ensure!(
+                !HaltedNetworks::<T>::get(network_id),
+                Error::<T>::NetworkHalted
+            );
+            Ok(())
+        }
+
         fn ensure_evm_channel(chain_id: EVMChainId, channel: H160) -> DispatchResult {
             let channel_address =
                 EVMChannelAddresses::<T>::get(chain_id).ok_or(Error::<T>::InvalidNetwork)?;
@@ -232,10 +295,25 @@ pub mod pallet {
 
         fn ensure_channel_nonce(network_id: GenericNetworkId, new_nonce: u64) -> DispatchResult {
             let nonce = ChannelNonces::<T>::get(network_id);
-            ensure!(nonce + 1 == new_nonce, Error::<T>::InvalidNonce);
+            if NonceWindowEnabled::<T>::get(network_id) {
+                let bit = Self::nonce_window_bit(nonce, new_nonce)?;
+                let window = ProcessedNonceWindow::<T>::get(network_id);
+                ensure!(window & (1 << bit) == 0, Error::<T>::InvalidNonce);
+            } else {
+                ensure!(nonce + 1 == new_nonce, Error::<T>::InvalidNonce);
+            }
             Ok(())
         }
 
+        /// Position of `new_nonce` in the processed nonce window above `nonce`.
+        fn nonce_window_bit(nonce: u64, new_nonce: u64) -> Result<u64, Error<T>> {
+            ensure!(
+                new_nonce > nonce && new_nonce - nonce <= NONCE_WINDOW_SIZE,
+                Error::<T>::InvalidNonce
+            );
+            Ok(new_nonce - nonce - 1)
+        }
+
         fn ensure_reported_nonce(network_id: GenericNetworkId, new_nonce: u64) -> DispatchResult {
             let nonce = ReportedChannelNonces::<T>::get(network_id);
             ensure!(nonce + 1 == new_nonce, Error::<T>::InvalidNonce);
@@ -243,6 +321,9 @@ pub mod pallet {
         }
 
         fn update_channel_nonce(network_id: GenericNetworkId, new_nonce: u64) -> DispatchResult {
+            if NonceWindowEnabled::<T>::get(network_id) {
+                return Self::update_nonce_window(network_id, new_nonce);
+            }
             <ChannelNonces<T>>::try_mutate(network_id, |nonce| -> DispatchResult {
                 if new_nonce != *nonce + 1 {
                     Err(Error::<T>::InvalidNonce.into())
@@ -254,6 +335,23 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Mark `new_nonce` as processed and advance the channel nonce
+        /// over the contiguous range of processed nonces.
+        fn update_nonce_window(network_id: GenericNetworkId, new_nonce: u64) -> DispatchResult {
+            <ChannelNonces<T>>::try_mutate(network_id, |nonce| -> DispatchResult {
+                <ProcessedNonceWindow<T>>::try_mutate(network_id, |window| -> DispatchResult {
+                    let bit = Self::nonce_window_bit(*nonce, new_nonce)?;
+                    ensure!(*window & (1 << bit) == 0, Error::<T>::InvalidNonce);
+                    *window |= 1 << bit;
+                    while *window & 1 == 1 {
+                        *window >>= 1;
+                        *nonce += 1;
+                    }
+                    Ok(())
+                })
+            })
+        }
+
         fn update_reported_nonce(network_id: GenericNetworkId, new_nonce: u64) -> DispatchResult {
             <ReportedChannelNonces<T>>::try_mutate(network_id, |nonce| -> DispatchResult {
                 if new_nonce != *nonce + 1 {
@@ -266,13 +364,17 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Handle the commitment and return the weight consumed by the message dispatch.
         fn handle_ton_commitment(
             network_id: TonNetworkId,
-            commitment: bridge_types::ton::Commitment<T::MaxMessagePayloadSize>,
-        ) -> DispatchResult {
+            commitment: bridge_types::ton::Commitment<
+                T::MaxMessagesPerCommit,
+                T::MaxMessagePayloadSize,
+            >,
+        ) -> Result<Weight, DispatchError> {
             Self::verify_ton_commitment(network_id, &commitment)?;
             let network_id = GenericNetworkId::TON(network_id);
-            match commitment {
+            let dispatch_weight = match commitment {
                 bridge_types::ton::Commitment::Inbound(inbound_commitment) => {
                     Self::update_channel_nonce(network_id, inbound_commitment.nonce)?;
                     let message_id = MessageId::basic(
@@ -289,15 +391,51 @@ pub mod pallet {
                             source: inbound_commitment.source,
                         }
                         .into(),
+                    )
+                }
+                bridge_types::ton::Commitment::StatusReport(status_report) => {
+                    Self::update_reported_nonce(network_id, status_report.nonce)?;
+                    let cancelled = T::CancelledMessagesProvider::take_cancelled_messages(
+                        network_id,
+                        status_report.nonce,
                     );
+                    for (i, result) in status_report.results.into_iter().enumerate() {
+                        if cancelled.contains(&(i as u64)) {
+                            continue;
+                        }
+                        let status = if result {
+                            MessageStatus::Done
+                        } else {
+                            MessageStatus::Failed
+                        };
+                        T::MessageStatusNotifier::update_status(
+                            network_id,
+                            MessageId::batched(
+                                T::ThisNetworkId::get(),
+                                network_id,
+                                status_report.nonce,
+                                i as u64,
+                            )
+                            .hash(),
+                            status,
+                            GenericTimepoint::TON(status_report.transaction_id),
+                        )
+                    }
+                    Weight::zero()
                 }
-            }
-            Ok(())
+                bridge_types::ton::Commitment::Outbound(_) => {
+                    frame_support::fail!(Error::<T>::InvalidCommitment);
+                }
+            };
+            Ok(dispatch_weight)
         }
 
         fn verify_ton_commitment(
             ton_network_id: TonNetworkId,
-            commitment: &bridge_types::ton::Commitment<T::MaxMessagePayloadSize>,
+            commitment: &bridge_types::ton::Commitment<
+                T::MaxMessagesPerCommit,
+                T::MaxMessagePayloadSize,
+            >,
         ) -> DispatchResult {
             let network_id = GenericNetworkId::TON(ton_network_id);
             match commitment {
@@ -305,20 +443,28 @@ pub mod pallet {
                     Self::ensure_ton_channel(ton_network_id, inbound_commitment.channel)?;
                     Self::ensure_channel_nonce(network_id, inbound_commitment.nonce)?;
                 }
+                bridge_types::ton::Commitment::StatusReport(status_report) => {
+                    Self::ensure_ton_channel(ton_network_id, status_report.channel)?;
+                    Self::ensure_reported_nonce(network_id, status_report.nonce)?;
+                }
+                bridge_types::ton::Commitment::Outbound(_) => {
+                    frame_support::fail!(Error::<T>::InvalidCommitment);
+                }
             }
             Ok(())
         }
 
+        /// Handle the commitment and return the weight consumed by the message dispatch.
         fn handle_evm_commitment(
             chain_id: EVMChainId,
             commitment: bridge_types::evm::Commitment<
                 T::MaxMessagesPerCommit,
                 T::MaxMessagePayloadSize,
             >,
-        ) -> DispatchResult {
+        ) -> Result<Weight, DispatchError> {
             Self::verify_evm_commitment(chain_id, &commitment)?;
             let network_id = GenericNetworkId::EVM(chain_id);
-            match commitment {
+            let dispatch_weight = match commitment {
                 bridge_types::evm::Commitment::Inbound(inbound_commitment) => {
                     Self::update_channel_nonce(network_id, inbound_commitment.nonce)?;
                     let message_id = MessageId::basic(
@@ -335,11 +481,18 @@ pub mod pallet {
                             source: inbound_commitment.source,
                         }
                         .into(),
-                    );
+                    )
                 }
                 bridge_types::evm::Commitment::StatusReport(status_report) => {
                     Self::update_reported_nonce(network_id, status_report.nonce)?;
+                    let cancelled = T::CancelledMessagesProvider::take_cancelled_messages(
+                        network_id,
+                        status_report.nonce,
+                    );
                     for (i, result) in status_report.results.into_iter().enumerate() {
+                        if cancelled.contains(&(i as u64)) {
+                            continue;
+                        }
                         let status = if result {
                             MessageStatus::Done
                         } else {
@@ -367,20 +520,22 @@ pub mod pallet {
                         .base_fee
                         .saturating_add(T::EVMPriorityFee::get().into());
                     let fee_paid = gas_used.saturating_mul(gas_price);
-                    T::EVMFeeHandler::on_fee_paid(chain_id, status_report.relayer, fee_paid)
+                    T::EVMFeeHandler::on_fee_paid(chain_id, status_report.relayer, fee_paid);
+                    Weight::zero()
                 }
                 bridge_types::evm::Commitment::BaseFeeUpdate(update) => {
                     T::EVMFeeHandler::update_base_fee(
                         chain_id,
                         update.new_base_fee,
                         update.evm_block_number,
-                    )
+                    );
+                    Weight::zero()
                 }
                 bridge_types::evm::Commitment::Outbound(_) => {
                     frame_support::fail!(Error::<T>::InvalidCommitment);
                 }
-            }
-            Ok(())
+            };
+            Ok(dispatch_weight)
         }
 
         fn verify_evm_commitment(
@@ -416,32 +571,38 @@ pub mod pallet {
             Ok(())
         }
 
+        /// Handle the commitment and return the weight consumed by the messages dispatch.
         fn handle_sub_commitment(
             sub_network_id: SubNetworkId,
             commitment: bridge_types::substrate::Commitment<
                 T::MaxMessagesPerCommit,
                 T::MaxMessagePayloadSize,
             >,
-        ) -> DispatchResult {
+        ) -> Result<Weight, DispatchError> {
             Self::verify_sub_commitment(sub_network_id, &commitment)?;
             let network_id = GenericNetworkId::Sub(sub_network_id);
             Self::update_channel_nonce(network_id, commitment.nonce)?;
+            let mut dispatch_weight = Weight::zero();
             for (idx, message) in commitment.messages.into_iter().enumerate() {
+                // Cancelled message, which only keeps ids of the other messages
+                if message.is_tombstone() {
+                    continue;
+                }
                 let message_id = MessageId::batched(
                     network_id,
                     T::ThisNetworkId::get(),
                     commitment.nonce,
                     idx as u64,
                 );
-                T::MessageDispatch::dispatch(
+                dispatch_weight = dispatch_weight.saturating_add(T::MessageDispatch::dispatch(
                     sub_network_id.into(),
                     message_id,
                     message.timepoint,
                     &message.payload,
                     GenericAdditionalInboundData::Sub,
-                );
+                ));
             }
-            Ok(())
+            Ok(dispatch_weight)
         }
 
         fn verify_sub_commitment(
@@ -471,9 +632,12 @@ pub mod pallet {
             proof: <T::Verifier as Verifier>::Proof,
         ) -> DispatchResultWithPostInfo {
             ensure_none(origin)?;
+            Self::ensure_network_not_halted(network_id)?;
             let commitment_hash = commitment.hash();
             T::Verifier::verify(network_id, commitment_hash, &proof)?;
-            match (network_id, commitment) {
+            let base_weight = Self::submit_base_weight(&commitment)
+                .saturating_add(T::Verifier::verify_weight(&proof));
+            let dispatch_weight = match (network_id, commitment) {
                 (
                     GenericNetworkId::EVM(evm_network_id),
                     bridge_types::GenericCommitment::EVM(evm_commitment),
@@ -489,8 +653,8 @@ pub mod pallet {
                 _ => {
                     frame_support::fail!(Error::<T>::InvalidCommitment);
                 }
-            }
-            Ok(().into())
+            };
+            Ok(Some(base_weight.saturating_add(dispatch_weight)).into())
         }
 
         #[pallet::call_index(1)]
@@ -516,6 +680,55 @@ pub mod pallet {
             TONChannelAddresses::<T>::insert(network_id, channel_address);
             Ok(().into())
         }
+
+        /// Enable or disable out-of-order delivery of commitments for `network_id`.
+        ///
+        /// Can be disabled only when there are no processed nonces ahead of the channel nonce,
+        /// otherwise they could be replayed.
+        #[pallet::call_index(3)]
+        #[pallet::weight(0)]
+        pub fn set_nonce_window(
+            origin: OriginFor<T>,
+            network_id: GenericNetworkId,
+            enabled: bool,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            if !enabled {
+                ensure!(
+                    ProcessedNonceWindow::<T>::get(network_id) == 0,
+                    Error::<T>::NonceWindowNotEmpty
+                );
+                ProcessedNonceWindow::<T>::remove(network_id);
+            }
+            NonceWindowEnabled::<T>::insert(network_id, enabled);
+            Ok(().into())
+        }
+
+        /// Reject commitments from `network_id` until it is resumed.
+        #[pallet::call_index(4)]
+        #[pallet::weight(0)]
+        pub fn halt_network(
+            origin: OriginFor<T>,
+            network_id: GenericNetworkId,
+        ) -> DispatchResultWithPostInfo {
+            T::HaltOrigin::ensure_origin(origin)?;
+            HaltedNetworks::<T>::insert(network_id, true);
+            Self::deposit_event(Event::NetworkHalted { network_id });
+            Ok(().into())
+        }
+
+        /// Accept commitments from `network_id` again.
+        #[pallet::call_index(5)]
+        #[pallet::weight(0)]
+        pub fn resume_network(
+            origin: OriginFor<T>,
+            network_id: GenericNetworkId,
+        ) -> DispatchResultWithPostInfo {
+            T::HaltOrigin::ensure_origin(origin)?;
+            HaltedNetworks::<T>::remove(network_id);
+            Self::deposit_event(Event::NetworkResumed { network_id });
+            Ok(().into())
+        }
     }
 
     #[pallet::validate_unsigned]
@@ -529,6 +742,8 @@ pub mod pallet {
                 proof,
             } = call
             {
+                Self::ensure_network_not_halted(*network_id)
+                    .map_err(|_| InvalidTransaction::Call)?;
                 match (network_id, &commitment) {
                     (
                         GenericNetworkId::EVM(evm_network_id),
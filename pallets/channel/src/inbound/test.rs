@@ -30,15 +30,18 @@
 
 use super::*;
 use bridge_types::substrate::BridgeMessage;
-use bridge_types::types::GenericAdditionalInboundData;
+use bridge_types::ton::{TonAddress, TonNetworkId};
+use bridge_types::types::{GenericAdditionalInboundData, MessageId};
 use codec::{Decode, Encode, MaxEncodedLen};
 
+use frame_support::dispatch::GetDispatchInfo;
 use frame_support::traits::{Everything, UnfilteredDispatchable};
+use frame_support::weights::Weight;
 use frame_support::{
     assert_err, assert_noop, assert_ok, parameter_types, Deserialize, RuntimeDebug, Serialize,
 };
 use scale_info::TypeInfo;
-use sp_core::{ConstU128, ConstU64, H256};
+use sp_core::{ConstU128, ConstU64, H256, U256};
 use sp_keyring::AccountKeyring as Keyring;
 use sp_runtime::testing::Header;
 use sp_runtime::traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, ValidateUnsigned, Verify};
@@ -47,6 +50,7 @@ use sp_runtime::transaction_validity::{
 };
 use sp_runtime::{DispatchError, MultiSignature};
 use sp_std::convert::From;
+use std::cell::RefCell;
 
 use bridge_types::traits::MessageDispatch;
 use bridge_types::{GenericNetworkId, GenericTimepoint};
@@ -59,6 +63,10 @@ type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
 const BASE_NETWORK_ID: GenericNetworkId = GenericNetworkId::Sub(SubNetworkId::Mainnet);
+const TON_NETWORK_ID: GenericNetworkId = GenericNetworkId::TON(TonNetworkId::Testnet);
+const EVM_NETWORK_ID: GenericNetworkId = GenericNetworkId::EVM(H256([
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+]));
 
 frame_support::construct_runtime!(
     pub enum Test where
@@ -158,6 +166,8 @@ impl Verifier for MockVerifier {
 
     fn verify(network_id: GenericNetworkId, _hash: H256, _proof: &Vec<u8>) -> DispatchResult {
         let network_id = match network_id {
+            bridge_types::GenericNetworkId::TON(_) if network_id == TON_NETWORK_ID => return Ok(()),
+            bridge_types::GenericNetworkId::EVM(_) if network_id == EVM_NETWORK_ID => return Ok(()),
             bridge_types::GenericNetworkId::EVM(_)
             | bridge_types::GenericNetworkId::TON(_)
             | bridge_types::GenericNetworkId::EVMLegacy(_) => {
@@ -177,11 +187,15 @@ impl Verifier for MockVerifier {
     }
 
     #[cfg(feature = "runtime-benchmarks")]
-    fn valid_proof() -> Option<Self::Proof> {
+    fn valid_proof(_network_id: GenericNetworkId, _commitment_hash: H256) -> Option<Self::Proof> {
         Some(Default::default())
     }
 }
 
+thread_local! {
+    static DISPATCHED_MESSAGES: RefCell<Vec<MessageId>> = RefCell::new(Vec::new());
+}
+
 // Mock Dispatch
 pub struct MockMessageDispatch;
 
@@ -190,15 +204,17 @@ impl MessageDispatch<Test, GenericNetworkId, MessageId, GenericAdditionalInbound
 {
     fn dispatch(
         _: GenericNetworkId,
-        _: MessageId,
+        message_id: MessageId,
         _: GenericTimepoint,
         _: &[u8],
         _: GenericAdditionalInboundData,
-    ) {
+    ) -> frame_support::weights::Weight {
+        DISPATCHED_MESSAGES.with(|messages| messages.borrow_mut().push(message_id));
+        frame_support::weights::Weight::from_parts(100, 0)
     }
 
     fn dispatch_weight(_: &[u8]) -> frame_support::weights::Weight {
-        Default::default()
+        frame_support::weights::Weight::from_parts(1000, 0)
     }
 
     #[cfg(feature = "runtime-benchmarks")]
@@ -243,11 +259,36 @@ impl OutboundChannel<EVMChainId, AccountId, AdditionalEVMOutboundData> for Outbo
     }
 }
 
+pub struct EVMFeeHandlerImpl;
+
+impl EVMFeeHandler<H256> for EVMFeeHandlerImpl {
+    fn get_latest_base_fee(_network_id: EVMChainId) -> Result<U256, DispatchError> {
+        Err(DispatchError::Unavailable)
+    }
+
+    fn get_network_fee_asset(_network_id: EVMChainId) -> Result<H256, DispatchError> {
+        Err(DispatchError::Unavailable)
+    }
+
+    fn on_fee_paid(_network_id: EVMChainId, _relayer: H160, _amount: U256) {}
+
+    fn update_base_fee(_network_id: EVMChainId, _new_base_fee: U256, _evm_block_number: u64) {}
+
+    fn can_update_base_fee(
+        _network_id: EVMChainId,
+        _new_base_fee: U256,
+        _evm_block_number: u64,
+    ) -> bool {
+        true
+    }
+}
+
 impl bridge_inbound_channel::Config for Test {
     type MessageStatusNotifier = ();
     type AssetId = H256;
     type Balance = Balance;
-    type EVMFeeHandler = ();
+    type EVMFeeHandler = EVMFeeHandlerImpl;
+    type CancelledMessagesProvider = ();
     type OutboundChannel = OutboundChannelImpl;
     type RuntimeEvent = RuntimeEvent;
     type Verifier = MockVerifier;
@@ -258,6 +299,7 @@ impl bridge_inbound_channel::Config for Test {
     type MaxMessagesPerCommit = MaxMessagesPerCommit;
     type ThisNetworkId = ThisNetworkId;
     type EVMPriorityFee = ConstU128<5_000_000_000>;
+    type HaltOrigin = frame_system::EnsureRoot<AccountId>;
     type WeightInfo = ();
 }
 
@@ -380,6 +422,44 @@ fn test_submit_with_invalid_nonce() {
     });
 }
 
+#[test]
+fn test_submit_skips_tombstones() {
+    new_tester().execute_with(|| {
+        let message = BridgeMessage {
+            timepoint: Default::default(),
+            payload: vec![0, 1, 2].try_into().unwrap(),
+        };
+        let tombstone = BridgeMessage {
+            timepoint: Default::default(),
+            payload: bridge_types::TOMBSTONE_PAYLOAD.to_vec().try_into().unwrap(),
+        };
+        let commitment =
+            bridge_types::GenericCommitment::Sub(bridge_types::substrate::Commitment {
+                nonce: 1,
+                messages: vec![tombstone, message].try_into().unwrap(),
+            });
+        let call = Call::<Test>::submit {
+            network_id: BASE_NETWORK_ID,
+            commitment,
+            proof: vec![],
+        };
+        assert_ok!(call.dispatch_bypass_filter(RuntimeOrigin::none()));
+
+        // Only the second message is dispatched, with its original id
+        DISPATCHED_MESSAGES.with(|messages| {
+            assert_eq!(
+                *messages.borrow(),
+                vec![MessageId::batched(
+                    BASE_NETWORK_ID,
+                    ThisNetworkId::get(),
+                    1,
+                    1
+                )]
+            )
+        });
+    });
+}
+
 #[test]
 fn test_submit_with_invalid_network_id() {
     new_tester().execute_with(|| {
@@ -412,3 +492,221 @@ fn test_submit_with_invalid_network_id() {
         );
     });
 }
+
+#[test]
+fn test_submit_ton_status_report() {
+    new_tester().execute_with(|| {
+        let origin = RuntimeOrigin::none();
+        let channel = TonAddress::new(0, H256::repeat_byte(1));
+        assert_ok!(BridgeInboundChannel::register_ton_channel(
+            RuntimeOrigin::root(),
+            TonNetworkId::Testnet,
+            channel
+        ));
+
+        let make_call = |channel| Call::<Test>::submit {
+            network_id: TON_NETWORK_ID,
+            commitment: bridge_types::GenericCommitment::TON(
+                bridge_types::ton::Commitment::StatusReport(bridge_types::ton::StatusReport {
+                    channel,
+                    transaction_id: Default::default(),
+                    nonce: 1,
+                    results: vec![true, false].try_into().unwrap(),
+                }),
+            ),
+            proof: vec![],
+        };
+
+        // Report from unknown channel
+        let call = make_call(TonAddress::new(0, H256::repeat_byte(2)));
+        assert_err!(
+            Pallet::<Test>::validate_unsigned(TransactionSource::External, &call),
+            TransactionValidityError::Invalid(InvalidTransaction::BadProof)
+        );
+        assert_noop!(
+            call.dispatch_bypass_filter(origin.clone()),
+            Error::<Test>::InvalidSourceChannel
+        );
+
+        let call = make_call(channel);
+        assert_eq!(
+            call.get_dispatch_info().weight,
+            <() as WeightInfo>::submit_status_report(2)
+        );
+        assert_ok!(Pallet::<Test>::validate_unsigned(
+            TransactionSource::External,
+            &call
+        ));
+        assert_ok!(call.clone().dispatch_bypass_filter(origin.clone()));
+        assert_eq!(<ReportedChannelNonces<Test>>::get(TON_NETWORK_ID), 1);
+        assert_eq!(<ChannelNonces<Test>>::get(TON_NETWORK_ID), 0);
+
+        // Submit the same again
+        assert_noop!(
+            call.dispatch_bypass_filter(origin),
+            Error::<Test>::InvalidNonce
+        );
+    });
+}
+
+fn sub_submit_call(nonce: u64) -> Call<Test> {
+    let message = BridgeMessage {
+        timepoint: Default::default(),
+        payload: Default::default(),
+    };
+    let commitment = bridge_types::GenericCommitment::Sub(bridge_types::substrate::Commitment {
+        nonce,
+        messages: vec![message].try_into().unwrap(),
+    });
+    Call::<Test>::submit {
+        network_id: BASE_NETWORK_ID,
+        commitment,
+        proof: vec![],
+    }
+}
+
+#[test]
+fn test_submit_returns_actual_weight() {
+    new_tester().execute_with(|| {
+        let call = sub_submit_call(1);
+        let info = call.get_dispatch_info();
+        assert_eq!(
+            info.weight,
+            <() as WeightInfo>::submit().saturating_add(Weight::from_parts(1000, 0))
+        );
+
+        // Mock dispatch consumes less than its worst case weight
+        let post_info = call.dispatch_bypass_filter(RuntimeOrigin::none()).unwrap();
+        assert_eq!(
+            post_info.actual_weight,
+            Some(<() as WeightInfo>::submit().saturating_add(Weight::from_parts(100, 0)))
+        );
+    });
+}
+
+#[test]
+fn test_submit_evm_base_fee_update_weight() {
+    new_tester().execute_with(|| {
+        let call = Call::<Test>::submit {
+            network_id: EVM_NETWORK_ID,
+            commitment: bridge_types::GenericCommitment::EVM(
+                bridge_types::evm::Commitment::BaseFeeUpdate(bridge_types::evm::BaseFeeUpdate {
+                    new_base_fee: 1000u64.into(),
+                    evm_block_number: 1,
+                }),
+            ),
+            proof: vec![],
+        };
+        let info = call.get_dispatch_info();
+        assert_eq!(info.weight, <() as WeightInfo>::submit_base_fee_update());
+
+        let post_info = call.dispatch_bypass_filter(RuntimeOrigin::none()).unwrap();
+        assert_eq!(post_info.actual_weight, Some(info.weight));
+    });
+}
+
+#[test]
+fn test_submit_out_of_order_with_nonce_window() {
+    new_tester().execute_with(|| {
+        let origin = RuntimeOrigin::none();
+        assert_ok!(BridgeInboundChannel::set_nonce_window(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID,
+            true
+        ));
+
+        let call = sub_submit_call(3);
+        assert_ok!(Pallet::<Test>::validate_unsigned(
+            TransactionSource::External,
+            &call
+        ));
+        assert_ok!(call.clone().dispatch_bypass_filter(origin.clone()));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 0);
+        assert_eq!(<ProcessedNonceWindow<Test>>::get(BASE_NETWORK_ID), 0b100);
+
+        // Replay is rejected
+        assert_err!(
+            Pallet::<Test>::validate_unsigned(TransactionSource::External, &call),
+            TransactionValidityError::Invalid(InvalidTransaction::BadProof)
+        );
+        assert_noop!(
+            call.dispatch_bypass_filter(origin.clone()),
+            Error::<Test>::InvalidNonce
+        );
+
+        // Disabling is not allowed while nonce 3 is processed ahead
+        assert_noop!(
+            BridgeInboundChannel::set_nonce_window(RuntimeOrigin::root(), BASE_NETWORK_ID, false),
+            Error::<Test>::NonceWindowNotEmpty
+        );
+
+        assert_ok!(sub_submit_call(1).dispatch_bypass_filter(origin.clone()));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+        assert_eq!(<ProcessedNonceWindow<Test>>::get(BASE_NETWORK_ID), 0b10);
+
+        assert_ok!(sub_submit_call(2).dispatch_bypass_filter(origin.clone()));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 3);
+        assert_eq!(<ProcessedNonceWindow<Test>>::get(BASE_NETWORK_ID), 0);
+
+        // Nonces outside of the window are rejected
+        assert_noop!(
+            sub_submit_call(3).dispatch_bypass_filter(origin.clone()),
+            Error::<Test>::InvalidNonce
+        );
+        assert_noop!(
+            sub_submit_call(3 + NONCE_WINDOW_SIZE + 1).dispatch_bypass_filter(origin.clone()),
+            Error::<Test>::InvalidNonce
+        );
+        assert_ok!(sub_submit_call(3 + NONCE_WINDOW_SIZE).dispatch_bypass_filter(origin));
+
+        assert_ok!(sub_submit_call(4).dispatch_bypass_filter(RuntimeOrigin::none()));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 4);
+    });
+}
+
+#[test]
+fn test_submit_to_halted_network() {
+    new_tester().execute_with(|| {
+        let origin = RuntimeOrigin::none();
+        let who: AccountId = Keyring::Bob.into();
+
+        assert_noop!(
+            BridgeInboundChannel::halt_network(RuntimeOrigin::signed(who), BASE_NETWORK_ID),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(BridgeInboundChannel::halt_network(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID
+        ));
+        assert!(<HaltedNetworks<Test>>::get(BASE_NETWORK_ID));
+        System::assert_last_event(RuntimeEvent::BridgeInboundChannel(Event::NetworkHalted {
+            network_id: BASE_NETWORK_ID,
+        }));
+
+        let call = sub_submit_call(1);
+        assert_err!(
+            Pallet::<Test>::validate_unsigned(TransactionSource::External, &call),
+            TransactionValidityError::Invalid(InvalidTransaction::Call)
+        );
+        assert_noop!(
+            call.clone().dispatch_bypass_filter(origin.clone()),
+            Error::<Test>::NetworkHalted
+        );
+
+        assert_ok!(BridgeInboundChannel::resume_network(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID
+        ));
+        assert!(!<HaltedNetworks<Test>>::get(BASE_NETWORK_ID));
+        System::assert_last_event(RuntimeEvent::BridgeInboundChannel(Event::NetworkResumed {
+            network_id: BASE_NETWORK_ID,
+        }));
+
+        assert_ok!(Pallet::<Test>::validate_unsigned(
+            TransactionSource::External,
+            &call
+        ));
+        assert_ok!(call.dispatch_bypass_filter(origin));
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+    });
+}
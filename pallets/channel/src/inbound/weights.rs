@@ -59,6 +59,8 @@ use core::marker::PhantomData;
 /// Weight functions needed for substrate_bridge_channel::inbound.
 pub trait WeightInfo {
 	fn submit() -> Weight;
+	fn submit_status_report(r: u32, ) -> Weight;
+	fn submit_base_fee_update() -> Weight;
 }
 
 /// Weights for substrate_bridge_channel::inbound using the Substrate node and recommended hardware.
@@ -75,6 +77,22 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Estimated by hand until the `submit_status_report` benchmark is run.
+	/// Each of the `r` reported messages reads and updates its bridge proxy transaction.
+	fn submit_status_report(r: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 8052)
+			.saturating_add(Weight::from_parts(3_600_000, 2595).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+	}
+	/// Estimated by hand until the `submit_base_fee_update` benchmark is run.
+	fn submit_base_fee_update() -> Weight {
+		Weight::from_parts(9_500_000, 2646)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -90,4 +108,20 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Estimated by hand until the `submit_status_report` benchmark is run.
+	/// Each of the `r` reported messages reads and updates its bridge proxy transaction.
+	fn submit_status_report(r: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 8052)
+			.saturating_add(Weight::from_parts(3_600_000, 2595).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(r.into())))
+	}
+	/// Estimated by hand until the `submit_base_fee_update` benchmark is run.
+	fn submit_base_fee_update() -> Weight {
+		Weight::from_parts(9_500_000, 2646)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
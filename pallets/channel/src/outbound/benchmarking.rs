@@ -121,6 +121,26 @@ benchmarks! {
         }.into());
     }
 
+    // Benchmark `cancel_message` for the last message of `b` full batches,
+    // so ids of all queued messages are checked.
+    cancel_message {
+        let b in 1 .. T::MaxOverflowBatches::get() + 1;
+        let messages_per_batch = T::MaxMessagesPerCommit::get();
+        let mut message_id = H256::zero();
+        for _ in 0 .. b * messages_per_batch {
+            message_id = BridgeOutboundChannel::<T>::submit(SubNetworkId::Mainnet, &RawOrigin::Root, &[0u8; 128], ()).unwrap();
+        }
+    }: {
+        BridgeOutboundChannel::<T>::cancel_message(BASE_NETWORK_ID, message_id).unwrap()
+    }
+    verify {
+        assert_last_event::<T>(crate::outbound::Event::<T>::MessageCancelled {
+            network_id: BASE_NETWORK_ID,
+            batch_nonce: b as u64,
+            message_nonce: (messages_per_batch - 1) as u64
+        }.into());
+    }
+
     impl_benchmark_test_suite!(
         BridgeOutboundChannel,
         crate::outbound::test::new_tester(),
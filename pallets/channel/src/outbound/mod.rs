@@ -32,8 +32,13 @@
 
 use bridge_types::evm::AdditionalEVMOutboundData;
 use bridge_types::substrate::BridgeMessage;
+use bridge_types::ton::{AdditionalTONOutboundData, TonNetworkId};
+use bridge_types::traits::BridgeAssetLocker;
+use bridge_types::traits::CancelledMessagesProvider;
+use bridge_types::traits::EVMFeeHandler;
 use bridge_types::traits::EVMOutboundChannel;
 use bridge_types::traits::OutboundChannel;
+use bridge_types::traits::OutboundChannelCancel;
 use bridge_types::traits::TimepointProvider;
 use bridge_types::EVMChainId;
 use bridge_types::GenericNetworkId;
@@ -45,8 +50,9 @@ use frame_support::traits::Get;
 use frame_support::weights::Weight;
 use frame_system::pallet_prelude::*;
 use frame_system::RawOrigin;
-use sp_core::H256;
+use sp_core::{H160, H256};
 use sp_runtime::DispatchError;
+use sp_std::prelude::*;
 
 use bridge_types::types::MessageNonce;
 
@@ -61,6 +67,22 @@ mod test;
 
 pub use pallet::*;
 
+/// Fee paid by the sender for every message to the network.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
+pub struct MessageFeeInfo<AssetId, Balance> {
+    /// Asset in which the fee is paid.
+    pub asset_id: AssetId,
+    /// Fee paid for every message.
+    pub base_fee: Balance,
+    /// Fee paid for every byte of the message payload.
+    pub per_byte_fee: Balance,
+    /// Fee paid for every unit of the message gas cost on EVM networks,
+    /// which is `max_gas` multiplied by the latest EVM base fee.
+    ///
+    /// Messages to the apps in [`GasFeeExemptApps`] don't pay it.
+    pub per_gas_fee: Balance,
+}
+
 #[frame_support::pallet]
 pub mod pallet {
     use super::*;
@@ -71,14 +93,26 @@ pub mod pallet {
     use bridge_types::types::GenericCommitmentWithBlock;
     use bridge_types::types::MessageId;
     use bridge_types::types::MessageStatus;
+    use bridge_types::utils::make_offchain_key;
     use bridge_types::GenericBridgeMessage;
     use bridge_types::GenericCommitment;
     use bridge_types::GenericNetworkId;
     use bridge_types::GenericTimepoint;
     use frame_support::log::debug;
     use frame_support::traits::StorageVersion;
+    use frame_system::ensure_root;
+    use sp_core::offchain::StorageKind;
     use sp_core::U256;
-    use sp_runtime::traits::Zero;
+    use sp_runtime::traits::{UniqueSaturatedFrom, Zero};
+    use sp_runtime::Percent;
+
+    type AccountIdOf<T> = <T as frame_system::Config>::AccountId;
+
+    pub type FeeAssetIdOf<T> =
+        <<T as Config>::BridgeAssetLocker as BridgeAssetLocker<AccountIdOf<T>>>::AssetId;
+
+    pub type FeeBalanceOf<T> =
+        <<T as Config>::BridgeAssetLocker as BridgeAssetLocker<AccountIdOf<T>>>::Balance;
 
     #[pallet::config]
     pub trait Config: frame_system::Config + pallet_timestamp::Config {
@@ -90,6 +124,10 @@ pub mod pallet {
         /// Max number of messages that can be queued and committed in one go for a given channel.
         type MaxMessagesPerCommit: Get<u32>;
 
+        /// Max number of batches which can wait in the overflow queue for a given channel.
+        #[pallet::constant]
+        type MaxOverflowBatches: Get<u32>;
+
         type AssetId;
 
         type Balance;
@@ -111,6 +149,15 @@ pub mod pallet {
         #[pallet::constant]
         type ThisNetworkId: Get<GenericNetworkId>;
 
+        /// Origin which can halt and resume networks.
+        type HaltOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Withdraws message fees to the network fee account.
+        type BridgeAssetLocker: BridgeAssetLocker<Self::AccountId>;
+
+        /// Provides base fee for EVM networks.
+        type EVMFeeHandler: EVMFeeHandler<FeeAssetIdOf<Self>>;
+
         /// Weight information for extrinsics in this pallet
         type WeightInfo: WeightInfo;
     }
@@ -127,6 +174,23 @@ pub mod pallet {
         10u32.into()
     }
 
+    /// Interval between committing messages for the network, overrides [`Interval`].
+    #[pallet::storage]
+    pub type NetworkIntervals<T: Config> =
+        StorageMap<_, Identity, GenericNetworkId, T::BlockNumber, OptionQuery>;
+
+    /// Fraction of [`Config::MaxMessagesPerCommit`] or [`Config::MaxGasPerCommit`]
+    /// after which the queue is committed without waiting for the interval.
+    #[pallet::storage]
+    pub type EarlyCommitThresholds<T: Config> =
+        StorageMap<_, Identity, GenericNetworkId, Percent, OptionQuery>;
+
+    /// Networks which queues reached [`EarlyCommitThresholds`] and should be committed
+    /// in `on_finalize`. Requests for the halted networks are kept until they are resumed.
+    #[pallet::storage]
+    pub(crate) type EarlyCommitRequests<T: Config> =
+        StorageMap<_, Identity, GenericNetworkId, (), OptionQuery>;
+
     /// Messages waiting to be committed. To update the queue, use `append_message_queue` and `take_message_queue` methods
     /// (to keep correct value in [QueuesTotalGas]).
     #[pallet::storage]
@@ -141,9 +205,50 @@ pub mod pallet {
     #[pallet::storage]
     pub type QueueTotalGas<T: Config> = StorageMap<_, Identity, GenericNetworkId, U256, ValueQuery>;
 
+    /// Batches which didn't fit into [`MessageQueues`], keyed by their future batch nonce.
+    /// On commit the next batch is moved to [`MessageQueues`].
+    #[pallet::storage]
+    pub(crate) type OverflowQueues<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        GenericNetworkId,
+        Identity,
+        u64,
+        BoundedVec<GenericBridgeMessage<T::MaxMessagePayloadSize>, T::MaxMessagesPerCommit>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    pub type OverflowQueueTotalGas<T: Config> =
+        StorageDoubleMap<_, Identity, GenericNetworkId, Identity, u64, U256, ValueQuery>;
+
+    /// Number of batches in [`OverflowQueues`] for the network.
+    #[pallet::storage]
+    pub type OverflowBatches<T: Config> =
+        StorageMap<_, Identity, GenericNetworkId, u32, ValueQuery>;
+
+    /// Positions of the cancelled messages in the batches which are not committed yet,
+    /// or which status is not reported yet. Cancelled messages are kept in the batch
+    /// as no-op tombstones, so ids of the other messages in the batch don't change.
+    #[pallet::storage]
+    pub type CancelledMessages<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        GenericNetworkId,
+        Identity,
+        u64,
+        Vec<MessageNonce>,
+        ValueQuery,
+    >;
+
     #[pallet::storage]
     pub type ChannelNonces<T: Config> = StorageMap<_, Identity, GenericNetworkId, u64, ValueQuery>;
 
+    /// Networks for which messages are neither queued nor committed until resumed.
+    #[pallet::storage]
+    pub type HaltedNetworks<T: Config> =
+        StorageMap<_, Identity, GenericNetworkId, bool, ValueQuery>;
+
     #[pallet::storage]
     pub type LatestCommitment<T: Config> = StorageMap<
         _,
@@ -166,6 +271,22 @@ pub mod pallet {
         200_000u32.into()
     }
 
+    /// Fee paid by the message sender, messages are free if not set.
+    #[pallet::storage]
+    pub type MessageFees<T: Config> = StorageMap<
+        _,
+        Identity,
+        GenericNetworkId,
+        MessageFeeInfo<FeeAssetIdOf<T>, FeeBalanceOf<T>>,
+        OptionQuery,
+    >;
+
+    /// EVM apps which charge the gas themselves with `EVMBridgeWithdrawFee`,
+    /// messages to them don't pay [`MessageFeeInfo::per_gas_fee`].
+    #[pallet::storage]
+    pub type GasFeeExemptApps<T: Config> =
+        StorageDoubleMap<_, Identity, EVMChainId, Identity, H160, (), OptionQuery>;
+
     /// The current storage version.
     const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
@@ -177,20 +298,58 @@ pub mod pallet {
 
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-        // Generate a message commitment every [`Interval`] blocks.
+        // Generate a message commitment every [`Interval`] blocks
+        // (or [`NetworkIntervals`] if set for the network).
         //
         // The commitment hash is included in an [`AuxiliaryDigestItem`] in the block header,
         // with the corresponding commitment is persisted offchain.
+        //
+        // Weight of the early commits done in `on_finalize` is reserved here
+        // for every network with [`EarlyCommitThresholds`] set.
         fn on_initialize(now: T::BlockNumber) -> Weight {
-            let interval = Self::interval();
-            let mut weight = Default::default();
-            if now % interval == Zero::zero() {
-                for chain_id in MessageQueues::<T>::iter_keys() {
-                    weight += Self::commit(chain_id);
+            let default_interval = Self::interval();
+            let mut weight = <T as Config>::WeightInfo::on_initialize_non_interval();
+            for network_id in MessageQueues::<T>::iter_keys() {
+                let interval = NetworkIntervals::<T>::get(network_id).unwrap_or(default_interval);
+                weight += T::DbWeight::get().reads(2);
+                if now % interval == Zero::zero() {
+                    weight += Self::commit(network_id);
+                    // Next batch from the overflow queue could be already filled
+                    if Self::should_commit_early(network_id) {
+                        EarlyCommitRequests::<T>::insert(network_id, ());
+                    } else {
+                        EarlyCommitRequests::<T>::remove(network_id);
+                    }
+                    weight += T::DbWeight::get().reads_writes(3, 1);
                 }
             }
+            let early_commit_weight = <T as Config>::WeightInfo::on_initialize(
+                T::MaxMessagesPerCommit::get(),
+                T::MaxMessagePayloadSize::get(),
+            )
+            .saturating_add(T::DbWeight::get().reads_writes(5, 2));
+            for _ in EarlyCommitThresholds::<T>::iter_keys() {
+                weight += early_commit_weight;
+            }
             weight
         }
+
+        // Commit the queues which reached [`EarlyCommitThresholds`] in this block.
+        fn on_finalize(_now: T::BlockNumber) {
+            for network_id in EarlyCommitRequests::<T>::iter_keys().collect::<Vec<_>>() {
+                // Request is kept until the network is resumed
+                if HaltedNetworks::<T>::get(network_id) {
+                    continue;
+                }
+                EarlyCommitRequests::<T>::remove(network_id);
+                Self::commit(network_id);
+                // Committed in the next block, so a full overflow queue is committed
+                // one batch per block
+                if Self::should_commit_early(network_id) {
+                    EarlyCommitRequests::<T>::insert(network_id, ());
+                }
+            }
+        }
     }
 
     #[pallet::event]
@@ -201,13 +360,46 @@ pub mod pallet {
             batch_nonce: u64,
             message_nonce: MessageNonce,
         },
+        /// Messages to the network are not accepted and committed.
+        NetworkHalted { network_id: GenericNetworkId },
+        /// Messages to the network are accepted and committed again.
+        NetworkResumed { network_id: GenericNetworkId },
+        /// Commitment interval for the network was updated, `None` means default interval.
+        IntervalUpdated {
+            network_id: GenericNetworkId,
+            interval: Option<T::BlockNumber>,
+        },
+        /// Early commit threshold for the network was updated.
+        EarlyCommitThresholdUpdated {
+            network_id: GenericNetworkId,
+            threshold: Option<Percent>,
+        },
+        /// Message fee for the network was updated, `None` means messages are free.
+        MessageFeeUpdated {
+            network_id: GenericNetworkId,
+            fee: Option<MessageFeeInfo<FeeAssetIdOf<T>, FeeBalanceOf<T>>>,
+        },
+        /// Gas used to submit a batch to the EVM network was updated.
+        EVMSubmitGasUpdated { chain_id: EVMChainId, gas: U256 },
+        /// EVM app was added to or removed from [`GasFeeExemptApps`].
+        GasFeeExemptionUpdated {
+            chain_id: EVMChainId,
+            target: H160,
+            exempt: bool,
+        },
+        /// Queued message was removed before commit.
+        MessageCancelled {
+            network_id: GenericNetworkId,
+            batch_nonce: u64,
+            message_nonce: MessageNonce,
+        },
     }
 
     #[pallet::error]
     pub enum Error<T> {
         /// The message payload exceeds byte limit.
         PayloadTooLarge,
-        /// No more messages can be queued for the channel during this commit cycle.
+        /// No more messages can be queued for the channel, overflow queue is full.
         QueueSizeLimitReached,
         /// Maximum gas for queued batch exceeds limit.
         MaxGasTooBig,
@@ -223,12 +415,22 @@ pub mod pallet {
         MessageGasLimitExceeded,
         /// Commitment consume too much gas
         CommitmentGasLimitExceeded,
+        /// Network is halted.
+        NetworkHalted,
+        /// Interval should be greater than zero.
+        ZeroInterval,
+        /// Message is not found in the queue.
+        MessageNotFound,
     }
 
     impl<T: Config> Pallet<T> {
         pub(crate) fn commit(network_id: GenericNetworkId) -> Weight {
             debug!("Commit substrate messages");
+            if HaltedNetworks::<T>::get(network_id) {
+                return <T as Config>::WeightInfo::on_initialize_no_messages();
+            }
             let messages = MessageQueues::<T>::take(network_id);
+            QueueTotalGas::<T>::remove(network_id);
             if messages.is_empty() {
                 return <T as Config>::WeightInfo::on_initialize_no_messages();
             }
@@ -237,8 +439,14 @@ pub mod pallet {
                 *nonce += 1;
                 *nonce
             });
+            Self::promote_overflow_batch(network_id, batch_nonce + 1);
 
-            for idx in 0..messages.len() as u64 {
+            let cancelled = match network_id {
+                // Status of the messages to Substrate networks is not reported back
+                GenericNetworkId::Sub(_) => CancelledMessages::<T>::take(network_id, batch_nonce),
+                _ => CancelledMessages::<T>::get(network_id, batch_nonce),
+            };
+            for idx in (0..messages.len() as u64).filter(|idx| !cancelled.contains(idx)) {
                 T::MessageStatusNotifier::update_status(
                     network_id,
                     MessageId::batched(T::ThisNetworkId::get(), network_id, batch_nonce, idx)
@@ -303,8 +511,27 @@ pub mod pallet {
                     return <T as Config>::WeightInfo::on_initialize_no_messages();
                 }
                 GenericNetworkId::TON(_) => {
-                    error!("TON messages are not supported yet by this channel (if you noticed this message, please report it)");
-                    return <T as Config>::WeightInfo::on_initialize_no_messages();
+                    let messages = messages.iter().fold(
+                        BoundedVec::default(),
+                        |mut messages, message| match message {
+                            GenericBridgeMessage::TON(message) => {
+                                if messages.try_push(message.clone()).is_err() {
+                                    error!("Messages limit exceeded, ignoring (if you noticed this message, please report it)");
+                                }
+                                messages
+                            }
+                            _ => {
+                                error!("Message is not a TON message, ignoring (if you noticed this message, please report it)");
+                                messages
+                            },
+                        },
+                    );
+                    GenericCommitment::TON(bridge_types::ton::Commitment::Outbound(
+                        bridge_types::ton::OutboundCommitment {
+                            messages,
+                            nonce: batch_nonce,
+                        },
+                    ))
                 }
             };
 
@@ -316,6 +543,10 @@ pub mod pallet {
                 commitment,
                 block_number: <frame_system::Pallet<T>>::block_number(),
             };
+            sp_io::offchain_index::set(
+                &make_offchain_key(network_id, batch_nonce),
+                &commitment.encode(),
+            );
             LatestCommitment::<T>::insert(network_id, commitment);
 
             <T as Config>::WeightInfo::on_initialize(
@@ -324,6 +555,108 @@ pub mod pallet {
             )
         }
 
+        /// Move the next batch from the overflow queue to [`MessageQueues`].
+        fn promote_overflow_batch(network_id: GenericNetworkId, batch_nonce: u64) {
+            let overflow_batches = OverflowBatches::<T>::get(network_id);
+            if overflow_batches == 0 {
+                return;
+            }
+            MessageQueues::<T>::insert(
+                network_id,
+                OverflowQueues::<T>::take(network_id, batch_nonce),
+            );
+            QueueTotalGas::<T>::insert(
+                network_id,
+                OverflowQueueTotalGas::<T>::take(network_id, batch_nonce),
+            );
+            OverflowBatches::<T>::insert(network_id, overflow_batches - 1);
+        }
+
+        /// Check if the queue reached [`EarlyCommitThresholds`] for the network.
+        fn should_commit_early(network_id: GenericNetworkId) -> bool {
+            let Some(threshold) = EarlyCommitThresholds::<T>::get(network_id) else {
+                return false;
+            };
+            let messages_count = MessageQueues::<T>::decode_len(network_id).unwrap_or(0) as u32;
+            if messages_count == 0 {
+                return false;
+            }
+            let total_gas = QueueTotalGas::<T>::get(network_id);
+            let gas_threshold = T::MaxGasPerCommit::get()
+                .saturating_mul(threshold.deconstruct().into())
+                / U256::from(100u8);
+            messages_count >= threshold.mul_ceil(T::MaxMessagesPerCommit::get())
+                || (!total_gas.is_zero() && total_gas >= gas_threshold)
+        }
+
+        /// Check if a message with `max_gas` can be added to the batch.
+        fn fits_batch(messages_count: u64, total_gas: U256, max_gas: U256) -> bool {
+            messages_count < T::MaxMessagesPerCommit::get() as u64
+                && (max_gas.is_zero()
+                    || total_gas.saturating_add(max_gas) < T::MaxGasPerCommit::get())
+        }
+
+        /// Get commitment with `batch_nonce` from the offchain storage.
+        ///
+        /// Offchain storage is available only in offchain context or if the `OffchainDbExt`
+        /// extension is registered, otherwise only the latest commitment could be returned.
+        pub fn commitment(
+            network_id: GenericNetworkId,
+            batch_nonce: u64,
+        ) -> Option<
+            GenericCommitmentWithBlock<
+                BlockNumberFor<T>,
+                T::MaxMessagesPerCommit,
+                T::MaxMessagePayloadSize,
+            >,
+        > {
+            if let Some(commitment) = LatestCommitment::<T>::get(network_id) {
+                if commitment.commitment.nonce() == batch_nonce {
+                    return Some(commitment);
+                }
+            }
+            let key = make_offchain_key(network_id, batch_nonce);
+            sp_io::offchain::local_storage_get(StorageKind::PERSISTENT, &key)
+                .and_then(|data| Decode::decode(&mut &data[..]).ok())
+        }
+
+        /// Calculate fee for the message to `network_id` with `payload_size` bytes and `max_gas`,
+        /// `None` means the message is free.
+        ///
+        /// `max_gas` is charged only for EVM networks and should be zero for the messages
+        /// to [`GasFeeExemptApps`].
+        pub fn message_fee(
+            network_id: GenericNetworkId,
+            payload_size: u32,
+            max_gas: U256,
+        ) -> Result<Option<(FeeAssetIdOf<T>, FeeBalanceOf<T>)>, DispatchError> {
+            let Some(fee_info) = MessageFees::<T>::get(network_id) else {
+                return Ok(None);
+            };
+            let mut fee = fee_info.base_fee.saturating_add(
+                fee_info
+                    .per_byte_fee
+                    .saturating_mul(FeeBalanceOf::<T>::from(payload_size)),
+            );
+            if let GenericNetworkId::EVM(chain_id) = network_id {
+                if !max_gas.is_zero() && !fee_info.per_gas_fee.is_zero() {
+                    let gas_cost =
+                        max_gas.saturating_mul(T::EVMFeeHandler::get_latest_base_fee(chain_id)?);
+                    let gas_cost = if gas_cost > U256::from(u128::MAX) {
+                        u128::MAX
+                    } else {
+                        gas_cost.as_u128()
+                    };
+                    fee = fee.saturating_add(
+                        fee_info
+                            .per_gas_fee
+                            .saturating_mul(FeeBalanceOf::<T>::unique_saturated_from(gas_cost)),
+                    );
+                }
+            }
+            Ok(Some((fee_info.asset_id, fee)))
+        }
+
         fn average_payload_size(
             messages: &[GenericBridgeMessage<T::MaxMessagePayloadSize>],
         ) -> usize {
@@ -334,6 +667,127 @@ pub mod pallet {
         }
     }
 
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Stop queuing and committing messages to `network_id` until it is resumed.
+        #[pallet::call_index(0)]
+        #[pallet::weight(0)]
+        pub fn halt_network(
+            origin: OriginFor<T>,
+            network_id: GenericNetworkId,
+        ) -> DispatchResultWithPostInfo {
+            T::HaltOrigin::ensure_origin(origin)?;
+            HaltedNetworks::<T>::insert(network_id, true);
+            Self::deposit_event(Event::NetworkHalted { network_id });
+            Ok(().into())
+        }
+
+        /// Queue and commit messages to `network_id` again.
+        #[pallet::call_index(1)]
+        #[pallet::weight(0)]
+        pub fn resume_network(
+            origin: OriginFor<T>,
+            network_id: GenericNetworkId,
+        ) -> DispatchResultWithPostInfo {
+            T::HaltOrigin::ensure_origin(origin)?;
+            HaltedNetworks::<T>::remove(network_id);
+            if Self::should_commit_early(network_id) {
+                EarlyCommitRequests::<T>::insert(network_id, ());
+            }
+            Self::deposit_event(Event::NetworkResumed { network_id });
+            Ok(().into())
+        }
+
+        /// Set commitment interval for `network_id`, `None` resets it to [`Interval`].
+        #[pallet::call_index(2)]
+        #[pallet::weight(0)]
+        pub fn set_network_interval(
+            origin: OriginFor<T>,
+            network_id: GenericNetworkId,
+            interval: Option<T::BlockNumber>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            if let Some(interval) = interval {
+                ensure!(interval > Zero::zero(), Error::<T>::ZeroInterval);
+            }
+            NetworkIntervals::<T>::set(network_id, interval);
+            Self::deposit_event(Event::IntervalUpdated {
+                network_id,
+                interval,
+            });
+            Ok(().into())
+        }
+
+        /// Set the queue fill level after which messages to `network_id` are committed
+        /// at the end of the block, `None` disables early commits.
+        #[pallet::call_index(3)]
+        #[pallet::weight(0)]
+        pub fn set_early_commit_threshold(
+            origin: OriginFor<T>,
+            network_id: GenericNetworkId,
+            threshold: Option<Percent>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            EarlyCommitThresholds::<T>::set(network_id, threshold);
+            Self::deposit_event(Event::EarlyCommitThresholdUpdated {
+                network_id,
+                threshold,
+            });
+            Ok(().into())
+        }
+
+        /// Set fee paid for messages to `network_id`, `None` makes messages free.
+        #[pallet::call_index(4)]
+        #[pallet::weight(0)]
+        pub fn set_message_fee(
+            origin: OriginFor<T>,
+            network_id: GenericNetworkId,
+            fee: Option<MessageFeeInfo<FeeAssetIdOf<T>, FeeBalanceOf<T>>>,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            MessageFees::<T>::set(network_id, fee.clone());
+            Self::deposit_event(Event::MessageFeeUpdated { network_id, fee });
+            Ok(().into())
+        }
+
+        /// Set gas used to submit a batch to the EVM network.
+        #[pallet::call_index(5)]
+        #[pallet::weight(0)]
+        pub fn set_evm_submit_gas(
+            origin: OriginFor<T>,
+            chain_id: EVMChainId,
+            gas: U256,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            EVMSubmitGas::<T>::insert(chain_id, gas);
+            Self::deposit_event(Event::EVMSubmitGasUpdated { chain_id, gas });
+            Ok(().into())
+        }
+
+        /// Add the EVM app with `target` address to [`GasFeeExemptApps`] or remove it.
+        #[pallet::call_index(6)]
+        #[pallet::weight(0)]
+        pub fn set_gas_fee_exemption(
+            origin: OriginFor<T>,
+            chain_id: EVMChainId,
+            target: H160,
+            exempt: bool,
+        ) -> DispatchResultWithPostInfo {
+            ensure_root(origin)?;
+            if exempt {
+                GasFeeExemptApps::<T>::insert(chain_id, target, ());
+            } else {
+                GasFeeExemptApps::<T>::remove(chain_id, target);
+            }
+            Self::deposit_event(Event::GasFeeExemptionUpdated {
+                chain_id,
+                target,
+                exempt,
+            });
+            Ok(().into())
+        }
+    }
+
     #[pallet::genesis_config]
     pub struct GenesisConfig<T: Config> {
         pub interval: T::BlockNumber,
@@ -362,17 +816,79 @@ pub mod pallet {
             message: GenericBridgeMessage<T::MaxMessagePayloadSize>,
         ) -> Result<H256, DispatchError> {
             debug!("Send message from {:?} to network {:?}", who, network_id);
-            let messages_count = MessageQueues::<T>::decode_len(network_id).unwrap_or(0) as u64;
             ensure!(
-                messages_count < T::MaxMessagesPerCommit::get() as u64,
-                Error::<T>::QueueSizeLimitReached,
+                !HaltedNetworks::<T>::get(network_id),
+                Error::<T>::NetworkHalted
             );
-            let batch_nonce = ChannelNonces::<T>::get(network_id)
+            let max_gas = match &message {
+                GenericBridgeMessage::EVM(message) => message.max_gas,
+                _ => U256::zero(),
+            };
+            let next_batch_nonce = ChannelNonces::<T>::get(network_id)
                 .checked_add(1)
                 .ok_or(Error::<T>::Overflow)?;
 
-            MessageQueues::<T>::try_append(network_id, message)
-                .map_err(|_| Error::<T>::QueueSizeLimitReached)?;
+            if let RawOrigin::Signed(who) = who {
+                let charged_gas = match (&message, network_id) {
+                    (GenericBridgeMessage::EVM(message), GenericNetworkId::EVM(chain_id))
+                        if !GasFeeExemptApps::<T>::contains_key(chain_id, message.target) =>
+                    {
+                        message.max_gas
+                    }
+                    _ => U256::zero(),
+                };
+                if let Some((asset_id, fee)) =
+                    Self::message_fee(network_id, message.payload().len() as u32, charged_gas)?
+                {
+                    T::BridgeAssetLocker::withdraw_fee(network_id, who, &asset_id, &fee)?;
+                }
+            }
+
+            // Messages are appended to the last batch, which is either
+            // the current queue or the last batch in the overflow queue.
+            let overflow_batches = OverflowBatches::<T>::get(network_id);
+            let mut batch_nonce = next_batch_nonce
+                .checked_add(overflow_batches as u64)
+                .ok_or(Error::<T>::Overflow)?;
+            let (mut messages_count, mut total_gas) = if overflow_batches == 0 {
+                (
+                    MessageQueues::<T>::decode_len(network_id).unwrap_or(0) as u64,
+                    QueueTotalGas::<T>::get(network_id),
+                )
+            } else {
+                (
+                    OverflowQueues::<T>::decode_len(network_id, batch_nonce).unwrap_or(0) as u64,
+                    OverflowQueueTotalGas::<T>::get(network_id, batch_nonce),
+                )
+            };
+            if !Self::fits_batch(messages_count, total_gas, max_gas) {
+                ensure!(
+                    overflow_batches < T::MaxOverflowBatches::get(),
+                    Error::<T>::QueueSizeLimitReached,
+                );
+                ensure!(
+                    Self::fits_batch(0, U256::zero(), max_gas),
+                    Error::<T>::CommitmentGasLimitExceeded
+                );
+                batch_nonce = batch_nonce.checked_add(1).ok_or(Error::<T>::Overflow)?;
+                messages_count = 0;
+                total_gas = U256::zero();
+                OverflowBatches::<T>::insert(network_id, overflow_batches + 1);
+            }
+            let total_gas = total_gas.saturating_add(max_gas);
+
+            if batch_nonce == next_batch_nonce {
+                MessageQueues::<T>::try_append(network_id, message)
+                    .map_err(|_| Error::<T>::QueueSizeLimitReached)?;
+                QueueTotalGas::<T>::insert(network_id, total_gas);
+            } else {
+                OverflowQueues::<T>::try_append(network_id, batch_nonce, message)
+                    .map_err(|_| Error::<T>::QueueSizeLimitReached)?;
+                OverflowQueueTotalGas::<T>::insert(network_id, batch_nonce, total_gas);
+            }
+            if Self::should_commit_early(network_id) {
+                EarlyCommitRequests::<T>::insert(network_id, ());
+            }
             Self::deposit_event(Event::MessageAccepted {
                 network_id,
                 batch_nonce,
@@ -386,6 +902,97 @@ pub mod pallet {
             )
             .hash())
         }
+
+        /// Replace the queued message with `message_id` with a tombstone and mark it as refunded.
+        ///
+        /// Tombstone is a no-op message with [`bridge_types::TOMBSTONE_PAYLOAD`] which keeps
+        /// the position of the cancelled message in the batch, so ids of the other messages
+        /// stay the same both here and in the target network. Target networks skip tombstones,
+        /// status reports for them are ignored.
+        pub fn cancel_message(
+            network_id: GenericNetworkId,
+            message_id: H256,
+        ) -> Result<GenericBridgeMessage<T::MaxMessagePayloadSize>, DispatchError> {
+            let next_batch_nonce = ChannelNonces::<T>::get(network_id)
+                .checked_add(1)
+                .ok_or(Error::<T>::Overflow)?;
+            let last_batch_nonce = next_batch_nonce
+                .checked_add(OverflowBatches::<T>::get(network_id) as u64)
+                .ok_or(Error::<T>::Overflow)?;
+            let this_network_id = T::ThisNetworkId::get();
+            let (batch_nonce, message_nonce) = (next_batch_nonce..=last_batch_nonce)
+                .find_map(|batch_nonce| {
+                    let messages_count = if batch_nonce == next_batch_nonce {
+                        MessageQueues::<T>::decode_len(network_id)
+                    } else {
+                        OverflowQueues::<T>::decode_len(network_id, batch_nonce)
+                    }
+                    .unwrap_or(0) as u64;
+                    (0..messages_count)
+                        .find(|idx| {
+                            MessageId::batched(this_network_id, network_id, batch_nonce, *idx)
+                                .hash()
+                                == message_id
+                        })
+                        .map(|idx| (batch_nonce, idx))
+                })
+                .ok_or(Error::<T>::MessageNotFound)?;
+            let mut cancelled = CancelledMessages::<T>::get(network_id, batch_nonce);
+            ensure!(
+                !cancelled.contains(&message_nonce),
+                Error::<T>::MessageNotFound
+            );
+
+            let replace_with_tombstone =
+                |messages: &mut BoundedVec<_, _>| -> Option<GenericBridgeMessage<_>> {
+                    let message = messages.get_mut(message_nonce as usize)?;
+                    let tombstone = message.tombstone();
+                    Some(sp_std::mem::replace(message, tombstone))
+                };
+            let message = if batch_nonce == next_batch_nonce {
+                MessageQueues::<T>::mutate(network_id, replace_with_tombstone)
+            } else {
+                OverflowQueues::<T>::mutate(network_id, batch_nonce, replace_with_tombstone)
+            }
+            .ok_or(Error::<T>::MessageNotFound)?;
+            let max_gas = match &message {
+                GenericBridgeMessage::EVM(message) => message.max_gas,
+                _ => U256::zero(),
+            };
+            if batch_nonce == next_batch_nonce {
+                QueueTotalGas::<T>::mutate(network_id, |total_gas| {
+                    *total_gas = total_gas.saturating_sub(max_gas)
+                });
+            } else {
+                OverflowQueueTotalGas::<T>::mutate(network_id, batch_nonce, |total_gas| {
+                    *total_gas = total_gas.saturating_sub(max_gas)
+                });
+            }
+            cancelled.push(message_nonce);
+            CancelledMessages::<T>::insert(network_id, batch_nonce, cancelled);
+
+            T::MessageStatusNotifier::update_status(
+                network_id,
+                message_id,
+                MessageStatus::Refunded,
+                T::TimepointProvider::get_timepoint(),
+            );
+            Self::deposit_event(Event::MessageCancelled {
+                network_id,
+                batch_nonce,
+                message_nonce,
+            });
+            Ok(message)
+        }
+    }
+}
+
+impl<T: Config> CancelledMessagesProvider for Pallet<T> {
+    fn take_cancelled_messages(
+        network_id: GenericNetworkId,
+        batch_nonce: u64,
+    ) -> Vec<MessageNonce> {
+        CancelledMessages::<T>::take(network_id, batch_nonce)
     }
 }
 
@@ -428,14 +1035,6 @@ impl<T: Config> OutboundChannel<EVMChainId, T::AccountId, AdditionalEVMOutboundD
             additional_data.max_gas < T::MaxGasPerMessage::get(),
             Error::<T>::MessageGasLimitExceeded
         );
-        QueueTotalGas::<T>::try_mutate(GenericNetworkId::EVM(network_id), |total_gas| {
-            *total_gas = total_gas.saturating_add(additional_data.max_gas);
-            ensure!(
-                *total_gas < T::MaxGasPerCommit::get(),
-                Error::<T>::CommitmentGasLimitExceeded
-            );
-            Ok::<(), DispatchError>(())
-        })?;
         let message = bridge_types::evm::Message {
             payload: payload
                 .to_vec()
@@ -456,6 +1055,91 @@ impl<T: Config> OutboundChannel<EVMChainId, T::AccountId, AdditionalEVMOutboundD
     }
 }
 
+impl<T: Config> OutboundChannel<TonNetworkId, T::AccountId, AdditionalTONOutboundData>
+    for Pallet<T>
+{
+    /// Submit message on the outbound channel
+    fn submit(
+        network_id: TonNetworkId,
+        who: &RawOrigin<T::AccountId>,
+        payload: &[u8],
+        additional_data: AdditionalTONOutboundData,
+    ) -> Result<H256, DispatchError> {
+        let message = bridge_types::ton::Message {
+            payload: payload
+                .to_vec()
+                .try_into()
+                .map_err(|_| Error::<T>::PayloadTooLarge)?,
+            target: additional_data.target,
+        };
+        Self::submit_message(
+            network_id.into(),
+            who,
+            bridge_types::GenericBridgeMessage::TON(message),
+        )
+    }
+
+    fn submit_weight() -> Weight {
+        <T as Config>::WeightInfo::submit()
+    }
+}
+
+impl<T: Config> OutboundChannelCancel<SubNetworkId, ()> for Pallet<T> {
+    fn cancel(network_id: SubNetworkId, message_id: H256) -> Result<(Vec<u8>, ()), DispatchError> {
+        match Self::cancel_message(network_id.into(), message_id)? {
+            bridge_types::GenericBridgeMessage::Sub(message) => Ok((message.payload.into(), ())),
+            _ => Err(Error::<T>::MessageTypeIsNotSupported.into()),
+        }
+    }
+
+    fn cancel_weight() -> Weight {
+        <T as Config>::WeightInfo::cancel_message(T::MaxOverflowBatches::get() + 1)
+    }
+}
+
+impl<T: Config> OutboundChannelCancel<EVMChainId, AdditionalEVMOutboundData> for Pallet<T> {
+    fn cancel(
+        network_id: EVMChainId,
+        message_id: H256,
+    ) -> Result<(Vec<u8>, AdditionalEVMOutboundData), DispatchError> {
+        match Self::cancel_message(network_id.into(), message_id)? {
+            bridge_types::GenericBridgeMessage::EVM(message) => Ok((
+                message.payload.into(),
+                AdditionalEVMOutboundData {
+                    max_gas: message.max_gas,
+                    target: message.target,
+                },
+            )),
+            _ => Err(Error::<T>::MessageTypeIsNotSupported.into()),
+        }
+    }
+
+    fn cancel_weight() -> Weight {
+        <T as Config>::WeightInfo::cancel_message(T::MaxOverflowBatches::get() + 1)
+    }
+}
+
+impl<T: Config> OutboundChannelCancel<TonNetworkId, AdditionalTONOutboundData> for Pallet<T> {
+    fn cancel(
+        network_id: TonNetworkId,
+        message_id: H256,
+    ) -> Result<(Vec<u8>, AdditionalTONOutboundData), DispatchError> {
+        match Self::cancel_message(network_id.into(), message_id)? {
+            bridge_types::GenericBridgeMessage::TON(message) => Ok((
+                message.payload.into(),
+                AdditionalTONOutboundData {
+                    target: message.target,
+                },
+            )),
+            _ => Err(Error::<T>::MessageTypeIsNotSupported.into()),
+        }
+    }
+
+    fn cancel_weight() -> Weight {
+        <T as Config>::WeightInfo::cancel_message(T::MaxOverflowBatches::get() + 1)
+    }
+}
+
 impl<T: Config> EVMOutboundChannel for Pallet<T> {
     fn submit_gas(network_id: EVMChainId) -> Result<sp_core::U256, DispatchError> {
         Ok(EVMSubmitGas::<T>::get(network_id))
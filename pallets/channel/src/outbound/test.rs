@@ -29,22 +29,31 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use super::*;
-use bridge_types::GenericNetworkId;
+use bridge_types::evm::AdditionalEVMOutboundData;
+use bridge_types::ton::{AdditionalTONOutboundData, TonAddress, TonNetworkId};
+use bridge_types::types::MessageId;
+use bridge_types::{EVMChainId, GenericCommitment, GenericNetworkId};
 use codec::{Decode, Encode, MaxEncodedLen};
 use currencies::BasicCurrencyAdapter;
 
-use bridge_types::traits::{OutboundChannel, TimepointProvider};
-use frame_support::traits::{Everything, GenesisBuild};
+use bridge_types::test_utils::BridgeAssetLockerImpl;
+use bridge_types::traits::{
+    CancelledMessagesProvider, EVMFeeHandler, OutboundChannel, OutboundChannelCancel,
+    TimepointProvider,
+};
+use frame_support::traits::{Everything, GenesisBuild, Hooks};
 use frame_support::{assert_noop, assert_ok, parameter_types, Deserialize, Serialize};
 use frame_system::RawOrigin;
 use scale_info::TypeInfo;
-use sp_core::H256;
+use sp_core::offchain::testing::TestOffchainExt;
+use sp_core::offchain::{OffchainDbExt, OffchainWorkerExt};
+use sp_core::{H160, H256, U256};
 use sp_keyring::AccountKeyring as Keyring;
 use sp_runtime::testing::Header;
 use sp_runtime::traits::{BlakeTwo256, IdentifyAccount, IdentityLookup, Verify};
-use sp_runtime::{AccountId32, MultiSignature};
+use sp_runtime::{AccountId32, MultiSignature, Percent};
 use sp_std::convert::From;
-use traits::parameter_type_with_key;
+use traits::{parameter_type_with_key, MultiCurrency};
 
 use crate::outbound as bridge_outbound_channel;
 
@@ -64,7 +73,7 @@ frame_support::construct_runtime!(
         Tokens: tokens::{Pallet, Call, Config<T>, Storage, Event<T>},
         Currencies: currencies::{Pallet, Call, Storage},
         Balances: pallet_balances::{Pallet, Call, Storage, Event<T>},
-        BridgeOutboundChannel: bridge_outbound_channel::{Pallet, Config<T>, Storage, Event<T>},
+        BridgeOutboundChannel: bridge_outbound_channel::{Pallet, Call, Config<T>, Storage, Event<T>},
     }
 );
 
@@ -178,6 +187,7 @@ parameter_types! {
 parameter_types! {
     pub const MaxMessagePayloadSize: u32 = 128;
     pub const MaxMessagesPerCommit: u32 = 5;
+    pub const MaxOverflowBatches: u32 = 2;
     pub const ThisNetworkId: GenericNetworkId = GenericNetworkId::Sub(SubNetworkId::Mainnet);
 }
 
@@ -189,6 +199,30 @@ impl TimepointProvider for GenericTimepointProvider {
     }
 }
 
+pub struct EVMFeeHandlerImpl;
+
+impl EVMFeeHandler<AssetId> for EVMFeeHandlerImpl {
+    fn get_latest_base_fee(_network_id: EVMChainId) -> Result<U256, DispatchError> {
+        Ok(10u64.into())
+    }
+
+    fn get_network_fee_asset(_network_id: EVMChainId) -> Result<AssetId, DispatchError> {
+        Ok(AssetId::Eth)
+    }
+
+    fn on_fee_paid(_network_id: EVMChainId, _relayer: H160, _amount: U256) {}
+
+    fn update_base_fee(_network_id: EVMChainId, _new_base_fee: U256, _evm_block_number: u64) {}
+
+    fn can_update_base_fee(
+        _network_id: EVMChainId,
+        _new_base_fee: U256,
+        _evm_block_number: u64,
+    ) -> bool {
+        true
+    }
+}
+
 parameter_types! {
     pub const BridgeMaxTotalGasLimit: u64 = 5_000_000;
     pub const BridgeMaxGasPerMessage: u64 = 5_000_000;
@@ -198,6 +232,7 @@ impl bridge_outbound_channel::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type MaxMessagePayloadSize = MaxMessagePayloadSize;
     type MaxMessagesPerCommit = MaxMessagesPerCommit;
+    type MaxOverflowBatches = MaxOverflowBatches;
     type MessageStatusNotifier = ();
     type AuxiliaryDigestHandler = ();
     type AssetId = ();
@@ -207,6 +242,9 @@ impl bridge_outbound_channel::Config for Test {
     type ThisNetworkId = ThisNetworkId;
     type MaxGasPerCommit = BridgeMaxTotalGasLimit;
     type MaxGasPerMessage = BridgeMaxGasPerMessage;
+    type HaltOrigin = frame_system::EnsureRoot<AccountId>;
+    type BridgeAssetLocker = BridgeAssetLockerImpl<Currencies>;
+    type EVMFeeHandler = EVMFeeHandlerImpl;
 }
 
 impl pallet_timestamp::Config for Test {
@@ -271,7 +309,7 @@ fn test_submit_exceeds_queue_limit() {
     new_tester().execute_with(|| {
         let who: AccountId = Keyring::Bob.into();
 
-        let max_messages = MaxMessagesPerCommit::get();
+        let max_messages = MaxMessagesPerCommit::get() * (1 + MaxOverflowBatches::get());
         (0..max_messages).for_each(|_| {
             BridgeOutboundChannel::submit(
                 BASE_NETWORK_ID.sub().unwrap(),
@@ -331,3 +369,671 @@ fn test_submit_fails_on_nonce_overflow() {
         );
     });
 }
+
+#[test]
+fn test_submit_and_commit_ton() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        let network_id = TonNetworkId::Testnet;
+        let target = TonAddress::new(0, H256::repeat_byte(1));
+
+        assert_ok!(BridgeOutboundChannel::submit(
+            network_id,
+            &RawOrigin::Signed(who),
+            &[0, 1, 2],
+            AdditionalTONOutboundData { target }
+        ));
+        BridgeOutboundChannel::commit(network_id.into());
+        assert_eq!(
+            <ChannelNonces<Test>>::get(GenericNetworkId::from(network_id)),
+            1
+        );
+
+        let commitment = <LatestCommitment<Test>>::get(GenericNetworkId::from(network_id))
+            .unwrap()
+            .commitment;
+        match commitment {
+            GenericCommitment::TON(bridge_types::ton::Commitment::Outbound(commitment)) => {
+                assert_eq!(commitment.nonce, 1);
+                assert_eq!(commitment.messages.len(), 1);
+                assert_eq!(commitment.messages[0].target, target);
+                assert_eq!(commitment.messages[0].payload.to_vec(), vec![0, 1, 2]);
+            }
+            _ => panic!("unexpected commitment"),
+        }
+    });
+}
+
+#[test]
+fn test_submit_and_commit_to_halted_network() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+
+        assert_ok!(BridgeOutboundChannel::submit(
+            BASE_NETWORK_ID.sub().unwrap(),
+            &RawOrigin::Signed(who.clone()),
+            &[0, 1, 2],
+            ()
+        ));
+
+        assert_noop!(
+            BridgeOutboundChannel::halt_network(
+                RuntimeOrigin::signed(who.clone()),
+                BASE_NETWORK_ID
+            ),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(BridgeOutboundChannel::halt_network(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID
+        ));
+        System::assert_last_event(RuntimeEvent::BridgeOutboundChannel(Event::NetworkHalted {
+            network_id: BASE_NETWORK_ID,
+        }));
+
+        assert_noop!(
+            BridgeOutboundChannel::submit(
+                BASE_NETWORK_ID.sub().unwrap(),
+                &RawOrigin::Signed(who),
+                &[0, 1, 2],
+                ()
+            ),
+            Error::<Test>::NetworkHalted,
+        );
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 0);
+        assert_eq!(<MessageQueues<Test>>::get(BASE_NETWORK_ID).len(), 1);
+
+        assert_ok!(BridgeOutboundChannel::resume_network(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID
+        ));
+        System::assert_last_event(RuntimeEvent::BridgeOutboundChannel(Event::NetworkResumed {
+            network_id: BASE_NETWORK_ID,
+        }));
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+        assert!(<MessageQueues<Test>>::get(BASE_NETWORK_ID).is_empty());
+    });
+}
+
+#[test]
+fn test_submit_to_overflow_queue() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        let max_messages = MaxMessagesPerCommit::get() as u64;
+
+        for i in 0..max_messages + 2 {
+            let message_id = BridgeOutboundChannel::submit(
+                BASE_NETWORK_ID.sub().unwrap(),
+                &RawOrigin::Signed(who.clone()),
+                &[0, 1, 2],
+                (),
+            )
+            .unwrap();
+            let (batch_nonce, message_nonce) = (1 + i / max_messages, i % max_messages);
+            assert_eq!(
+                message_id,
+                MessageId::batched(
+                    ThisNetworkId::get(),
+                    BASE_NETWORK_ID,
+                    batch_nonce,
+                    message_nonce
+                )
+                .hash()
+            );
+            System::assert_last_event(RuntimeEvent::BridgeOutboundChannel(
+                Event::MessageAccepted {
+                    network_id: BASE_NETWORK_ID,
+                    batch_nonce,
+                    message_nonce,
+                },
+            ));
+        }
+        assert_eq!(<OverflowBatches<Test>>::get(BASE_NETWORK_ID), 1);
+        assert_eq!(<OverflowQueues<Test>>::get(BASE_NETWORK_ID, 2).len(), 2);
+
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+        assert_eq!(<OverflowBatches<Test>>::get(BASE_NETWORK_ID), 0);
+        assert_eq!(<MessageQueues<Test>>::get(BASE_NETWORK_ID).len(), 2);
+
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 2);
+        assert!(<MessageQueues<Test>>::get(BASE_NETWORK_ID).is_empty());
+    });
+}
+
+#[test]
+fn test_submit_evm_to_overflow_queue_by_gas() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        let chain_id = EVMChainId::from_low_u64_be(1);
+        let network_id = GenericNetworkId::EVM(chain_id);
+        let additional = AdditionalEVMOutboundData {
+            target: Default::default(),
+            max_gas: 3_000_000u64.into(),
+        };
+
+        for _ in 0..2 {
+            assert_ok!(BridgeOutboundChannel::submit(
+                chain_id,
+                &RawOrigin::Signed(who.clone()),
+                &[0, 1, 2],
+                additional
+            ));
+        }
+        assert_eq!(<MessageQueues<Test>>::get(network_id).len(), 1);
+        assert_eq!(<QueueTotalGas<Test>>::get(network_id), 3_000_000u64.into());
+        assert_eq!(<OverflowQueues<Test>>::get(network_id, 2).len(), 1);
+
+        BridgeOutboundChannel::commit(network_id);
+        assert_eq!(<MessageQueues<Test>>::get(network_id).len(), 1);
+        assert_eq!(<QueueTotalGas<Test>>::get(network_id), 3_000_000u64.into());
+        BridgeOutboundChannel::commit(network_id);
+        assert_eq!(<ChannelNonces<Test>>::get(network_id), 2);
+        assert!(<QueueTotalGas<Test>>::get(network_id).is_zero());
+    });
+}
+
+#[test]
+fn test_submit_with_message_fee() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        let chain_id = EVMChainId::from_low_u64_be(1);
+        let network_id = GenericNetworkId::EVM(chain_id);
+        let additional = AdditionalEVMOutboundData {
+            target: Default::default(),
+            max_gas: 10_000u64.into(),
+        };
+
+        let fee = MessageFeeInfo {
+            asset_id: AssetId::Eth,
+            base_fee: 100,
+            per_byte_fee: 10,
+            per_gas_fee: 2,
+        };
+        assert_ok!(BridgeOutboundChannel::set_message_fee(
+            RuntimeOrigin::root(),
+            network_id,
+            Some(fee.clone())
+        ));
+        System::assert_last_event(RuntimeEvent::BridgeOutboundChannel(
+            Event::MessageFeeUpdated {
+                network_id,
+                fee: Some(fee),
+            },
+        ));
+
+        // base fee + payload fee + max gas * EVM base fee * gas fee
+        let expected_fee = 100 + 3 * 10 + 10_000 * 10 * 2;
+        assert_eq!(
+            BridgeOutboundChannel::message_fee(network_id, 3, additional.max_gas),
+            Ok(Some((AssetId::Eth, expected_fee)))
+        );
+
+        Tokens::deposit(AssetId::Eth, &who, expected_fee).unwrap();
+        assert_ok!(BridgeOutboundChannel::submit(
+            chain_id,
+            &RawOrigin::Signed(who.clone()),
+            &[0, 1, 2],
+            additional
+        ));
+        assert_eq!(Tokens::free_balance(AssetId::Eth, &who), 0);
+        assert_eq!(
+            Tokens::free_balance(
+                AssetId::Eth,
+                &BridgeAssetLockerImpl::<()>::bridge_fee_account(network_id)
+            ),
+            expected_fee
+        );
+
+        assert_noop!(
+            BridgeOutboundChannel::submit(
+                chain_id,
+                &RawOrigin::Signed(who),
+                &[0, 1, 2],
+                additional
+            ),
+            tokens::Error::<Test>::BalanceTooLow
+        );
+        // Messages from root are free
+        assert_ok!(BridgeOutboundChannel::submit(
+            chain_id,
+            &RawOrigin::Root,
+            &[0, 1, 2],
+            additional
+        ));
+    });
+}
+
+#[test]
+fn test_gas_fee_exempt_apps() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        let chain_id = EVMChainId::from_low_u64_be(1);
+        let network_id = GenericNetworkId::EVM(chain_id);
+        let additional = AdditionalEVMOutboundData {
+            target: H160::repeat_byte(1),
+            max_gas: 10_000u64.into(),
+        };
+        assert_ok!(BridgeOutboundChannel::set_message_fee(
+            RuntimeOrigin::root(),
+            network_id,
+            Some(MessageFeeInfo {
+                asset_id: AssetId::Eth,
+                base_fee: 100,
+                per_byte_fee: 10,
+                per_gas_fee: 2,
+            })
+        ));
+        assert_ok!(BridgeOutboundChannel::set_gas_fee_exemption(
+            RuntimeOrigin::root(),
+            chain_id,
+            additional.target,
+            true
+        ));
+        System::assert_last_event(RuntimeEvent::BridgeOutboundChannel(
+            Event::GasFeeExemptionUpdated {
+                chain_id,
+                target: additional.target,
+                exempt: true,
+            },
+        ));
+
+        // The app charges the gas itself
+        let expected_fee = 100 + 3 * 10;
+        Tokens::deposit(AssetId::Eth, &who, expected_fee).unwrap();
+        assert_ok!(BridgeOutboundChannel::submit(
+            chain_id,
+            &RawOrigin::Signed(who.clone()),
+            &[0, 1, 2],
+            additional
+        ));
+        assert_eq!(Tokens::free_balance(AssetId::Eth, &who), 0);
+
+        assert_ok!(BridgeOutboundChannel::set_gas_fee_exemption(
+            RuntimeOrigin::root(),
+            chain_id,
+            additional.target,
+            false
+        ));
+        Tokens::deposit(AssetId::Eth, &who, expected_fee).unwrap();
+        assert_noop!(
+            BridgeOutboundChannel::submit(
+                chain_id,
+                &RawOrigin::Signed(who),
+                &[0, 1, 2],
+                additional
+            ),
+            tokens::Error::<Test>::BalanceTooLow
+        );
+    });
+}
+
+#[test]
+fn test_cancel_message() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        let chain_id = EVMChainId::from_low_u64_be(1);
+        let network_id = GenericNetworkId::EVM(chain_id);
+        let additional = AdditionalEVMOutboundData {
+            target: H160::repeat_byte(1),
+            max_gas: 3_000_000u64.into(),
+        };
+
+        let message_ids = (0..2)
+            .map(|_| {
+                BridgeOutboundChannel::submit(
+                    chain_id,
+                    &RawOrigin::Signed(who.clone()),
+                    &[0, 1, 2],
+                    additional,
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(<OverflowBatches<Test>>::get(network_id), 1);
+
+        assert_eq!(
+            <BridgeOutboundChannel as OutboundChannelCancel<_, _>>::cancel(
+                chain_id,
+                message_ids[0]
+            ),
+            Ok((vec![0, 1, 2], additional))
+        );
+        System::assert_last_event(RuntimeEvent::BridgeOutboundChannel(
+            Event::MessageCancelled {
+                network_id,
+                batch_nonce: 1,
+                message_nonce: 0,
+            },
+        ));
+        // Tombstone keeps the position of the message
+        assert_eq!(<MessageQueues<Test>>::get(network_id).len(), 1);
+        assert!(<QueueTotalGas<Test>>::get(network_id).is_zero());
+        assert_eq!(<CancelledMessages<Test>>::get(network_id, 1), vec![0]);
+        assert_noop!(
+            <BridgeOutboundChannel as OutboundChannelCancel<_, _>>::cancel(
+                chain_id,
+                message_ids[0]
+            ),
+            Error::<Test>::MessageNotFound
+        );
+
+        assert_ok!(
+            <BridgeOutboundChannel as OutboundChannelCancel<_, _>>::cancel(
+                chain_id,
+                message_ids[1]
+            )
+        );
+        assert_eq!(<OverflowBatches<Test>>::get(network_id), 1);
+        assert_eq!(<OverflowQueues<Test>>::get(network_id, 2).len(), 1);
+        assert!(<OverflowQueueTotalGas<Test>>::get(network_id, 2).is_zero());
+
+        BridgeOutboundChannel::commit(network_id);
+        assert_eq!(<ChannelNonces<Test>>::get(network_id), 1);
+        match <LatestCommitment<Test>>::get(network_id)
+            .unwrap()
+            .commitment
+        {
+            GenericCommitment::EVM(bridge_types::evm::Commitment::Outbound(commitment)) => {
+                assert_eq!(commitment.messages.len(), 1);
+                assert_eq!(
+                    commitment.messages[0].payload.as_slice(),
+                    bridge_types::TOMBSTONE_PAYLOAD
+                );
+                assert_eq!(commitment.messages[0].target, H160::zero());
+                assert!(commitment.messages[0].max_gas.is_zero());
+                assert!(commitment.total_max_gas.is_zero());
+            }
+            _ => panic!("unexpected commitment"),
+        }
+
+        // Positions are kept until the status report for the batch
+        assert_eq!(
+            <BridgeOutboundChannel as CancelledMessagesProvider>::take_cancelled_messages(
+                network_id, 1
+            ),
+            vec![0]
+        );
+        assert!(!<CancelledMessages<Test>>::contains_key(network_id, 1));
+        assert_eq!(<CancelledMessages<Test>>::get(network_id, 2), vec![0]);
+    });
+}
+
+#[test]
+fn test_cancel_message_keeps_message_ids() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+
+        let message_ids = [[0, 1, 2], [3, 4, 5]]
+            .iter()
+            .map(|payload| {
+                BridgeOutboundChannel::submit(
+                    SubNetworkId::Mainnet,
+                    &RawOrigin::Signed(who.clone()),
+                    payload,
+                    (),
+                )
+                .unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        assert_ok!(
+            <BridgeOutboundChannel as OutboundChannelCancel<_, _>>::cancel(
+                SubNetworkId::Mainnet,
+                message_ids[0]
+            )
+        );
+        // Other messages can be cancelled by the same id
+        assert_eq!(
+            <BridgeOutboundChannel as OutboundChannelCancel<_, _>>::cancel(
+                SubNetworkId::Mainnet,
+                message_ids[1]
+            ),
+            Ok((vec![3, 4, 5], ()))
+        );
+        // and new messages don't reuse ids of the cancelled ones
+        let message_id = BridgeOutboundChannel::submit(
+            SubNetworkId::Mainnet,
+            &RawOrigin::Signed(who),
+            &[6, 7, 8],
+            (),
+        )
+        .unwrap();
+        assert_eq!(
+            message_id,
+            MessageId::batched(
+                <Test as Config>::ThisNetworkId::get(),
+                BASE_NETWORK_ID,
+                1,
+                2
+            )
+            .hash()
+        );
+
+        BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        match <LatestCommitment<Test>>::get(BASE_NETWORK_ID)
+            .unwrap()
+            .commitment
+        {
+            GenericCommitment::Sub(commitment) => {
+                let payloads = commitment
+                    .messages
+                    .iter()
+                    .map(|message| message.payload.to_vec())
+                    .collect::<Vec<_>>();
+                assert_eq!(payloads, vec![vec![], vec![], vec![6, 7, 8]]);
+            }
+            _ => panic!("unexpected commitment"),
+        }
+        // Substrate networks don't report status back
+        assert!(!<CancelledMessages<Test>>::contains_key(BASE_NETWORK_ID, 1));
+    });
+}
+
+#[test]
+fn test_commit_with_network_interval() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        let other_network_id = GenericNetworkId::Sub(SubNetworkId::Kusama);
+
+        assert_noop!(
+            BridgeOutboundChannel::set_network_interval(
+                RuntimeOrigin::root(),
+                BASE_NETWORK_ID,
+                Some(0)
+            ),
+            Error::<Test>::ZeroInterval
+        );
+        assert_ok!(BridgeOutboundChannel::set_network_interval(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID,
+            Some(3)
+        ));
+        System::assert_last_event(RuntimeEvent::BridgeOutboundChannel(
+            Event::IntervalUpdated {
+                network_id: BASE_NETWORK_ID,
+                interval: Some(3),
+            },
+        ));
+
+        for network_id in [BASE_NETWORK_ID, other_network_id] {
+            assert_ok!(BridgeOutboundChannel::submit(
+                network_id.sub().unwrap(),
+                &RawOrigin::Signed(who.clone()),
+                &[0, 1, 2],
+                ()
+            ));
+        }
+
+        BridgeOutboundChannel::on_initialize(3);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+        assert_eq!(<ChannelNonces<Test>>::get(other_network_id), 0);
+
+        BridgeOutboundChannel::on_initialize(10);
+        assert_eq!(<ChannelNonces<Test>>::get(other_network_id), 1);
+    });
+}
+
+#[test]
+fn test_early_commit_by_messages_count() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+
+        assert_ok!(BridgeOutboundChannel::set_early_commit_threshold(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID,
+            Some(Percent::from_percent(60))
+        ));
+
+        // 60% of 5 messages
+        for _ in 0..3 {
+            assert_ok!(BridgeOutboundChannel::submit(
+                BASE_NETWORK_ID.sub().unwrap(),
+                &RawOrigin::Signed(who.clone()),
+                &[0, 1, 2],
+                ()
+            ));
+        }
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 0);
+
+        // Committed in the same block
+        BridgeOutboundChannel::on_finalize(1);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+        assert!(<MessageQueues<Test>>::get(BASE_NETWORK_ID).is_empty());
+        assert!(<EarlyCommitRequests<Test>>::get(BASE_NETWORK_ID).is_none());
+    });
+}
+
+#[test]
+fn test_early_commit_weight_is_reserved() {
+    new_tester().execute_with(|| {
+        let full_commit_weight = <() as WeightInfo>::on_initialize(
+            MaxMessagesPerCommit::get(),
+            MaxMessagePayloadSize::get(),
+        );
+        assert!(!BridgeOutboundChannel::on_initialize(1).all_gte(full_commit_weight));
+
+        assert_ok!(BridgeOutboundChannel::set_early_commit_threshold(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID,
+            Some(Percent::from_percent(60))
+        ));
+        assert!(BridgeOutboundChannel::on_initialize(1).all_gte(full_commit_weight));
+    });
+}
+
+#[test]
+fn test_early_commit_request_is_kept_for_halted_network() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+
+        assert_ok!(BridgeOutboundChannel::set_early_commit_threshold(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID,
+            Some(Percent::from_percent(60))
+        ));
+        for _ in 0..3 {
+            assert_ok!(BridgeOutboundChannel::submit(
+                BASE_NETWORK_ID.sub().unwrap(),
+                &RawOrigin::Signed(who.clone()),
+                &[0, 1, 2],
+                ()
+            ));
+        }
+        assert_ok!(BridgeOutboundChannel::halt_network(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID
+        ));
+        BridgeOutboundChannel::on_finalize(1);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 0);
+        assert!(<EarlyCommitRequests<Test>>::get(BASE_NETWORK_ID).is_some());
+
+        assert_ok!(BridgeOutboundChannel::resume_network(
+            RuntimeOrigin::root(),
+            BASE_NETWORK_ID
+        ));
+        BridgeOutboundChannel::on_finalize(2);
+        assert_eq!(<ChannelNonces<Test>>::get(BASE_NETWORK_ID), 1);
+        assert!(<EarlyCommitRequests<Test>>::get(BASE_NETWORK_ID).is_none());
+    });
+}
+
+#[test]
+fn test_early_commit_by_gas() {
+    new_tester().execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        let chain_id = EVMChainId::from_low_u64_be(1);
+        let network_id = GenericNetworkId::EVM(chain_id);
+
+        assert_ok!(BridgeOutboundChannel::set_early_commit_threshold(
+            RuntimeOrigin::root(),
+            network_id,
+            Some(Percent::from_percent(50))
+        ));
+
+        assert_ok!(BridgeOutboundChannel::submit(
+            chain_id,
+            &RawOrigin::Signed(who.clone()),
+            &[0, 1, 2],
+            AdditionalEVMOutboundData {
+                target: Default::default(),
+                max_gas: 1_000_000u64.into(),
+            }
+        ));
+        BridgeOutboundChannel::on_finalize(1);
+        assert_eq!(<ChannelNonces<Test>>::get(network_id), 0);
+
+        assert_ok!(BridgeOutboundChannel::submit(
+            chain_id,
+            &RawOrigin::Signed(who),
+            &[0, 1, 2],
+            AdditionalEVMOutboundData {
+                target: Default::default(),
+                max_gas: 2_000_000u64.into(),
+            }
+        ));
+        BridgeOutboundChannel::on_finalize(2);
+        assert_eq!(<ChannelNonces<Test>>::get(network_id), 1);
+    });
+}
+
+#[test]
+fn test_commitment_offchain_indexing() {
+    let mut ext = new_tester();
+    ext.execute_with(|| {
+        let who: AccountId = Keyring::Bob.into();
+        for payload in [[0u8], [1u8]] {
+            assert_ok!(BridgeOutboundChannel::submit(
+                BASE_NETWORK_ID.sub().unwrap(),
+                &RawOrigin::Signed(who.clone()),
+                &payload,
+                ()
+            ));
+            BridgeOutboundChannel::commit(BASE_NETWORK_ID);
+        }
+    });
+    ext.persist_offchain_overlay();
+
+    let (offchain, _) = TestOffchainExt::with_offchain_db(ext.offchain_db());
+    ext.register_extension(OffchainDbExt::new(offchain.clone()));
+    ext.register_extension(OffchainWorkerExt::new(offchain));
+
+    ext.execute_with(|| {
+        for (batch_nonce, payload) in [(1, 0u8), (2, 1u8)] {
+            let commitment = BridgeOutboundChannel::commitment(BASE_NETWORK_ID, batch_nonce)
+                .unwrap()
+                .commitment;
+            assert_eq!(commitment.nonce(), batch_nonce);
+            match commitment {
+                GenericCommitment::Sub(commitment) => {
+                    assert_eq!(commitment.messages[0].payload.to_vec(), vec![payload]);
+                }
+                _ => panic!("unexpected commitment"),
+            }
+        }
+        assert!(BridgeOutboundChannel::commitment(BASE_NETWORK_ID, 3).is_none());
+    });
+}
@@ -62,6 +62,7 @@ pub trait WeightInfo {
 	fn on_initialize_non_interval() -> Weight;
 	fn on_initialize_no_messages() -> Weight;
 	fn submit() -> Weight;
+	fn cancel_message(b: u32, ) -> Weight;
 }
 
 /// Weights for substrate_bridge_channel::outbound using the Substrate node and recommended hardware.
@@ -130,6 +131,15 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Estimated by hand until the `cancel_message` benchmark is run.
+	/// Ids of all queued messages are checked, up to `MaxMessagesPerCommit` in each of `b` batches.
+	fn cancel_message(b: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 5012)
+			.saturating_add(Weight::from_parts(60_000_000, 3006).saturating_mul(b.into()))
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -197,4 +207,13 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Estimated by hand until the `cancel_message` benchmark is run.
+	/// Ids of all queued messages are checked, up to `MaxMessagesPerCommit` in each of `b` batches.
+	fn cancel_message(b: u32, ) -> Weight {
+		Weight::from_parts(15_000_000, 5012)
+			.saturating_add(Weight::from_parts(60_000_000, 3006).saturating_mul(b.into()))
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(b.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
 }
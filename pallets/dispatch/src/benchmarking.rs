@@ -37,12 +37,16 @@ use bridge_types::evm::AdditionalEVMInboundData;
 use bridge_types::traits::MessageDispatch;
 use bridge_types::types::GenericAdditionalInboundData;
 use bridge_types::types::MessageId;
+use bridge_types::types::{DispatchRecord, DispatchStatus};
 use bridge_types::GenericNetworkId;
 use bridge_types::SubNetworkId;
 use frame_benchmarking::benchmarks_instance_pallet;
 use frame_support::dispatch::DispatchError;
+use frame_support::traits::Hooks;
+use frame_support::weights::Weight;
 use frame_system::EventRecord;
 use frame_system::{self};
+use sp_runtime::traits::Hash;
 use sp_std::prelude::*;
 
 fn assert_last_event<T: Config<I>, I: 'static>(
@@ -77,7 +81,7 @@ benchmarks_instance_pallet! {
             AdditionalEVMInboundData {
                 source: Default::default()
             }.into()
-        )
+        );
     }
     verify {
         assert_last_event::<T, I>(crate::Event::<T, I>::MessageDispatched(message_id, Err(DispatchError::BadOrigin)).into());
@@ -94,11 +98,130 @@ benchmarks_instance_pallet! {
             AdditionalEVMInboundData {
                 source: Default::default()
             }.into()
-        )
+        );
     }
     verify {
         assert_last_event::<T, I>(crate::Event::<T, I>::MessageDecodeFailed(message_id).into());
     }
 
+    dispatch_batch {
+        let c in 1 .. T::MaxBatchCalls::get();
+        let message_id = MessageId::basic(GenericNetworkId::EVM([1u8; 32].into()), GenericNetworkId::Sub(SubNetworkId::Mainnet), 1);
+        // system.remark()
+        let call = <T as Config<I>>::Call::decode(&mut &[0u8, 0, 0][..]).unwrap();
+        let payload = MessageEnvelope::V1(MessageCalls::Batch(vec![call; c as usize], BatchMode::BestEffort)).encode_payload();
+    }: {
+        crate::Pallet::<T, I>::dispatch(
+            H256::repeat_byte(1).into(),
+            message_id,
+            Default::default(),
+            &payload,
+            AdditionalEVMInboundData {
+                source: Default::default()
+            }.into()
+        );
+    }
+    verify {
+        assert_last_event::<T, I>(crate::Event::<T, I>::MessageDispatched(message_id, Err(DispatchError::BadOrigin)).into());
+    }
+
+    dispatch_deferred {
+        let message_id = MessageId::basic(GenericNetworkId::EVM([1u8; 32].into()), GenericNetworkId::Sub(SubNetworkId::Mainnet), 1);
+        let origin = <T::OriginOutput as bridge_types::traits::BridgeOriginOutput>::try_successful_origin().unwrap();
+        // system.remark()
+        let call = <T as Config<I>>::Call::decode(&mut &[0u8, 0, 0][..]).unwrap();
+        let message_hash = message_id.using_encoded(|v| <T as Config<I>>::Hashing::hash(v));
+    }: {
+        crate::Pallet::<T, I>::defer_message(message_id, origin, MessageCalls::Single(call));
+        crate::Pallet::<T, I>::record_dispatch(message_hash, DispatchStatus::Deferred, Weight::zero());
+    }
+    verify {
+        assert_eq!(DeferredQueueTail::<T, I>::get(), 1);
+    }
+
+    service_deferred_message {
+        let message_id = MessageId::basic(GenericNetworkId::EVM([1u8; 32].into()), GenericNetworkId::Sub(SubNetworkId::Mainnet), 1);
+        let origin = <T::OriginOutput as bridge_types::traits::BridgeOriginOutput>::try_successful_origin().unwrap();
+        // system.remark()
+        let call = <T as Config<I>>::Call::decode(&mut &[0u8, 0, 0][..]).unwrap();
+        crate::Pallet::<T, I>::defer_message(message_id, origin, MessageCalls::Single(call));
+        let block_number = frame_system::Pallet::<T>::block_number();
+    }: {
+        crate::Pallet::<T, I>::service_deferred_queue(block_number, Weight::MAX);
+    }
+    verify {
+        assert_eq!(DeferredQueueHead::<T, I>::get(), 1);
+        assert_last_event::<T, I>(crate::Event::<T, I>::DeferredMessageDispatched(message_id, Err(DispatchError::BadOrigin)).into());
+    }
+
+    retry_dispatch {
+        let message_id = MessageId::basic(GenericNetworkId::EVM([1u8; 32].into()), GenericNetworkId::Sub(SubNetworkId::Mainnet), 1);
+        let origin = <T::OriginOutput as bridge_types::traits::BridgeOriginOutput>::try_successful_origin().unwrap();
+        // system.remark()
+        let call = <T as Config<I>>::Call::decode(&mut &[0u8, 0, 0][..]).unwrap();
+        let message_hash = message_id.using_encoded(|v| <T as Config<I>>::Hashing::hash(v));
+        crate::Pallet::<T, I>::store_failed_message(message_id, message_hash, origin, MessageCalls::Single(call));
+        let retry_origin = T::RetryOrigin::try_successful_origin().unwrap();
+    }: {
+        crate::Pallet::<T, I>::retry_dispatch(retry_origin, message_hash, Weight::MAX)?;
+    }
+    verify {
+        assert_last_event::<T, I>(crate::Event::<T, I>::MessageRetried(message_id, Err(DispatchError::BadOrigin)).into());
+    }
+
+    prune_failed_messages {
+        let f in 0 .. 100;
+        let block_number = frame_system::Pallet::<T>::block_number();
+        let origin = <T::OriginOutput as bridge_types::traits::BridgeOriginOutput>::try_successful_origin().unwrap();
+        // system.remark()
+        let call = <T as Config<I>>::Call::decode(&mut &[0u8, 0, 0][..]).unwrap();
+        for i in 0..f {
+            let message_id = MessageId::basic(GenericNetworkId::EVM([1u8; 32].into()), GenericNetworkId::Sub(SubNetworkId::Mainnet), i as u64);
+            let message_hash = message_id.using_encoded(|v| <T as Config<I>>::Hashing::hash(v));
+            FailedMessages::<T, I>::insert(message_hash, FailedMessage {
+                message_id,
+                origin: origin.clone(),
+                calls: MessageCalls::Single(call.clone()),
+                retries: 0,
+                expires_at: block_number,
+            });
+            FailedMessagesExpiration::<T, I>::append(block_number, message_hash);
+        }
+    }: {
+        crate::Pallet::<T, I>::on_initialize(block_number);
+    }
+    verify {
+        assert_eq!(FailedMessages::<T, I>::iter().count(), 0);
+    }
+
+    set_call_filter_rule {
+        let origin = T::CallFilterOrigin::try_successful_origin().unwrap();
+        let network_id: GenericNetworkId = H256::repeat_byte(1).into();
+    }: {
+        crate::Pallet::<T, I>::set_call_filter_rule(origin, network_id, 0, Some(0), Some(CallPermission::Blocked))?;
+    }
+    verify {
+        assert_eq!(CallFilterRules::<T, I>::get(network_id, (0, Some(0))), Some(CallPermission::Blocked));
+    }
+
+    on_initialize {
+        let r in 0 .. 100;
+        let block_number = frame_system::Pallet::<T>::block_number();
+        for i in 0..r {
+            let message_hash = H256::from_low_u64_be(i as u64);
+            DispatchResults::<T, I>::insert(message_hash, DispatchRecord {
+                block_number,
+                status: DispatchStatus::Success,
+                weight: Weight::zero(),
+            });
+            DispatchResultsExpiration::<T, I>::append(block_number, message_hash);
+        }
+    }: {
+        crate::Pallet::<T, I>::on_initialize(block_number);
+    }
+    verify {
+        assert_eq!(DispatchResults::<T, I>::iter().count(), 0);
+    }
+
     impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test,);
 }
@@ -0,0 +1,129 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Versioned message payload format.
+//!
+//! Payload which doesn't start with [`ENVELOPE_PREFIX`] is decoded as a single call,
+//! so messages sent before envelopes were introduced are still dispatched.
+
+use codec::{Decode, Encode};
+use frame_support::dispatch::GetDispatchInfo;
+use frame_support::weights::Weight;
+use sp_core::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// Prefix of the payload encoded as [`MessageEnvelope`].
+///
+/// Call is encoded as pallet index and call index, this pair is not used by the runtimes.
+pub const ENVELOPE_PREFIX: [u8; 2] = [0xff, 0xff];
+
+/// Batch dispatch mode.
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    RuntimeDebug,
+    scale_info::TypeInfo,
+    codec::MaxEncodedLen,
+)]
+pub enum BatchMode {
+    /// All calls are reverted if any call fails.
+    AllOrNothing,
+    /// Calls are dispatched independently, failed calls don't affect the others.
+    BestEffort,
+}
+
+/// Calls carried by the message.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
+pub enum MessageCalls<Call> {
+    Single(Call),
+    Batch(Vec<Call>, BatchMode),
+}
+
+/// Versioned message payload.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
+pub enum MessageEnvelope<Call> {
+    #[codec(index = 0)]
+    V1(MessageCalls<Call>),
+}
+
+impl<Call: Encode> MessageEnvelope<Call> {
+    /// Encode envelope with prefix to be sent as the message payload.
+    pub fn encode_payload(&self) -> Vec<u8> {
+        let mut payload = ENVELOPE_PREFIX.to_vec();
+        self.encode_to(&mut payload);
+        payload
+    }
+}
+
+impl<Call> MessageCalls<Call> {
+    /// Number of calls in the message.
+    pub fn len(&self) -> usize {
+        match self {
+            MessageCalls::Single(_) => 1,
+            MessageCalls::Batch(calls, _) => calls.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> sp_std::slice::Iter<'_, Call> {
+        match self {
+            MessageCalls::Single(call) => sp_std::slice::from_ref(call).iter(),
+            MessageCalls::Batch(calls, _) => calls.iter(),
+        }
+    }
+}
+
+impl<Call: Decode> MessageCalls<Call> {
+    /// Decode message payload, either an envelope or a single call.
+    pub fn decode_payload(payload: &[u8]) -> Result<Self, codec::Error> {
+        match payload.strip_prefix(&ENVELOPE_PREFIX[..]) {
+            Some(mut envelope) => match MessageEnvelope::<Call>::decode(&mut envelope)? {
+                MessageEnvelope::V1(calls) => Ok(calls),
+            },
+            None => Ok(MessageCalls::Single(Call::decode(&mut &payload[..])?)),
+        }
+    }
+}
+
+impl<Call: GetDispatchInfo> MessageCalls<Call> {
+    /// Total declared weight of the calls.
+    pub fn weight(&self) -> Weight {
+        self.iter().fold(Weight::zero(), |weight, call| {
+            weight.saturating_add(call.get_dispatch_info().weight)
+        })
+    }
+}
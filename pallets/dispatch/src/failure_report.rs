@@ -0,0 +1,114 @@
+// This file is part of the SORA network and Polkaswap app.
+
+// Copyright (c) 2020, 2021, Polka Biome Ltd. All rights reserved.
+// SPDX-License-Identifier: BSD-4-Clause
+
+// Redistribution and use in source and binary forms, with or without modification,
+// are permitted provided that the following conditions are met:
+
+// Redistributions of source code must retain the above copyright notice, this list
+// of conditions and the following disclaimer.
+// Redistributions in binary form must reproduce the above copyright notice, this
+// list of conditions and the following disclaimer in the documentation and/or other
+// materials provided with the distribution.
+//
+// All advertising materials mentioning features or use of this software must display
+// the following acknowledgement: This product includes software developed by Polka Biome
+// Ltd., SORA, and Polkaswap.
+//
+// Neither the name of the Polka Biome Ltd. nor the names of its contributors may be used
+// to endorse or promote products derived from this software without specific prior written permission.
+
+// THIS SOFTWARE IS PROVIDED BY Polka Biome Ltd. AS IS AND ANY EXPRESS OR IMPLIED WARRANTIES,
+// INCLUDING, BUT NOT LIMITED TO, THE IMPLIED WARRANTIES OF MERCHANTABILITY AND FITNESS FOR
+// A PARTICULAR PURPOSE ARE DISCLAIMED. IN NO EVENT SHALL Polka Biome Ltd. BE LIABLE FOR ANY
+// DIRECT, INDIRECT, INCIDENTAL, SPECIAL, EXEMPLARY, OR CONSEQUENTIAL DAMAGES (INCLUDING,
+// BUT NOT LIMITED TO, PROCUREMENT OF SUBSTITUTE GOODS OR SERVICES; LOSS OF USE, DATA, OR PROFITS;
+// OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY, WHETHER IN CONTRACT,
+// STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
+// USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
+
+//! Failure reports for the Substrate bridge transfers.
+//!
+//! Runtime call is matched by its encoding as [`BridgeCall`], so the bridge apps
+//! should have the same pallet and call indexes in the runtime as in [`BridgeCall`].
+
+use bridge_types::substrate::{
+    BridgeCall, ParachainAppCall, SubstrateAppCall, SubstrateBridgeMessageEncode, XCMAppCall,
+    XCMAppTransferStatus,
+};
+use bridge_types::traits::{DispatchFailureReporter, OutboundChannel};
+use bridge_types::types::MessageStatus;
+use bridge_types::H256;
+use codec::{DecodeAll, Encode};
+use frame_support::weights::Weight;
+use sp_std::marker::PhantomData;
+use sp_std::prelude::*;
+
+/// Sends transfer failure reports to the source network with the outbound channel.
+///
+/// * `SubstrateApp` transfer is reported with `SubstrateAppCall::ReportTransferResult`.
+/// * `XCMApp` transfer is reported with `ParachainAppCall::ReportXCMTransferResult`.
+///
+/// Other calls are not reported.
+pub struct SubstrateTransferFailureReporter<T, Channel>(PhantomData<(T, Channel)>);
+
+impl<T, Channel> SubstrateTransferFailureReporter<T, Channel> {
+    /// Payload of the failure report for the transfer call.
+    pub fn failure_report<Call: Encode>(message_hash: H256, call: &Call) -> Option<Vec<u8>> {
+        let report = match call.using_encoded(|encoded| BridgeCall::decode_all(&mut &*encoded)) {
+            Ok(BridgeCall::SubstrateApp(SubstrateAppCall::Transfer { .. })) => {
+                SubstrateAppCall::ReportTransferResult {
+                    message_id: message_hash,
+                    message_status: MessageStatus::Failed,
+                }
+                .prepare_message()
+            }
+            Ok(BridgeCall::XCMApp(XCMAppCall::Transfer { .. })) => {
+                ParachainAppCall::ReportXCMTransferResult {
+                    message_id: message_hash,
+                    transfer_status: XCMAppTransferStatus::XCMTransferError,
+                }
+                .prepare_message()
+            }
+            _ => return None,
+        };
+        Some(report)
+    }
+}
+
+impl<NetworkId, Call, T, Channel> DispatchFailureReporter<NetworkId, Call>
+    for SubstrateTransferFailureReporter<T, Channel>
+where
+    NetworkId: core::fmt::Debug + Clone,
+    Call: Encode,
+    T: frame_system::Config,
+    Channel: OutboundChannel<NetworkId, T::AccountId, ()>,
+{
+    fn report_failure(network_id: NetworkId, message_hash: H256, call: &Call) -> bool {
+        let Some(report) = Self::failure_report(message_hash, call) else {
+            return false;
+        };
+        match Channel::submit(
+            network_id.clone(),
+            &frame_system::RawOrigin::Root,
+            &report,
+            (),
+        ) {
+            Ok(_) => true,
+            Err(err) => {
+                frame_support::log::warn!(
+                    "Failed to report message {:?} failure to {:?}: {:?}",
+                    message_hash,
+                    network_id,
+                    err
+                );
+                false
+            }
+        }
+    }
+
+    fn report_weight() -> Weight {
+        Channel::submit_weight()
+    }
+}
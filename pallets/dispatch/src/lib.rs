@@ -42,6 +42,12 @@ mod benchmarking;
 pub mod weights;
 pub use weights::WeightInfo;
 
+mod envelope;
+pub use envelope::{BatchMode, MessageCalls, MessageEnvelope, ENVELOPE_PREFIX};
+
+mod failure_report;
+pub use failure_report::SubstrateTransferFailureReporter;
+
 use frame_support::dispatch::{DispatchResult, Dispatchable, Parameter};
 use frame_support::traits::{Contains, EnsureOrigin};
 
@@ -103,6 +109,70 @@ where
     }
 }
 
+/// Message parked until there is enough weight to dispatch it.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
+pub struct DeferredMessage<MessageId, OriginOutput, Call, BlockNumber> {
+    pub message_id: MessageId,
+    pub origin: OriginOutput,
+    pub calls: MessageCalls<Call>,
+    /// Message is dropped if it is not dispatched before this block.
+    pub expires_at: BlockNumber,
+}
+
+/// Permission set by the call filter rule.
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    RuntimeDebug,
+    scale_info::TypeInfo,
+    codec::MaxEncodedLen,
+)]
+pub enum CallPermission {
+    Allowed,
+    Blocked,
+}
+
+/// Reason of the message call rejection.
+#[derive(
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Encode,
+    Decode,
+    RuntimeDebug,
+    scale_info::TypeInfo,
+    codec::MaxEncodedLen,
+)]
+pub enum RejectReason {
+    /// Call is not allowed by the runtime call filter.
+    CallFilter,
+    /// Pallet is blocked for the network by the call filter rule.
+    PalletBlocked,
+    /// Call is blocked for the network by the call filter rule.
+    CallBlocked,
+    /// Calls are heavier than the weight the deferred queue could get in a block.
+    TooHeavy,
+}
+
+/// Message which call has failed and can be retried.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, scale_info::TypeInfo)]
+pub struct FailedMessage<MessageId, OriginOutput, Call, BlockNumber> {
+    pub message_id: MessageId,
+    /// Original bridge origin, includes network id, message hash, timepoint and additional data.
+    pub origin: OriginOutput,
+    /// Failed calls, for the best effort batch only calls which have failed are kept.
+    pub calls: MessageCalls<Call>,
+    /// Number of retries already made.
+    pub retries: u32,
+    /// Message is removed at this block.
+    pub expires_at: BlockNumber,
+}
+
 pub use pallet::*;
 
 #[frame_support::pallet]
@@ -110,13 +180,16 @@ pub mod pallet {
 
     use super::*;
     use crate::weights::WeightInfo;
+    use bridge_types::traits::{BridgeOriginOutput, DispatchFailureReporter};
+    use bridge_types::types::{DispatchRecord, DispatchStatus};
     use bridge_types::GenericTimepoint;
-    use frame_support::dispatch::GetDispatchInfo;
+    use frame_support::dispatch::{extract_actual_weight, GetDispatchInfo};
     use frame_support::pallet_prelude::*;
+    use frame_support::storage::{with_transaction, TransactionOutcome};
     use frame_support::traits::StorageVersion;
     use frame_support::weights::Weight;
     use frame_system::pallet_prelude::*;
-    use sp_runtime::traits::Hash;
+    use sp_runtime::traits::{Hash, One};
 
     /// The current storage version.
     const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
@@ -138,7 +211,7 @@ pub mod pallet {
         type RuntimeEvent: From<Event<Self, I>>
             + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
-        type OriginOutput: traits::BridgeOriginOutput;
+        type OriginOutput: traits::BridgeOriginOutput + Parameter;
 
         /// The overarching origin type.
         type Origin: From<RawOrigin<Self::OriginOutput>>;
@@ -158,16 +231,248 @@ pub mod pallet {
 
         /// The pallet will filter all incoming calls right before they're dispatched. If this filter
         /// rejects the call, special event (`Event::MessageRejected`) is emitted.
+        ///
+        /// Could be overridden for the network by the call filter rules.
         type CallFilter: Contains<<Self as Config<I>>::Call>;
 
+        /// Origin allowed to update the call filter rules.
+        type CallFilterOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Number of blocks the dispatch results are stored for.
+        #[pallet::constant]
+        type DispatchResultsRetention: Get<Self::BlockNumber>;
+
+        /// Calls heavier than this limit or the weight left in the block are not dispatched
+        /// immediately, but put in the deferred queue which is serviced in `on_idle`.
+        #[pallet::constant]
+        type MaxImmediateDispatchWeight: Get<Weight>;
+
+        /// Number of blocks the deferred message waits for dispatch before it expires.
+        #[pallet::constant]
+        type DeferredMessageLifetime: Get<Self::BlockNumber>;
+
+        /// Origin allowed to retry failed messages.
+        type RetryOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+
+        /// Number of times the failed message can be retried.
+        /// Failed messages are not stored if set to zero.
+        #[pallet::constant]
+        type MaxDispatchRetries: Get<u32>;
+
+        /// Number of blocks the failed message is stored for.
+        #[pallet::constant]
+        type FailedMessageLifetime: Get<Self::BlockNumber>;
+
+        /// Maximum number of calls in the message batch.
+        #[pallet::constant]
+        type MaxBatchCalls: Get<u32>;
+
+        /// Reports rejected calls and failed calls which will not be retried back to the
+        /// source network, so the source app could refund the transfer.
+        type FailureReporter: DispatchFailureReporter<
+            <Self::OriginOutput as BridgeOriginOutput>::NetworkId,
+            <Self as Config<I>>::Call,
+        >;
+
         type WeightInfo: WeightInfo;
     }
 
+    /// Dispatch results by message hash.
+    #[pallet::storage]
+    #[pallet::getter(fn dispatch_result)]
+    pub type DispatchResults<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Identity, H256, DispatchRecord<T::BlockNumber>, OptionQuery>;
+
+    /// Message hashes which dispatch results should be removed at given block.
+    #[pallet::storage]
+    pub type DispatchResultsExpiration<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, Vec<H256>, ValueQuery>;
+
+    type DeferredMessageOf<T, I> = DeferredMessage<
+        <T as Config<I>>::MessageId,
+        <T as Config<I>>::OriginOutput,
+        <T as Config<I>>::Call,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    /// Deferred messages by queue index.
+    #[pallet::storage]
+    pub type DeferredQueue<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, u64, DeferredMessageOf<T, I>, OptionQuery>;
+
+    /// Index of the first message in the deferred queue.
+    #[pallet::storage]
+    pub type DeferredQueueHead<T: Config<I>, I: 'static = ()> = StorageValue<_, u64, ValueQuery>;
+
+    /// Index of the next message to be put in the deferred queue.
+    #[pallet::storage]
+    pub type DeferredQueueTail<T: Config<I>, I: 'static = ()> = StorageValue<_, u64, ValueQuery>;
+
+    /// Call filter rules by network id and (pallet index, call index).
+    /// Rule without call index is applied to all pallet calls.
+    #[pallet::storage]
+    #[pallet::getter(fn call_filter_rule)]
+    pub type CallFilterRules<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        NetworkIdOf<T, I>,
+        Blake2_128Concat,
+        (u8, Option<u8>),
+        CallPermission,
+        OptionQuery,
+    >;
+
+    type FailedMessageOf<T, I> = FailedMessage<
+        <T as Config<I>>::MessageId,
+        <T as Config<I>>::OriginOutput,
+        <T as Config<I>>::Call,
+        <T as frame_system::Config>::BlockNumber,
+    >;
+
+    /// Failed messages by message hash.
+    #[pallet::storage]
+    #[pallet::getter(fn failed_message)]
+    pub type FailedMessages<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Identity, H256, FailedMessageOf<T, I>, OptionQuery>;
+
+    /// Failed message hashes which should be removed at given block.
+    #[pallet::storage]
+    pub type FailedMessagesExpiration<T: Config<I>, I: 'static = ()> =
+        StorageMap<_, Twox64Concat, T::BlockNumber, Vec<H256>, ValueQuery>;
+
     #[pallet::hooks]
-    impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {}
+    impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+        fn on_initialize(now: T::BlockNumber) -> Weight {
+            let expired_failed = FailedMessagesExpiration::<T, I>::take(now);
+            let mut failed_weight =
+                <T as Config<I>>::WeightInfo::prune_failed_messages(expired_failed.len() as u32);
+            for message_hash in expired_failed.iter() {
+                if let Some(message) = FailedMessages::<T, I>::take(message_hash) {
+                    failed_weight.saturating_accrue(Self::report_failure(
+                        message.origin.network_id(),
+                        *message_hash,
+                        &message.calls,
+                    ));
+                }
+            }
+
+            let expired = DispatchResultsExpiration::<T, I>::take(now);
+            let retention = T::DispatchResultsRetention::get().max(One::one());
+            for message_hash in expired.iter() {
+                // Record could be overwritten later, e.g. when deferred message is dispatched.
+                DispatchResults::<T, I>::mutate_exists(message_hash, |record| {
+                    let expired = record
+                        .as_ref()
+                        .map_or(false, |r| r.block_number.saturating_add(retention) <= now);
+                    if expired {
+                        *record = None;
+                    }
+                });
+            }
+            <T as Config<I>>::WeightInfo::on_initialize(expired.len() as u32)
+                .saturating_add(failed_weight)
+        }
+
+        fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+            Self::service_deferred_queue(now, remaining_weight)
+        }
+    }
 
     #[pallet::call]
-    impl<T: Config<I>, I: 'static> Pallet<T, I> {}
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
+        /// Dispatch the failed message call again with the original bridge origin.
+        ///
+        /// `weight_limit` should cover the weight of the message call.
+        #[pallet::call_index(0)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::retry_dispatch().saturating_add(*weight_limit))]
+        pub fn retry_dispatch(
+            origin: OriginFor<T>,
+            message_hash: H256,
+            weight_limit: Weight,
+        ) -> DispatchResultWithPostInfo {
+            T::RetryOrigin::ensure_origin(origin)?;
+            let mut message = FailedMessages::<T, I>::get(message_hash)
+                .ok_or(Error::<T, I>::FailedMessageNotFound)?;
+            ensure!(
+                message.expires_at > frame_system::Pallet::<T>::block_number(),
+                Error::<T, I>::FailedMessageExpired
+            );
+            ensure!(
+                weight_limit.all_gte(
+                    message
+                        .calls
+                        .weight()
+                        .saturating_add(Self::failure_report_weight(&message.calls))
+                ),
+                Error::<T, I>::WeightLimitExceeded
+            );
+
+            let execution = Self::execute_calls(message.origin.clone(), message.calls);
+            let mut weight = execution
+                .weight
+                .saturating_add(<T as Config<I>>::WeightInfo::retry_dispatch());
+
+            message.retries = message.retries.saturating_add(1);
+            match execution.failed_calls {
+                Some(calls) if message.retries < T::MaxDispatchRetries::get() => {
+                    message.calls = calls;
+                    FailedMessages::<T, I>::insert(message_hash, message.clone());
+                }
+                Some(calls) => {
+                    FailedMessages::<T, I>::remove(message_hash);
+                    weight.saturating_accrue(Self::report_failure(
+                        message.origin.network_id(),
+                        message_hash,
+                        &calls,
+                    ));
+                }
+                None => FailedMessages::<T, I>::remove(message_hash),
+            }
+            Self::record_dispatch(
+                message_hash,
+                Self::dispatch_status(execution.result),
+                weight,
+            );
+            if let Some(results) = execution.batch_results {
+                Self::deposit_event(Event::BatchDispatched(message.message_id.clone(), results));
+            }
+            Self::deposit_event(Event::MessageRetried(message.message_id, execution.result));
+            Ok(Some(weight).into())
+        }
+
+        /// Set or remove (if `permission` is `None`) the call filter rule for the network.
+        /// If `call_index` is `None` the rule is applied to all calls of the pallet.
+        /// Calls rejected by the static [`Config::CallFilter`] stay rejected whatever the rule.
+        #[pallet::call_index(1)]
+        #[pallet::weight(<T as Config<I>>::WeightInfo::set_call_filter_rule())]
+        pub fn set_call_filter_rule(
+            origin: OriginFor<T>,
+            network_id: NetworkIdOf<T, I>,
+            pallet_index: u8,
+            call_index: Option<u8>,
+            permission: Option<CallPermission>,
+        ) -> DispatchResult {
+            T::CallFilterOrigin::ensure_origin(origin)?;
+            CallFilterRules::<T, I>::set(&network_id, (pallet_index, call_index), permission);
+            Self::deposit_event(Event::CallFilterRuleUpdated {
+                network_id,
+                pallet_index,
+                call_index,
+                permission,
+            });
+            Ok(())
+        }
+    }
+
+    #[pallet::error]
+    pub enum Error<T, I = ()> {
+        /// Failed message is not found.
+        FailedMessageNotFound,
+        /// Failed message is expired.
+        FailedMessageExpired,
+        /// Message call weight exceeds the given weight limit.
+        WeightLimitExceeded,
+    }
 
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -175,9 +480,398 @@ pub mod pallet {
         /// Message has been dispatched with given result.
         MessageDispatched(T::MessageId, DispatchResult),
         /// Message has been rejected
-        MessageRejected(T::MessageId),
+        MessageRejected(T::MessageId, RejectReason),
         /// We have failed to decode a Call from the message.
         MessageDecodeFailed(T::MessageId),
+        /// Message call is too heavy and has been put in the deferred queue.
+        MessageDeferred(T::MessageId),
+        /// Deferred message has been dispatched with given result.
+        DeferredMessageDispatched(T::MessageId, DispatchResult),
+        /// Deferred message has not been dispatched before its expiration.
+        DeferredMessageExpired(T::MessageId),
+        /// Failed message has been dispatched again with given result.
+        MessageRetried(T::MessageId, DispatchResult),
+        /// Message batch has been dispatched with given results of the calls.
+        BatchDispatched(T::MessageId, Vec<DispatchResult>),
+        /// Call filter rule has been updated.
+        CallFilterRuleUpdated {
+            network_id: NetworkIdOf<T, I>,
+            pallet_index: u8,
+            call_index: Option<u8>,
+            permission: Option<CallPermission>,
+        },
+    }
+
+    impl<T: Config<I>, I: 'static> Pallet<T, I> {
+        fn message_hash(message_id: &T::MessageId) -> H256 {
+            message_id.using_encoded(|v| <T as Config<I>>::Hashing::hash(v))
+        }
+
+        pub(crate) fn record_dispatch(message_hash: H256, status: DispatchStatus, weight: Weight) {
+            let block_number = frame_system::Pallet::<T>::block_number();
+            let expiration =
+                block_number.saturating_add(T::DispatchResultsRetention::get().max(One::one()));
+            DispatchResults::<T, I>::insert(
+                message_hash,
+                DispatchRecord {
+                    block_number,
+                    status,
+                    weight,
+                },
+            );
+            DispatchResultsExpiration::<T, I>::append(expiration, message_hash);
+        }
+
+        fn dispatch_status(result: DispatchResult) -> DispatchStatus {
+            match result {
+                Ok(()) => DispatchStatus::Success,
+                Err(err) => DispatchStatus::Failed(err),
+            }
+        }
+
+        /// Weight of the message dispatch without the calls weight.
+        fn dispatch_base_weight(calls: &MessageCalls<<T as Config<I>>::Call>) -> Weight {
+            match calls {
+                MessageCalls::Single(_) => <T as Config<I>>::WeightInfo::dispatch_success(),
+                MessageCalls::Batch(calls, _) => {
+                    <T as Config<I>>::WeightInfo::dispatch_batch(calls.len() as u32)
+                }
+            }
+        }
+
+        fn decode_calls(payload: &[u8]) -> Option<MessageCalls<<T as Config<I>>::Call>> {
+            let calls = MessageCalls::decode_payload(payload).ok()?;
+            if calls.is_empty() || calls.len() > T::MaxBatchCalls::get() as usize {
+                return None;
+            }
+            Some(calls)
+        }
+
+        /// Worst case weight of the failure reports for the message calls.
+        fn failure_report_weight(calls: &MessageCalls<<T as Config<I>>::Call>) -> Weight {
+            T::FailureReporter::report_weight().saturating_mul(calls.len() as u64)
+        }
+
+        /// Report the calls failure to the source network and return the weight of sent reports.
+        fn report_failure(
+            network_id: NetworkIdOf<T, I>,
+            message_hash: H256,
+            calls: &MessageCalls<<T as Config<I>>::Call>,
+        ) -> Weight {
+            let reported = calls
+                .iter()
+                .filter(|call| {
+                    T::FailureReporter::report_failure(network_id.clone(), message_hash, call)
+                })
+                .count();
+            T::FailureReporter::report_weight().saturating_mul(reported as u64)
+        }
+
+        /// Dispatch the message calls with the bridge origin.
+        fn execute_calls(
+            origin: T::OriginOutput,
+            calls: MessageCalls<<T as Config<I>>::Call>,
+        ) -> ExecutionResult<<T as Config<I>>::Call> {
+            let dispatch_one = |call: <T as Config<I>>::Call| {
+                let dispatch_info = call.get_dispatch_info();
+                let result = call.dispatch(RawOrigin::new(origin.clone()).into());
+                (
+                    extract_actual_weight(&result, &dispatch_info),
+                    result.map(drop).map_err(|e| e.error),
+                )
+            };
+            match calls {
+                MessageCalls::Single(call) => {
+                    let (weight, result) = dispatch_one(call.clone());
+                    ExecutionResult {
+                        weight,
+                        result,
+                        batch_results: None,
+                        failed_calls: result.is_err().then_some(MessageCalls::Single(call)),
+                    }
+                }
+                MessageCalls::Batch(calls, BatchMode::AllOrNothing) => {
+                    let failed_calls = calls.clone();
+                    let mut weight = Weight::zero();
+                    let mut results = Vec::with_capacity(calls.len());
+                    let result = with_transaction(|| {
+                        for call in calls {
+                            let (call_weight, call_result) = dispatch_one(call);
+                            weight.saturating_accrue(call_weight);
+                            results.push(call_result);
+                            if let Err(err) = call_result {
+                                return TransactionOutcome::Rollback(Err(err));
+                            }
+                        }
+                        TransactionOutcome::Commit(Ok(()))
+                    });
+                    ExecutionResult {
+                        weight,
+                        result,
+                        batch_results: Some(results),
+                        failed_calls: result
+                            .is_err()
+                            .then_some(MessageCalls::Batch(failed_calls, BatchMode::AllOrNothing)),
+                    }
+                }
+                MessageCalls::Batch(calls, BatchMode::BestEffort) => {
+                    let mut weight = Weight::zero();
+                    let mut results = Vec::with_capacity(calls.len());
+                    let mut failed_calls = Vec::new();
+                    for call in calls {
+                        let (call_weight, call_result) = dispatch_one(call.clone());
+                        weight.saturating_accrue(call_weight);
+                        results.push(call_result);
+                        if call_result.is_err() {
+                            failed_calls.push(call);
+                        }
+                    }
+                    // Message is failed if any call is failed
+                    let result = results
+                        .iter()
+                        .find(|r| r.is_err())
+                        .cloned()
+                        .unwrap_or(Ok(()));
+                    ExecutionResult {
+                        weight,
+                        result,
+                        batch_results: Some(results),
+                        failed_calls: (!failed_calls.is_empty())
+                            .then(|| MessageCalls::Batch(failed_calls, BatchMode::BestEffort)),
+                    }
+                }
+            }
+        }
+
+        /// Dispatch the message calls with the bridge origin and record the result.
+        ///
+        /// Failed calls are stored for retry or reported to the source network if retries
+        /// are disabled. Returns actual weight of the calls with `base_weight` added.
+        fn dispatch_calls(
+            message_id: &T::MessageId,
+            message_hash: H256,
+            origin: T::OriginOutput,
+            calls: MessageCalls<<T as Config<I>>::Call>,
+            base_weight: Weight,
+        ) -> (Weight, DispatchResult) {
+            let execution = Self::execute_calls(origin.clone(), calls);
+            let mut weight = execution.weight.saturating_add(base_weight);
+            if let Some(calls) = execution.failed_calls {
+                if T::MaxDispatchRetries::get() > 0 {
+                    Self::store_failed_message(message_id.clone(), message_hash, origin, calls);
+                } else {
+                    weight.saturating_accrue(Self::report_failure(
+                        origin.network_id(),
+                        message_hash,
+                        &calls,
+                    ));
+                }
+            }
+            if let Some(results) = execution.batch_results {
+                Self::deposit_event(Event::BatchDispatched(message_id.clone(), results));
+            }
+            Self::record_dispatch(
+                message_hash,
+                Self::dispatch_status(execution.result),
+                weight,
+            );
+            (weight, execution.result)
+        }
+
+        pub(crate) fn store_failed_message(
+            message_id: T::MessageId,
+            message_hash: H256,
+            origin: T::OriginOutput,
+            calls: MessageCalls<<T as Config<I>>::Call>,
+        ) {
+            let expires_at = frame_system::Pallet::<T>::block_number()
+                .saturating_add(T::FailedMessageLifetime::get().max(One::one()));
+            FailedMessages::<T, I>::insert(
+                message_hash,
+                FailedMessage {
+                    message_id,
+                    origin,
+                    calls,
+                    retries: 0,
+                    expires_at,
+                },
+            );
+            FailedMessagesExpiration::<T, I>::append(expires_at, message_hash);
+        }
+
+        /// Check the message call against the static call filter and the call filter rules
+        /// of the network.
+        ///
+        /// Rules only narrow the static filter, so a call rejected by it can't be allowed.
+        /// Rule for the call takes precedence over the rule for the whole pallet.
+        fn check_call_filter(
+            network_id: &NetworkIdOf<T, I>,
+            call: &<T as Config<I>>::Call,
+        ) -> Result<(), RejectReason> {
+            if !T::CallFilter::contains(call) {
+                return Err(RejectReason::CallFilter);
+            }
+            // Call is encoded as pallet index, call index and call arguments
+            let indexes = call.using_encoded(|encoded| match encoded {
+                [pallet_index, call_index, ..] => Some((*pallet_index, *call_index)),
+                _ => None,
+            });
+            if let Some((pallet_index, call_index)) = indexes {
+                match CallFilterRules::<T, I>::get(network_id, (pallet_index, Some(call_index))) {
+                    Some(CallPermission::Allowed) => return Ok(()),
+                    Some(CallPermission::Blocked) => return Err(RejectReason::CallBlocked),
+                    None => {}
+                }
+                if let Some(CallPermission::Blocked) =
+                    CallFilterRules::<T, I>::get(network_id, (pallet_index, None))
+                {
+                    return Err(RejectReason::PalletBlocked);
+                }
+            }
+            Ok(())
+        }
+
+        fn is_deferred(call_weight: Weight) -> bool {
+            call_weight.any_gt(T::MaxImmediateDispatchWeight::get())
+        }
+
+        /// Weight left in the current block.
+        fn remaining_block_weight() -> Weight {
+            T::BlockWeights::get()
+                .max_block
+                .saturating_sub(frame_system::Pallet::<T>::block_weight().total())
+        }
+
+        /// Check if the calls can't be dispatched from the deferred queue even by `on_idle`
+        /// of an empty block, so they would block the queue until expired.
+        fn exceeds_deferred_limit(calls: &MessageCalls<<T as Config<I>>::Call>) -> bool {
+            let block_weights = T::BlockWeights::get();
+            let limit = block_weights
+                .max_block
+                .saturating_sub(block_weights.base_block);
+            // Queue head and tail reads and head update
+            let service_weight = <T as Config<I>>::WeightInfo::service_deferred_message()
+                .saturating_add(T::DbWeight::get().reads_writes(2, 1));
+            calls
+                .weight()
+                .saturating_add(service_weight)
+                .saturating_add(Self::failure_report_weight(calls))
+                .any_gt(limit)
+        }
+
+        /// Put the message calls in the deferred queue.
+        pub(crate) fn defer_message(
+            message_id: T::MessageId,
+            origin: T::OriginOutput,
+            calls: MessageCalls<<T as Config<I>>::Call>,
+        ) {
+            let expires_at = frame_system::Pallet::<T>::block_number()
+                .saturating_add(T::DeferredMessageLifetime::get());
+            let index = DeferredQueueTail::<T, I>::get();
+            DeferredQueue::<T, I>::insert(
+                index,
+                DeferredMessage {
+                    message_id,
+                    origin,
+                    calls,
+                    expires_at,
+                },
+            );
+            DeferredQueueTail::<T, I>::put(index.saturating_add(1));
+        }
+
+        /// Dispatch deferred messages in order while they fit in the given weight.
+        ///
+        /// Expired messages are dropped.
+        pub(crate) fn service_deferred_queue(
+            now: T::BlockNumber,
+            remaining_weight: Weight,
+        ) -> Weight {
+            let db_weight = T::DbWeight::get();
+            let mut used_weight = db_weight.reads(2);
+            if used_weight.any_gt(remaining_weight) {
+                return Weight::zero();
+            }
+            let mut head = DeferredQueueHead::<T, I>::get();
+            let tail = DeferredQueueTail::<T, I>::get();
+            if head == tail {
+                return used_weight;
+            }
+            // Head update
+            used_weight.saturating_accrue(db_weight.writes(1));
+            let service_weight = <T as Config<I>>::WeightInfo::service_deferred_message();
+            while head < tail {
+                if used_weight
+                    .saturating_add(service_weight)
+                    .any_gt(remaining_weight)
+                {
+                    break;
+                }
+                let Some(message) = DeferredQueue::<T, I>::get(head) else {
+                    used_weight.saturating_accrue(db_weight.reads(1));
+                    head = head.saturating_add(1);
+                    continue;
+                };
+                let message_hash = Self::message_hash(&message.message_id);
+                let report_weight = Self::failure_report_weight(&message.calls);
+                if message.expires_at < now {
+                    if used_weight
+                        .saturating_add(service_weight)
+                        .saturating_add(report_weight)
+                        .any_gt(remaining_weight)
+                    {
+                        used_weight.saturating_accrue(db_weight.reads(1));
+                        break;
+                    }
+                    DeferredQueue::<T, I>::remove(head);
+                    head = head.saturating_add(1);
+                    let weight = Self::report_failure(
+                        message.origin.network_id(),
+                        message_hash,
+                        &message.calls,
+                    )
+                    .saturating_add(service_weight);
+                    used_weight.saturating_accrue(weight);
+                    Self::record_dispatch(message_hash, DispatchStatus::Expired, weight);
+                    Self::deposit_event(Event::DeferredMessageExpired(message.message_id));
+                    continue;
+                }
+                if used_weight
+                    .saturating_add(service_weight)
+                    .saturating_add(message.calls.weight())
+                    .saturating_add(report_weight)
+                    .any_gt(remaining_weight)
+                {
+                    // Message has been read, but not dispatched
+                    used_weight.saturating_accrue(db_weight.reads(1));
+                    break;
+                }
+                DeferredQueue::<T, I>::remove(head);
+                head = head.saturating_add(1);
+                let (weight, result) = Self::dispatch_calls(
+                    &message.message_id,
+                    message_hash,
+                    message.origin,
+                    message.calls,
+                    service_weight,
+                );
+                used_weight.saturating_accrue(weight);
+                Self::deposit_event(Event::DeferredMessageDispatched(message.message_id, result));
+            }
+            DeferredQueueHead::<T, I>::put(head);
+            used_weight
+        }
+    }
+
+    /// Result of the message calls execution.
+    pub(crate) struct ExecutionResult<Call> {
+        /// Actual weight of the calls.
+        pub weight: Weight,
+        pub result: DispatchResult,
+        /// Results of the batch calls.
+        pub batch_results: Option<Vec<DispatchResult>>,
+        /// Failed calls to be kept for retry or reported to the source network.
+        pub failed_calls: Option<MessageCalls<Call>>,
     }
 
     #[pallet::origin]
@@ -194,46 +888,73 @@ pub mod pallet {
             timepoint: GenericTimepoint,
             payload: &[u8],
             additional: AdditionalOf<T, I>,
-        ) {
-            let call = match <T as Config<I>>::Call::decode(&mut &payload[..]) {
-                Ok(call) => call,
-                Err(_) => {
-                    Self::deposit_event(Event::MessageDecodeFailed(message_id));
-                    return;
-                }
+        ) -> Weight {
+            let message_hash = Self::message_hash(&message_id);
+            let Some(calls) = Self::decode_calls(payload) else {
+                let weight = <T as Config<I>>::WeightInfo::dispatch_decode_failed();
+                Self::record_dispatch(message_hash, DispatchStatus::DecodeFailed, weight);
+                Self::deposit_event(Event::MessageDecodeFailed(message_id));
+                return weight;
             };
 
-            if !T::CallFilter::contains(&call) {
-                Self::deposit_event(Event::MessageRejected(message_id));
-                return;
+            // Message is rejected if any of the calls is rejected
+            let base_weight = Self::dispatch_base_weight(&calls);
+            if let Err(reason) = calls
+                .iter()
+                .try_for_each(|call| Self::check_call_filter(&network_id, call))
+            {
+                let weight = Self::report_failure(network_id, message_hash, &calls)
+                    .saturating_add(base_weight);
+                Self::record_dispatch(message_hash, DispatchStatus::Rejected, weight);
+                Self::deposit_event(Event::MessageRejected(message_id, reason));
+                return weight;
             }
 
-            let origin = RawOrigin::new(<T::OriginOutput as traits::BridgeOriginOutput>::new(
+            let origin = <T::OriginOutput as traits::BridgeOriginOutput>::new(
                 network_id,
-                message_id.using_encoded(|v| <T as Config<I>>::Hashing::hash(v)),
+                message_hash,
                 timepoint,
                 additional,
-            ))
-            .into();
-            let result = call.dispatch(origin);
-
-            Self::deposit_event(Event::MessageDispatched(
-                message_id,
-                result.map(drop).map_err(|e| e.error),
-            ));
+            );
+            let calls_weight = calls.weight();
+            if Self::is_deferred(calls_weight)
+                || calls_weight.any_gt(Self::remaining_block_weight())
+            {
+                if Self::exceeds_deferred_limit(&calls) {
+                    let network_id = origin.network_id();
+                    let weight = Self::report_failure(network_id, message_hash, &calls)
+                        .saturating_add(<T as Config<I>>::WeightInfo::dispatch_deferred());
+                    Self::record_dispatch(message_hash, DispatchStatus::Rejected, weight);
+                    Self::deposit_event(Event::MessageRejected(message_id, RejectReason::TooHeavy));
+                    return weight;
+                }
+                let weight = <T as Config<I>>::WeightInfo::dispatch_deferred();
+                Self::defer_message(message_id.clone(), origin, calls);
+                Self::record_dispatch(message_hash, DispatchStatus::Deferred, weight);
+                Self::deposit_event(Event::MessageDeferred(message_id));
+                return weight;
+            }
+            let (weight, result) =
+                Self::dispatch_calls(&message_id, message_hash, origin, calls, base_weight);
+            Self::deposit_event(Event::MessageDispatched(message_id, result));
+            weight
         }
 
         fn dispatch_weight(payload: &[u8]) -> Weight {
-            let call = match <T as Config<I>>::Call::decode(&mut &payload[..]) {
-                Ok(call) => call,
-                Err(_) => {
-                    return <T as Config<I>>::WeightInfo::dispatch_decode_failed();
-                }
+            let Some(calls) = Self::decode_calls(payload) else {
+                return <T as Config<I>>::WeightInfo::dispatch_decode_failed();
             };
-            let dispatch_info = call.get_dispatch_info();
-            dispatch_info
-                .weight
-                .saturating_add(<T as Config<I>>::WeightInfo::dispatch_success())
+            let calls_weight = calls.weight();
+            // Rejected or failed calls are reported back to the source network
+            let base_weight = Self::dispatch_base_weight(&calls)
+                .saturating_add(Self::failure_report_weight(&calls));
+            if Self::is_deferred(calls_weight) {
+                // Too heavy calls are rejected and reported when they are deferred
+                return <T as Config<I>>::WeightInfo::dispatch_deferred()
+                    .saturating_add(Self::failure_report_weight(&calls))
+                    .max(base_weight);
+            }
+            calls_weight.saturating_add(base_weight)
         }
 
         #[cfg(feature = "runtime-benchmarks")]
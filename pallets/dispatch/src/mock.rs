@@ -1,9 +1,13 @@
 use super::*;
+use bridge_types::traits::DispatchFailureReporter;
 use bridge_types::types;
 use bridge_types::types::GenericAdditionalInboundData;
 use bridge_types::GenericNetworkId;
 use frame_support::parameter_types;
-use frame_support::traits::{ConstU32, Everything};
+use frame_support::traits::{ConstU32, ConstU64, Everything, Get};
+use frame_support::weights::constants::WEIGHT_REF_TIME_PER_SECOND;
+use frame_support::weights::Weight;
+use frame_system::limits::BlockWeights;
 use sp_core::H256;
 use sp_runtime::testing::Header;
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup, Keccak256};
@@ -20,14 +24,49 @@ frame_support::construct_runtime!(
         UncheckedExtrinsic = UncheckedExtrinsic,
     {
         System: frame_system::{Pallet, Call, Storage, Event<T>},
-        Dispatch: dispatch::{Pallet, Storage, Origin<T>, Event<T>},
+        Dispatch: dispatch::{Pallet, Call, Storage, Origin<T>, Event<T>},
+        MockApp: mock_app::{Pallet, Call, Storage},
     }
 );
 
 type AccountId = u64;
 
+/// Application which accepts calls from the bridge origin.
+#[frame_support::pallet]
+pub mod mock_app {
+    use frame_support::pallet_prelude::*;
+    use frame_system::pallet_prelude::*;
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        type BridgeOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+    }
+
+    #[pallet::storage]
+    #[pallet::getter(fn value)]
+    pub type Value<T> = StorageValue<_, u32, OptionQuery>;
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        #[pallet::call_index(0)]
+        #[pallet::weight(frame_support::weights::Weight::zero())]
+        pub fn set_value(origin: OriginFor<T>, value: u32) -> DispatchResult {
+            T::BridgeOrigin::ensure_origin(origin)?;
+            Value::<T>::put(value);
+            Ok(())
+        }
+    }
+}
+
 parameter_types! {
     pub const BlockHashCount: u64 = 250;
+    pub static MaxImmediateDispatchWeight: Weight = Weight::MAX;
+    pub static MaxBlockWeight: Weight = Weight::from_parts(2 * WEIGHT_REF_TIME_PER_SECOND, u64::MAX);
+    pub static MaxDispatchRetries: u32 = 2;
+    pub static FailureReports: Vec<(GenericNetworkId, H256, RuntimeCall)> = vec![];
 }
 
 impl frame_system::Config for Test {
@@ -49,7 +88,7 @@ impl frame_system::Config for Test {
     type OnKilledAccount = ();
     type BaseCallFilter = Everything;
     type SystemWeightInfo = ();
-    type BlockWeights = ();
+    type BlockWeights = TestBlockWeights;
     type BlockLength = ();
     type DbWeight = ();
     type SS58Prefix = ();
@@ -57,16 +96,42 @@ impl frame_system::Config for Test {
     type MaxConsumers = ConstU32<65536>;
 }
 
+pub struct TestBlockWeights;
+impl Get<BlockWeights> for TestBlockWeights {
+    fn get() -> BlockWeights {
+        BlockWeights::simple_max(MaxBlockWeight::get())
+    }
+}
+
 pub struct CallFilter;
 impl Contains<RuntimeCall> for CallFilter {
     fn contains(call: &RuntimeCall) -> bool {
         matches!(
             call,
             RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { .. })
+                | RuntimeCall::MockApp(mock_app::Call::<Test>::set_value { .. })
         )
     }
 }
 
+pub struct TestFailureReporter;
+impl DispatchFailureReporter<GenericNetworkId, RuntimeCall> for TestFailureReporter {
+    fn report_failure(
+        network_id: GenericNetworkId,
+        message_hash: H256,
+        call: &RuntimeCall,
+    ) -> bool {
+        let mut reports = FailureReports::get();
+        reports.push((network_id, message_hash, call.clone()));
+        FailureReports::set(reports);
+        true
+    }
+
+    fn report_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
 impl dispatch::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type OriginOutput =
@@ -76,9 +141,24 @@ impl dispatch::Config for Test {
     type Hashing = Keccak256;
     type Call = RuntimeCall;
     type CallFilter = CallFilter;
+    type CallFilterOrigin = frame_system::EnsureRoot<AccountId>;
+    type DispatchResultsRetention = ConstU64<100>;
+    type MaxImmediateDispatchWeight = MaxImmediateDispatchWeight;
+    type DeferredMessageLifetime = ConstU64<10>;
+    type RetryOrigin = frame_system::EnsureSigned<AccountId>;
+    type MaxDispatchRetries = MaxDispatchRetries;
+    type FailedMessageLifetime = ConstU64<10>;
+    type MaxBatchCalls = ConstU32<20>;
+    type FailureReporter = TestFailureReporter;
     type WeightInfo = ();
 }
 
+impl mock_app::Config for Test {
+    type BridgeOrigin = dispatch::EnsureAccount<
+        types::CallOriginOutput<GenericNetworkId, H256, GenericAdditionalInboundData>,
+    >;
+}
+
 pub fn new_test_ext() -> sp_io::TestExternalities {
     let t = frame_system::GenesisConfig::default()
         .build_storage::<Test>()
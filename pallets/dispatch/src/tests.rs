@@ -1,10 +1,19 @@
 use super::*;
 use crate::mock::*;
 use bridge_types::evm::AdditionalEVMInboundData;
+use bridge_types::substrate::{
+    BridgeCall, Junctions, ParachainAppCall, SubstrateAppCall, SubstrateBridgeMessageEncode,
+    VersionedMultiLocation, XCMAppCall, XCMAppTransferStatus,
+};
 use bridge_types::traits::MessageDispatch as _;
 use bridge_types::H160;
-use bridge_types::{types, SubNetworkId};
+use bridge_types::{
+    types, GenericAccount, GenericAssetId, GenericBalance, GenericNetworkId, SubNetworkId,
+};
 use frame_support::dispatch::DispatchError;
+use frame_support::traits::Hooks;
+use frame_support::weights::Weight;
+use frame_support::{assert_noop, assert_ok};
 use frame_system::{EventRecord, Phase};
 
 #[test]
@@ -106,9 +115,775 @@ fn test_message_rejected() {
             System::events(),
             vec![EventRecord {
                 phase: Phase::Initialization,
-                event: RuntimeEvent::Dispatch(crate::Event::<Test>::MessageRejected(id)),
+                event: RuntimeEvent::Dispatch(crate::Event::<Test>::MessageRejected(
+                    id,
+                    RejectReason::CallFilter
+                )),
                 topics: vec![],
             }],
         );
+        assert_eq!(
+            FailureReports::get(),
+            vec![(
+                H256::from_low_u64_be(2).into(),
+                id.hash(),
+                RuntimeCall::System(frame_system::pallet::Call::<Test>::set_code { code: vec![] })
+            )]
+        );
+    })
+}
+
+#[test]
+fn test_dispatch_result_stored() {
+    new_test_ext().execute_with(|| {
+        let id = types::MessageId::batched(
+            SubNetworkId::Mainnet.into(),
+            SubNetworkId::Rococo.into(),
+            1,
+            37,
+        );
+        let source = H160::repeat_byte(7);
+
+        let message =
+            RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { remark: vec![] })
+                .encode();
+
+        System::set_block_number(1);
+        let weight = Dispatch::dispatch(
+            H256::from_low_u64_be(2).into(),
+            id,
+            Default::default(),
+            &message,
+            AdditionalEVMInboundData { source }.into(),
+        );
+
+        assert_eq!(
+            Dispatch::dispatch_result(id.hash()),
+            Some(types::DispatchRecord {
+                block_number: 1,
+                status: types::DispatchStatus::Failed(DispatchError::BadOrigin),
+                weight,
+            })
+        );
+        assert_eq!(DispatchResultsExpiration::<Test>::get(101), vec![id.hash()]);
+    })
+}
+
+#[test]
+fn test_dispatch_results_pruned() {
+    new_test_ext().execute_with(|| {
+        let id = types::MessageId::batched(
+            SubNetworkId::Mainnet.into(),
+            SubNetworkId::Rococo.into(),
+            1,
+            37,
+        );
+        let source = H160::repeat_byte(7);
+
+        System::set_block_number(1);
+        Dispatch::dispatch(
+            H256::from_low_u64_be(2).into(),
+            id,
+            Default::default(),
+            &[1, 2, 3],
+            AdditionalEVMInboundData { source }.into(),
+        );
+        assert_eq!(
+            Dispatch::dispatch_result(id.hash()).map(|record| record.status),
+            Some(types::DispatchStatus::DecodeFailed)
+        );
+
+        Dispatch::on_initialize(100);
+        assert!(Dispatch::dispatch_result(id.hash()).is_some());
+
+        Dispatch::on_initialize(101);
+        assert!(Dispatch::dispatch_result(id.hash()).is_none());
+        assert!(DispatchResultsExpiration::<Test>::get(101).is_empty());
+    })
+}
+
+#[test]
+fn test_message_deferred_and_dispatched_on_idle() {
+    new_test_ext().execute_with(|| {
+        MaxImmediateDispatchWeight::set(Weight::zero());
+        let id = types::MessageId::batched(
+            SubNetworkId::Mainnet.into(),
+            SubNetworkId::Rococo.into(),
+            1,
+            37,
+        );
+        let source = H160::repeat_byte(7);
+
+        let message =
+            RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { remark: vec![] })
+                .encode();
+
+        System::set_block_number(1);
+        let weight = Dispatch::dispatch(
+            H256::from_low_u64_be(2).into(),
+            id,
+            Default::default(),
+            &message,
+            AdditionalEVMInboundData { source }.into(),
+        );
+        assert_eq!(weight, <() as WeightInfo>::dispatch_deferred());
+        assert_eq!(
+            Dispatch::dispatch_weight(&message),
+            <() as WeightInfo>::dispatch_deferred()
+        );
+        assert_eq!(
+            System::events(),
+            vec![EventRecord {
+                phase: Phase::Initialization,
+                event: RuntimeEvent::Dispatch(crate::Event::<Test>::MessageDeferred(id)),
+                topics: vec![],
+            }],
+        );
+        assert_eq!(
+            Dispatch::dispatch_result(id.hash()).map(|record| record.status),
+            Some(types::DispatchStatus::Deferred)
+        );
+
+        // Not enough weight to dispatch the message
+        Dispatch::on_idle(1, <() as WeightInfo>::service_deferred_message());
+        assert_eq!(DeferredQueueHead::<Test>::get(), 0);
+
+        Dispatch::on_idle(1, Weight::MAX);
+        assert_eq!(DeferredQueueHead::<Test>::get(), 1);
+        assert!(DeferredQueue::<Test>::get(0).is_none());
+        assert_eq!(
+            System::events().last().map(|record| record.event.clone()),
+            Some(RuntimeEvent::Dispatch(
+                crate::Event::<Test>::DeferredMessageDispatched(id, Err(DispatchError::BadOrigin))
+            )),
+        );
+        assert_eq!(
+            Dispatch::dispatch_result(id.hash()).map(|record| record.status),
+            Some(types::DispatchStatus::Failed(DispatchError::BadOrigin))
+        );
+    })
+}
+
+#[test]
+fn test_deferred_message_expired() {
+    new_test_ext().execute_with(|| {
+        MaxImmediateDispatchWeight::set(Weight::zero());
+        let id = types::MessageId::batched(
+            SubNetworkId::Mainnet.into(),
+            SubNetworkId::Rococo.into(),
+            1,
+            37,
+        );
+        let source = H160::repeat_byte(7);
+
+        let message =
+            RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { remark: vec![] })
+                .encode();
+
+        System::set_block_number(1);
+        Dispatch::dispatch(
+            H256::from_low_u64_be(2).into(),
+            id,
+            Default::default(),
+            &message,
+            AdditionalEVMInboundData { source }.into(),
+        );
+
+        Dispatch::on_idle(12, Weight::MAX);
+        assert_eq!(DeferredQueueHead::<Test>::get(), 1);
+        assert_eq!(
+            System::events().last().map(|record| record.event.clone()),
+            Some(RuntimeEvent::Dispatch(
+                crate::Event::<Test>::DeferredMessageExpired(id)
+            )),
+        );
+        assert_eq!(
+            Dispatch::dispatch_result(id.hash()).map(|record| record.status),
+            Some(types::DispatchStatus::Expired)
+        );
+    })
+}
+
+#[test]
+fn test_message_deferred_if_block_is_full() {
+    new_test_ext().execute_with(|| {
+        let id = types::MessageId::batched(
+            SubNetworkId::Mainnet.into(),
+            SubNetworkId::Rococo.into(),
+            1,
+            37,
+        );
+        let source = H160::repeat_byte(7);
+
+        let message =
+            RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { remark: vec![] })
+                .encode();
+
+        System::set_block_number(1);
+        System::register_extra_weight_unchecked(
+            MaxBlockWeight::get(),
+            frame_support::dispatch::DispatchClass::Mandatory,
+        );
+        Dispatch::dispatch(
+            H256::from_low_u64_be(2).into(),
+            id,
+            Default::default(),
+            &message,
+            AdditionalEVMInboundData { source }.into(),
+        );
+        assert_eq!(
+            System::events().last().map(|record| record.event.clone()),
+            Some(RuntimeEvent::Dispatch(
+                crate::Event::<Test>::MessageDeferred(id)
+            )),
+        );
+        assert_eq!(DeferredQueueTail::<Test>::get(), 1);
+    })
+}
+
+#[test]
+fn test_too_heavy_message_rejected() {
+    new_test_ext().execute_with(|| {
+        MaxImmediateDispatchWeight::set(Weight::zero());
+        // Deferred queue can't get more than the message servicing weight in a block
+        MaxBlockWeight::set(<() as WeightInfo>::service_deferred_message());
+        let id = types::MessageId::batched(
+            SubNetworkId::Mainnet.into(),
+            SubNetworkId::Rococo.into(),
+            1,
+            37,
+        );
+        let source = H160::repeat_byte(7);
+
+        let remark =
+            RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { remark: vec![] });
+
+        System::set_block_number(1);
+        Dispatch::dispatch(
+            H256::from_low_u64_be(2).into(),
+            id,
+            Default::default(),
+            &remark.encode(),
+            AdditionalEVMInboundData { source }.into(),
+        );
+        assert_eq!(
+            System::events().last().map(|record| record.event.clone()),
+            Some(RuntimeEvent::Dispatch(
+                crate::Event::<Test>::MessageRejected(id, RejectReason::TooHeavy)
+            )),
+        );
+        assert_eq!(
+            Dispatch::dispatch_result(id.hash()).map(|record| record.status),
+            Some(types::DispatchStatus::Rejected)
+        );
+        assert_eq!(DeferredQueueTail::<Test>::get(), 0);
+        assert_eq!(
+            FailureReports::get(),
+            vec![(H256::from_low_u64_be(2).into(), id.hash(), remark)]
+        );
+    })
+}
+
+#[test]
+fn test_failed_message_retry() {
+    new_test_ext().execute_with(|| {
+        let id = types::MessageId::batched(
+            SubNetworkId::Mainnet.into(),
+            SubNetworkId::Rococo.into(),
+            1,
+            37,
+        );
+        let source = H160::repeat_byte(7);
+
+        let message =
+            RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { remark: vec![] })
+                .encode();
+
+        System::set_block_number(1);
+        Dispatch::dispatch(
+            H256::from_low_u64_be(2).into(),
+            id,
+            Default::default(),
+            &message,
+            AdditionalEVMInboundData { source }.into(),
+        );
+        let failed = Dispatch::failed_message(id.hash()).unwrap();
+        assert_eq!(failed.message_id, id);
+        assert_eq!(failed.origin.message_id, id.hash());
+        assert_eq!(failed.retries, 0);
+        assert_eq!(failed.expires_at, 11);
+
+        assert_noop!(
+            Dispatch::retry_dispatch(RuntimeOrigin::none(), id.hash(), Weight::MAX),
+            DispatchError::BadOrigin
+        );
+        assert_noop!(
+            Dispatch::retry_dispatch(RuntimeOrigin::signed(1), id.hash(), Weight::zero()),
+            Error::<Test>::WeightLimitExceeded
+        );
+
+        assert_ok!(Dispatch::retry_dispatch(
+            RuntimeOrigin::signed(1),
+            id.hash(),
+            Weight::MAX
+        ));
+        assert_eq!(
+            System::events().last().map(|record| record.event.clone()),
+            Some(RuntimeEvent::Dispatch(
+                crate::Event::<Test>::MessageRetried(id, Err(DispatchError::BadOrigin))
+            )),
+        );
+        assert_eq!(Dispatch::failed_message(id.hash()).unwrap().retries, 1);
+        assert!(FailureReports::get().is_empty());
+
+        // Message is removed and reported after the last retry
+        assert_ok!(Dispatch::retry_dispatch(
+            RuntimeOrigin::signed(1),
+            id.hash(),
+            Weight::MAX
+        ));
+        assert!(Dispatch::failed_message(id.hash()).is_none());
+        assert_eq!(FailureReports::get().len(), 1);
+        assert_noop!(
+            Dispatch::retry_dispatch(RuntimeOrigin::signed(1), id.hash(), Weight::MAX),
+            Error::<Test>::FailedMessageNotFound
+        );
+    })
+}
+
+#[test]
+fn test_failed_message_expired() {
+    new_test_ext().execute_with(|| {
+        let id = types::MessageId::batched(
+            SubNetworkId::Mainnet.into(),
+            SubNetworkId::Rococo.into(),
+            1,
+            37,
+        );
+        let source = H160::repeat_byte(7);
+
+        let message =
+            RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { remark: vec![] })
+                .encode();
+
+        System::set_block_number(1);
+        Dispatch::dispatch(
+            H256::from_low_u64_be(2).into(),
+            id,
+            Default::default(),
+            &message,
+            AdditionalEVMInboundData { source }.into(),
+        );
+        assert!(Dispatch::failed_message(id.hash()).is_some());
+
+        System::set_block_number(11);
+        assert_noop!(
+            Dispatch::retry_dispatch(RuntimeOrigin::signed(1), id.hash(), Weight::MAX),
+            Error::<Test>::FailedMessageExpired
+        );
+
+        Dispatch::on_initialize(11);
+        assert!(Dispatch::failed_message(id.hash()).is_none());
+        assert_eq!(
+            FailureReports::get(),
+            vec![(
+                H256::from_low_u64_be(2).into(),
+                id.hash(),
+                RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { remark: vec![] })
+            )]
+        );
+    })
+}
+
+#[test]
+fn test_failed_message_reported_without_retries() {
+    new_test_ext().execute_with(|| {
+        MaxDispatchRetries::set(0);
+        let id = types::MessageId::batched(
+            SubNetworkId::Mainnet.into(),
+            SubNetworkId::Rococo.into(),
+            1,
+            37,
+        );
+        let source = H160::repeat_byte(7);
+
+        let message =
+            RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { remark: vec![] })
+                .encode();
+
+        System::set_block_number(1);
+        Dispatch::dispatch(
+            H256::from_low_u64_be(2).into(),
+            id,
+            Default::default(),
+            &message,
+            AdditionalEVMInboundData { source }.into(),
+        );
+        assert!(Dispatch::failed_message(id.hash()).is_none());
+        assert_eq!(
+            FailureReports::get(),
+            vec![(
+                H256::from_low_u64_be(2).into(),
+                id.hash(),
+                RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { remark: vec![] })
+            )]
+        );
+        MaxDispatchRetries::set(2);
+    })
+}
+
+#[test]
+fn test_substrate_transfer_failure_report() {
+    let message_hash = H256::repeat_byte(1);
+    let transfer = BridgeCall::SubstrateApp(SubstrateAppCall::Transfer {
+        asset_id: GenericAssetId::Sora(H256::repeat_byte(2)),
+        sender: GenericAccount::Root,
+        recipient: GenericAccount::Root,
+        amount: GenericBalance::Substrate(100),
+    });
+    assert_eq!(
+        SubstrateTransferFailureReporter::<Test, ()>::failure_report(message_hash, &transfer),
+        Some(
+            SubstrateAppCall::ReportTransferResult {
+                message_id: message_hash,
+                message_status: types::MessageStatus::Failed,
+            }
+            .prepare_message()
+        )
+    );
+
+    let xcm_transfer = BridgeCall::XCMApp(XCMAppCall::Transfer {
+        asset_id: H256::repeat_byte(2),
+        sender: sp_runtime::AccountId32::new([1; 32]),
+        recipient: VersionedMultiLocation::V3(Junctions::Here.into()),
+        amount: 100,
+    });
+    assert_eq!(
+        SubstrateTransferFailureReporter::<Test, ()>::failure_report(message_hash, &xcm_transfer),
+        Some(
+            ParachainAppCall::ReportXCMTransferResult {
+                message_id: message_hash,
+                transfer_status: XCMAppTransferStatus::XCMTransferError,
+            }
+            .prepare_message()
+        )
+    );
+
+    // Reports are not reported back
+    let report = BridgeCall::SubstrateApp(SubstrateAppCall::ReportTransferResult {
+        message_id: message_hash,
+        message_status: types::MessageStatus::Failed,
+    });
+    assert_eq!(
+        SubstrateTransferFailureReporter::<Test, ()>::failure_report(message_hash, &report),
+        None
+    );
+}
+
+#[test]
+fn test_call_filter_rules() {
+    new_test_ext().execute_with(|| {
+        let network_id: GenericNetworkId = H256::from_low_u64_be(2).into();
+        let source = H160::repeat_byte(7);
+        let remark =
+            RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { remark: vec![] })
+                .encode();
+        let set_code =
+            RuntimeCall::System(frame_system::pallet::Call::<Test>::set_code { code: vec![] })
+                .encode();
+        let dispatch = |nonce: u64, message: &[u8]| {
+            let id = types::MessageId::batched(
+                SubNetworkId::Mainnet.into(),
+                SubNetworkId::Rococo.into(),
+                1,
+                nonce,
+            );
+            System::reset_events();
+            Dispatch::dispatch(
+                network_id,
+                id,
+                Default::default(),
+                message,
+                AdditionalEVMInboundData { source }.into(),
+            );
+            (id, System::events().pop().map(|record| record.event))
+        };
+
+        System::set_block_number(1);
+        assert_noop!(
+            Dispatch::set_call_filter_rule(
+                RuntimeOrigin::signed(1),
+                network_id,
+                remark[0],
+                Some(remark[1]),
+                Some(CallPermission::Blocked)
+            ),
+            DispatchError::BadOrigin
+        );
+
+        // Block the call
+        assert_ok!(Dispatch::set_call_filter_rule(
+            RuntimeOrigin::root(),
+            network_id,
+            remark[0],
+            Some(remark[1]),
+            Some(CallPermission::Blocked)
+        ));
+        let (id, event) = dispatch(1, &remark);
+        assert_eq!(
+            event,
+            Some(RuntimeEvent::Dispatch(
+                crate::Event::<Test>::MessageRejected(id, RejectReason::CallBlocked)
+            ))
+        );
+
+        // Block the pallet, rule for the call is still applied
+        assert_ok!(Dispatch::set_call_filter_rule(
+            RuntimeOrigin::root(),
+            network_id,
+            remark[0],
+            None,
+            Some(CallPermission::Blocked)
+        ));
+        let (id, event) = dispatch(2, &remark);
+        assert_eq!(
+            event,
+            Some(RuntimeEvent::Dispatch(
+                crate::Event::<Test>::MessageRejected(id, RejectReason::CallBlocked)
+            ))
+        );
+
+        // Allow the call, rule for the pallet is not applied
+        assert_ok!(Dispatch::set_call_filter_rule(
+            RuntimeOrigin::root(),
+            network_id,
+            remark[0],
+            Some(remark[1]),
+            Some(CallPermission::Allowed)
+        ));
+        let (id, event) = dispatch(3, &remark);
+        assert_eq!(
+            event,
+            Some(RuntimeEvent::Dispatch(
+                crate::Event::<Test>::MessageDispatched(id, Err(DispatchError::BadOrigin))
+            ))
+        );
+
+        // Rules can't allow the call which is rejected by the static filter
+        assert_ok!(Dispatch::set_call_filter_rule(
+            RuntimeOrigin::root(),
+            network_id,
+            remark[0],
+            None,
+            Some(CallPermission::Allowed)
+        ));
+        let (id, event) = dispatch(4, &set_code);
+        assert_eq!(
+            event,
+            Some(RuntimeEvent::Dispatch(
+                crate::Event::<Test>::MessageRejected(id, RejectReason::CallFilter)
+            ))
+        );
+
+        // Remove the rules
+        assert_ok!(Dispatch::set_call_filter_rule(
+            RuntimeOrigin::root(),
+            network_id,
+            remark[0],
+            None,
+            None
+        ));
+        assert_ok!(Dispatch::set_call_filter_rule(
+            RuntimeOrigin::root(),
+            network_id,
+            remark[0],
+            Some(remark[1]),
+            None
+        ));
+        let (id, event) = dispatch(5, &remark);
+        assert_eq!(
+            event,
+            Some(RuntimeEvent::Dispatch(
+                crate::Event::<Test>::MessageDispatched(id, Err(DispatchError::BadOrigin))
+            ))
+        );
+    })
+}
+
+#[test]
+fn test_bridge_origin_cannot_set_call_filter_rule() {
+    new_test_ext().execute_with(|| {
+        let network_id: GenericNetworkId = H256::from_low_u64_be(2).into();
+        let id = types::MessageId::batched(
+            SubNetworkId::Mainnet.into(),
+            SubNetworkId::Rococo.into(),
+            1,
+            37,
+        );
+        let source = H160::repeat_byte(7);
+        let set_rule = crate::Call::<Test>::set_call_filter_rule {
+            network_id,
+            pallet_index: 0,
+            call_index: None,
+            permission: Some(CallPermission::Allowed),
+        };
+        let payload = RuntimeCall::Dispatch(set_rule.clone()).encode();
+
+        System::set_block_number(1);
+        Dispatch::dispatch(
+            network_id,
+            id,
+            Default::default(),
+            &payload,
+            AdditionalEVMInboundData { source }.into(),
+        );
+        assert_eq!(
+            System::events().pop().map(|record| record.event),
+            Some(RuntimeEvent::Dispatch(
+                crate::Event::<Test>::MessageRejected(id, RejectReason::CallFilter)
+            ))
+        );
+
+        // The origin is rejected even if the call passes the filter
+        let origin = RuntimeOrigin::from(crate::RawOrigin::new(types::CallOriginOutput {
+            network_id,
+            message_id: id.hash(),
+            timepoint: Default::default(),
+            additional: AdditionalEVMInboundData { source }.into(),
+        }));
+        assert_noop!(
+            RuntimeCall::Dispatch(set_rule).dispatch(origin),
+            DispatchError::BadOrigin
+        );
+        assert_eq!(Dispatch::call_filter_rule(network_id, (0, None)), None);
+    })
+}
+
+fn batch_payload(mode: BatchMode) -> Vec<u8> {
+    let set_value = RuntimeCall::MockApp(mock_app::Call::<Test>::set_value { value: 3 });
+    let remark = RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { remark: vec![] });
+    MessageEnvelope::V1(MessageCalls::Batch(vec![set_value, remark], mode)).encode_payload()
+}
+
+#[test]
+fn test_batch_all_or_nothing() {
+    new_test_ext().execute_with(|| {
+        let network_id: GenericNetworkId = H256::from_low_u64_be(2).into();
+        let id = types::MessageId::batched(
+            SubNetworkId::Mainnet.into(),
+            SubNetworkId::Rococo.into(),
+            1,
+            37,
+        );
+        let source = H160::repeat_byte(7);
+        let payload = batch_payload(BatchMode::AllOrNothing);
+
+        System::set_block_number(1);
+        Dispatch::dispatch(
+            network_id,
+            id,
+            Default::default(),
+            &payload,
+            AdditionalEVMInboundData { source }.into(),
+        );
+
+        // Value update is reverted with the event
+        assert_eq!(MockApp::value(), None);
+        assert_eq!(
+            System::events()
+                .into_iter()
+                .map(|record| record.event)
+                .collect::<Vec<_>>(),
+            vec![
+                RuntimeEvent::Dispatch(crate::Event::<Test>::BatchDispatched(
+                    id,
+                    vec![Ok(()), Err(DispatchError::BadOrigin)]
+                )),
+                RuntimeEvent::Dispatch(crate::Event::<Test>::MessageDispatched(
+                    id,
+                    Err(DispatchError::BadOrigin)
+                )),
+            ]
+        );
+        assert_eq!(
+            Dispatch::failed_message(id.hash()).map(|message| message.calls.len()),
+            Some(2)
+        );
+    })
+}
+
+#[test]
+fn test_batch_best_effort() {
+    new_test_ext().execute_with(|| {
+        let network_id: GenericNetworkId = H256::from_low_u64_be(2).into();
+        let id = types::MessageId::batched(
+            SubNetworkId::Mainnet.into(),
+            SubNetworkId::Rococo.into(),
+            1,
+            37,
+        );
+        let source = H160::repeat_byte(7);
+        let payload = batch_payload(BatchMode::BestEffort);
+
+        System::set_block_number(1);
+        Dispatch::dispatch(
+            network_id,
+            id,
+            Default::default(),
+            &payload,
+            AdditionalEVMInboundData { source }.into(),
+        );
+
+        assert_eq!(MockApp::value(), Some(3));
+        assert_eq!(
+            System::events().pop().map(|record| record.event),
+            Some(RuntimeEvent::Dispatch(
+                crate::Event::<Test>::MessageDispatched(id, Err(DispatchError::BadOrigin))
+            ))
+        );
+        assert!(System::events().iter().any(|record| record.event
+            == RuntimeEvent::Dispatch(crate::Event::<Test>::BatchDispatched(
+                id,
+                vec![Ok(()), Err(DispatchError::BadOrigin)]
+            ))));
+        // Only failed call is kept for retry
+        assert_eq!(
+            Dispatch::failed_message(id.hash()).map(|message| message.calls),
+            Some(MessageCalls::Batch(
+                vec![RuntimeCall::System(
+                    frame_system::pallet::Call::<Test>::remark { remark: vec![] }
+                )],
+                BatchMode::BestEffort
+            ))
+        );
+    })
+}
+
+#[test]
+fn test_batch_weight() {
+    new_test_ext().execute_with(|| {
+        let payload = batch_payload(BatchMode::AllOrNothing);
+        let calls = MessageCalls::<RuntimeCall>::decode_payload(&payload).unwrap();
+        assert_eq!(
+            Dispatch::dispatch_weight(&payload),
+            calls
+                .weight()
+                .saturating_add(<() as WeightInfo>::dispatch_batch(2))
+        );
+
+        let remark =
+            RuntimeCall::System(frame_system::pallet::Call::<Test>::remark { remark: vec![] });
+        assert_eq!(
+            MessageCalls::<RuntimeCall>::decode_payload(&remark.encode()).ok(),
+            Some(MessageCalls::Single(remark.clone()))
+        );
+
+        let too_many =
+            MessageEnvelope::V1(MessageCalls::Batch(vec![remark; 21], BatchMode::BestEffort))
+                .encode_payload();
+        assert_eq!(
+            Dispatch::dispatch_weight(&too_many),
+            <() as WeightInfo>::dispatch_decode_failed()
+        );
     })
 }
@@ -35,6 +35,9 @@
 //! WORST CASE MAP SIZE: `1000000`
 //! HOSTNAME: `TRX40`, CPU: `AMD Ryzen Threadripper 3960X 24-Core Processor`
 //! EXECUTION: None, WASM-EXECUTION: Compiled, CHAIN: Some("local"), DB CACHE: 1024
+//!
+//! Only `dispatch_success` and `dispatch_decode_failed` come from this run, the other
+//! functions are estimated by hand until the matching benchmarks are run.
 
 // Executed Command:
 // ./target/release/framenode
@@ -60,41 +63,154 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
 	fn dispatch_success() -> Weight;
 	fn dispatch_decode_failed() -> Weight;
+	fn on_initialize(r: u32, ) -> Weight;
+	fn dispatch_deferred() -> Weight;
+	fn service_deferred_message() -> Weight;
+	fn retry_dispatch() -> Weight;
+	fn prune_failed_messages(f: u32, ) -> Weight;
+	fn set_call_filter_rule() -> Weight;
+	fn dispatch_batch(c: u32, ) -> Weight;
 }
 
 /// Weights for dispatch using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	/// Storage reads and writes of the dispatch result and the failed message
+	/// are added to the measured weight, they weren't done in the benchmark run.
 	fn dispatch_success() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
 		//  Estimated: `0`
 		// Minimum execution time: 4_981_000 picoseconds.
 		Weight::from_parts(5_230_000, 0)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
+	/// Storage writes of the dispatch result are added to the measured weight.
 	fn dispatch_decode_failed() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
 		//  Estimated: `0`
 		// Minimum execution time: 2_790_000 picoseconds.
 		Weight::from_parts(2_900_000, 0)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Removes `r` expired dispatch results.
+	fn on_initialize(r: u32, ) -> Weight {
+		Weight::from_parts(3_600_000, 3572)
+			.saturating_add(Weight::from_parts(3_200_000, 2587).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(r.into())))
+	}
+	fn dispatch_deferred() -> Weight {
+		Weight::from_parts(13_900_000, 4455)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Excludes the weight of the dispatched call itself.
+	fn service_deferred_message() -> Weight {
+		Weight::from_parts(21_300_000, 8769)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Excludes the weight of the dispatched call itself.
+	fn retry_dispatch() -> Weight {
+		Weight::from_parts(16_900_000, 5398)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Removes `f` expired failed messages.
+	fn prune_failed_messages(f: u32, ) -> Weight {
+		Weight::from_parts(3_400_000, 3572)
+			.saturating_add(Weight::from_parts(1_100_000, 32).saturating_mul(f.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(f.into())))
+	}
+	fn set_call_filter_rule() -> Weight {
+		Weight::from_parts(8_500_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Checks the call filter rules for each of the `c` calls in the batch.
+	fn dispatch_batch(c: u32, ) -> Weight {
+		Weight::from_parts(14_300_000, 6930)
+			.saturating_add(Weight::from_parts(7_900_000, 4950).saturating_mul(c.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(c.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	/// Storage reads and writes of the dispatch result and the failed message
+	/// are added to the measured weight, they weren't done in the benchmark run.
 	fn dispatch_success() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
 		//  Estimated: `0`
 		// Minimum execution time: 4_981_000 picoseconds.
 		Weight::from_parts(5_230_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
+	/// Storage writes of the dispatch result are added to the measured weight.
 	fn dispatch_decode_failed() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `0`
 		//  Estimated: `0`
 		// Minimum execution time: 2_790_000 picoseconds.
 		Weight::from_parts(2_900_000, 0)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Removes `r` expired dispatch results.
+	fn on_initialize(r: u32, ) -> Weight {
+		Weight::from_parts(3_600_000, 3572)
+			.saturating_add(Weight::from_parts(3_200_000, 2587).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(r.into())))
+	}
+	fn dispatch_deferred() -> Weight {
+		Weight::from_parts(13_900_000, 4455)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Excludes the weight of the dispatched call itself.
+	fn service_deferred_message() -> Weight {
+		Weight::from_parts(21_300_000, 8769)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Excludes the weight of the dispatched call itself.
+	fn retry_dispatch() -> Weight {
+		Weight::from_parts(16_900_000, 5398)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Removes `f` expired failed messages.
+	fn prune_failed_messages(f: u32, ) -> Weight {
+		Weight::from_parts(3_400_000, 3572)
+			.saturating_add(Weight::from_parts(1_100_000, 32).saturating_mul(f.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(f.into())))
+	}
+	fn set_call_filter_rule() -> Weight {
+		Weight::from_parts(8_500_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Checks the call filter rules for each of the `c` calls in the batch.
+	fn dispatch_batch(c: u32, ) -> Weight {
+		Weight::from_parts(14_300_000, 6930)
+			.saturating_add(Weight::from_parts(7_900_000, 4950).saturating_mul(c.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(c.into())))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
 	}
 }
@@ -72,6 +72,20 @@ benchmarks! {
         assert_eq!(Currencies::<T>::free_balance(asset_id, &caller), 0u128.into());
     }
 
+    cancel_transfer {
+        let asset_id = <T as Config>::AssetRegistry::register_asset(BASE_NETWORK_ID.into(), b"ETH".to_vec().into(), b"ETH".to_vec().into())?;
+        crate::Pallet::<T>::register_network_with_existing_asset(RawOrigin::Root.into(), BASE_NETWORK_ID, H160::repeat_byte(1), asset_id.clone(), 18).unwrap();
+        let caller: T::AccountId = whitelisted_caller();
+        let recipient = H160::repeat_byte(2);
+        let amount = 1000u128;
+
+        Currencies::<T>::deposit(asset_id.clone(), &caller, amount.into())?;
+        let message_id = crate::Pallet::<T>::burn_inner(caller.clone(), BASE_NETWORK_ID, asset_id.clone(), recipient, amount.into())?;
+    }: _(RawOrigin::Signed(caller.clone()), BASE_NETWORK_ID, message_id)
+    verify {
+        assert_eq!(Currencies::<T>::free_balance(asset_id, &caller), amount.into());
+    }
+
     // Benchmark `mint` extrinsic under worst case conditions:
     // * `mint` successfully adds amount to recipient account
     mint {
@@ -124,7 +124,7 @@ pub mod pallet {
     use bridge_types::evm::*;
     use bridge_types::traits::{
         AppRegistry, BalancePrecisionConverter, BridgeApp, BridgeAssetRegistry,
-        MessageStatusNotifier, OutboundChannel,
+        MessageStatusNotifier, OutboundChannel, OutboundChannelCancel,
     };
     use bridge_types::traits::{BridgeAssetLocker, EVMOutboundChannel};
     use bridge_types::types::{
@@ -164,6 +164,7 @@ pub mod pallet {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
         type OutboundChannel: OutboundChannel<EVMChainId, Self::AccountId, AdditionalEVMOutboundData>
+            + OutboundChannelCancel<EVMChainId, AdditionalEVMOutboundData>
             + EVMOutboundChannel;
 
         type CallOrigin: EnsureOrigin<
@@ -307,6 +308,8 @@ pub mod pallet {
         NotEnoughFeesCollected,
         BaseFeeIsNotAvailable,
         InvalidBaseFeeUpdate,
+        /// Transfer can be cancelled only by its sender
+        NotTransferSender,
     }
 
     #[pallet::genesis_config]
@@ -689,6 +692,39 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Cancel transfer which is not committed yet and unlock transferred asset.
+        ///
+        /// Exactly the amount locked on send is unlocked, as recorded by
+        /// [`Config::MessageStatusNotifier`]. Fees paid for the transfer are not refunded.
+        #[pallet::call_index(9)]
+        #[pallet::weight(<T as Config>::WeightInfo::cancel_transfer())]
+        pub fn cancel_transfer(
+            origin: OriginFor<T>,
+            network_id: EVMChainId,
+            message_id: H256,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let (sender, asset_id, amount) =
+                T::MessageStatusNotifier::outbound_request_info(network_id.into(), message_id)
+                    .ok_or(Error::<T>::WrongRequest)?;
+            ensure!(sender == who, Error::<T>::NotTransferSender);
+
+            let (payload, additional) = T::OutboundChannel::cancel(network_id, message_id)?;
+            ensure!(
+                AppAddresses::<T>::get(network_id) == Some(additional.target),
+                Error::<T>::WrongRequest
+            );
+            let payload = MintPayload::<T::AccountId>::decode(&payload)
+                .map_err(|_| Error::<T>::WrongRequest)?;
+            ensure!(
+                payload.sender == who
+                    && AssetsByAddresses::<T>::get(network_id, payload.token).as_ref()
+                        == Some(&asset_id),
+                Error::<T>::WrongRequest
+            );
+            Self::refund_inner(network_id, who, asset_id, amount)
+        }
     }
 
     impl<T: Config> Pallet<T> {
@@ -29,18 +29,21 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
 use bridge_types::traits::{AppRegistry, BalancePrecisionConverter, BridgeAssetRegistry};
-use bridge_types::traits::{EVMOutboundChannel, OutboundChannel};
+use bridge_types::traits::{
+    EVMOutboundChannel, MessageStatusNotifier, OutboundChannel, OutboundChannelCancel,
+};
 use currencies::BasicCurrencyAdapter;
 
 // Mock runtime
 use bridge_types::evm::AdditionalEVMOutboundData;
-use bridge_types::types::{AssetKind, GenericAdditionalInboundData};
+use bridge_types::types::{AssetKind, GenericAdditionalInboundData, MessageStatus};
 use bridge_types::H160;
 use bridge_types::H256;
-use bridge_types::{EVMChainId, GenericNetworkId, U256};
+use bridge_types::{EVMChainId, GenericAccount, GenericNetworkId, GenericTimepoint, U256};
 use frame_support::dispatch::DispatchResult;
 use frame_support::parameter_types;
 use frame_support::traits::{Everything, GenesisBuild};
+use frame_support::weights::Weight;
 use frame_system as system;
 use sp_core::{ConstU128, ConstU64};
 use sp_keyring::sr25519::Keyring;
@@ -60,6 +63,7 @@ type Amount = i128;
 pub const XOR: AssetId = H256::repeat_byte(1);
 pub const DAI: AssetId = H256::repeat_byte(2);
 pub const ETH: AssetId = H256::repeat_byte(3);
+pub const USDT: AssetId = H256::repeat_byte(4);
 
 frame_support::construct_runtime!(
     pub enum Test where
@@ -160,6 +164,10 @@ impl currencies::Config for Test {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const MaxImmediateDispatchWeight: Weight = Weight::MAX;
+}
+
 impl dispatch::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type OriginOutput =
@@ -169,6 +177,15 @@ impl dispatch::Config for Test {
     type Hashing = Keccak256;
     type Call = RuntimeCall;
     type CallFilter = Everything;
+    type CallFilterOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type DispatchResultsRetention = ConstU64<100>;
+    type MaxImmediateDispatchWeight = MaxImmediateDispatchWeight;
+    type DeferredMessageLifetime = ConstU64<100>;
+    type RetryOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type MaxDispatchRetries = frame_support::traits::ConstU32<0>;
+    type FailedMessageLifetime = ConstU64<100>;
+    type MaxBatchCalls = frame_support::traits::ConstU32<20>;
+    type FailureReporter = ();
     type WeightInfo = ();
 }
 
@@ -200,18 +217,22 @@ pub struct BalancePrecisionConverterImpl;
 impl BalancePrecisionConverter<AssetId, Balance, U256> for BalancePrecisionConverterImpl {
     fn from_sidechain(
         _asset_id: &AssetId,
-        _sidechain_precision: u8,
+        sidechain_precision: u8,
         amount: U256,
     ) -> Option<(Balance, U256)> {
-        Some((amount.try_into().ok()?, amount))
+        let coeff = 10u128.checked_pow(18u32.checked_sub(sidechain_precision.into())?)?;
+        let thischain_amount: Balance = amount.try_into().ok()?;
+        Some((thischain_amount.checked_mul(coeff)?, amount))
     }
 
     fn to_sidechain(
         _asset_id: &AssetId,
-        _sidechain_precision: u8,
+        sidechain_precision: u8,
         amount: Balance,
     ) -> Option<(Balance, U256)> {
-        Some((amount, amount.into()))
+        let coeff = 10u128.checked_pow(18u32.checked_sub(sidechain_precision.into())?)?;
+        let sidechain_amount = amount / coeff;
+        Some((sidechain_amount * coeff, sidechain_amount.into()))
     }
 }
 
@@ -257,14 +278,25 @@ impl BridgeAssetRegistry<AccountId, AssetId> for BridgeAssetRegistryImpl {
 
 pub struct OutboundChannelImpl;
 
+thread_local! {
+    static QUEUED_MESSAGES: std::cell::RefCell<Vec<(H256, Vec<u8>, AdditionalEVMOutboundData)>> =
+        Default::default();
+}
+
 impl OutboundChannel<EVMChainId, AccountId, AdditionalEVMOutboundData> for OutboundChannelImpl {
     fn submit(
         _network_id: EVMChainId,
         _who: &system::RawOrigin<AccountId>,
-        _payload: &[u8],
-        _additional: AdditionalEVMOutboundData,
+        payload: &[u8],
+        additional: AdditionalEVMOutboundData,
     ) -> Result<H256, DispatchError> {
-        Ok(H256::random())
+        let message_id = H256::random();
+        QUEUED_MESSAGES.with(|messages| {
+            messages
+                .borrow_mut()
+                .push((message_id, payload.to_vec(), additional))
+        });
+        Ok(message_id)
     }
 
     fn submit_weight() -> frame_support::weights::Weight {
@@ -272,6 +304,85 @@ impl OutboundChannel<EVMChainId, AccountId, AdditionalEVMOutboundData> for Outbo
     }
 }
 
+impl OutboundChannelCancel<EVMChainId, AdditionalEVMOutboundData> for OutboundChannelImpl {
+    fn cancel(
+        _network_id: EVMChainId,
+        message_id: H256,
+    ) -> Result<(Vec<u8>, AdditionalEVMOutboundData), DispatchError> {
+        QUEUED_MESSAGES.with(|messages| {
+            let mut messages = messages.borrow_mut();
+            let idx = messages
+                .iter()
+                .position(|(id, _, _)| *id == message_id)
+                .ok_or(DispatchError::Other("message not found"))?;
+            let (_, payload, additional) = messages.remove(idx);
+            Ok((payload, additional))
+        })
+    }
+
+    fn cancel_weight() -> frame_support::weights::Weight {
+        frame_support::weights::Weight::from_all(1)
+    }
+}
+
+pub struct MessageStatusNotifierImpl;
+
+thread_local! {
+    static OUTBOUND_REQUESTS: std::cell::RefCell<Vec<(H256, AccountId, AssetId, Balance)>> =
+        Default::default();
+}
+
+impl MessageStatusNotifier<AssetId, AccountId, Balance> for MessageStatusNotifierImpl {
+    fn update_status(
+        _network_id: GenericNetworkId,
+        _message_id: H256,
+        _status: MessageStatus,
+        _end_timepoint: GenericTimepoint,
+    ) {
+    }
+
+    fn inbound_request(
+        _network_id: GenericNetworkId,
+        _message_id: H256,
+        _source: GenericAccount,
+        _dest: AccountId,
+        _asset_id: AssetId,
+        _amount: Balance,
+        _start_timepoint: GenericTimepoint,
+        _status: MessageStatus,
+    ) {
+    }
+
+    fn outbound_request(
+        _network_id: GenericNetworkId,
+        message_id: H256,
+        source: AccountId,
+        _dest: GenericAccount,
+        asset_id: AssetId,
+        amount: Balance,
+        _status: MessageStatus,
+    ) {
+        OUTBOUND_REQUESTS.with(|requests| {
+            requests
+                .borrow_mut()
+                .push((message_id, source, asset_id, amount))
+        });
+    }
+
+    fn outbound_request_info(
+        _network_id: GenericNetworkId,
+        message_id: H256,
+    ) -> Option<(AccountId, AssetId, Balance)> {
+        OUTBOUND_REQUESTS.with(|requests| {
+            requests
+                .borrow()
+                .iter()
+                .find(|(id, ..)| *id == message_id)
+                .map(|(_, source, asset_id, amount)| (source.clone(), *asset_id, *amount))
+        })
+    }
+}
+
 impl EVMOutboundChannel for OutboundChannelImpl {
     fn submit_gas(_chain_id: EVMChainId) -> Result<U256, DispatchError> {
         Ok(U256::one())
@@ -285,7 +396,7 @@ impl fungible_app::Config for Test {
         bridge_types::types::CallOriginOutput<GenericNetworkId, H256, GenericAdditionalInboundData>,
     >;
     type WeightInfo = ();
-    type MessageStatusNotifier = ();
+    type MessageStatusNotifier = MessageStatusNotifierImpl;
     type BalancePrecisionConverter = BalancePrecisionConverterImpl;
     type AppRegistry = AppRegistryImpl;
     type AssetRegistry = BridgeAssetRegistryImpl;
@@ -328,6 +439,13 @@ pub fn new_tester() -> sp_io::TestExternalities {
                     AssetKind::Sidechain,
                     18,
                 ),
+                (
+                    BASE_NETWORK_ID,
+                    USDT,
+                    H160::repeat_byte(5),
+                    AssetKind::Thischain,
+                    6,
+                ),
             ],
         },
         &mut storage,
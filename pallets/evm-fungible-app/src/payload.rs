@@ -33,7 +33,7 @@
 #[cfg(not(feature = "std"))]
 use alloc::string::{String, ToString};
 
-use codec::Encode;
+use codec::{Decode, Encode};
 use sp_core::RuntimeDebug;
 use sp_std::prelude::*;
 use sp_std::vec;
@@ -186,6 +186,28 @@ impl<AccountId: Encode> MintPayload<AccountId> {
     }
 }
 
+impl<AccountId: Encode + Decode> MintPayload<AccountId> {
+    /// Decode ABI-encoded payload
+    pub fn decode(data: &[u8]) -> Result<Self, ethabi::Error> {
+        let function = unlock_function();
+        if data.len() < 4 || data[..4] != function.short_signature() {
+            return Err(ethabi::Error::InvalidData);
+        }
+        match function.decode_input(&data[4..])?.as_slice() {
+            [Token::Address(token), Token::FixedBytes(sender), Token::Address(recipient), Token::Uint(amount)] => {
+                Ok(Self {
+                    token: *token,
+                    sender: AccountId::decode(&mut &sender[..])
+                        .map_err(|_| ethabi::Error::InvalidData)?,
+                    recipient: *recipient,
+                    amount: *amount,
+                })
+            }
+            _ => Err(ethabi::Error::InvalidData),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -206,4 +228,18 @@ mod tests {
         println!("Payload (ABI-encoded):");
         println!("  {:?}", payload.encode().unwrap().to_hex::<String>());
     }
+
+    #[test]
+    fn test_outbound_payload_decode() {
+        let payload: MintPayload<[u8; 32]> = MintPayload {
+            token: hex!["e1638d0a9f5349bb7d3d748b514b8553dfddb46c"].into(),
+            sender: hex!["1aabf8593d9d109b6288149afa35690314f0b798289f8c5c466838dd218a4d50"],
+            recipient: hex!["ccb3c82493ac988cebe552779e7195a3a9dc651f"].into(),
+            amount: U256::from_str_radix("100", 10).unwrap(),
+        };
+
+        let encoded = payload.encode().unwrap();
+        assert_eq!(MintPayload::decode(&encoded).unwrap(), payload);
+        assert!(MintPayload::<[u8; 32]>::decode(&encoded[1..]).is_err());
+    }
 }
@@ -30,7 +30,7 @@
 
 use crate::mock::{
     new_tester, AccountId, FungibleApp, RuntimeEvent, RuntimeOrigin, System, Test, Tokens,
-    BASE_NETWORK_ID, ETH, XOR,
+    BASE_NETWORK_ID, ETH, USDT, XOR,
 };
 use crate::Error;
 use crate::{AppAddresses, AssetKinds, AssetsByAddresses, TokenAddresses};
@@ -155,6 +155,76 @@ fn burn_should_emit_bridge_event() {
     });
 }
 
+#[test]
+fn cancel_transfer_should_unlock_asset() {
+    new_tester().execute_with(|| {
+        let asset_id = XOR;
+        let recipient = H160::repeat_byte(2);
+        let bob: AccountId = Keyring::Bob.into();
+        let alice: AccountId = Keyring::Alice.into();
+        let amount = 20;
+        Tokens::deposit(asset_id, &bob, 500).unwrap();
+
+        let message_id =
+            FungibleApp::burn_inner(bob.clone(), BASE_NETWORK_ID, asset_id, recipient, amount)
+                .unwrap();
+        assert_eq!(Tokens::free_balance(asset_id, &bob), 480);
+
+        assert_noop!(
+            FungibleApp::cancel_transfer(RuntimeOrigin::signed(alice), BASE_NETWORK_ID, message_id),
+            Error::<Test>::NotTransferSender
+        );
+        assert_ok!(FungibleApp::cancel_transfer(
+            RuntimeOrigin::signed(bob.clone()),
+            BASE_NETWORK_ID,
+            message_id
+        ));
+        assert_eq!(Tokens::free_balance(asset_id, &bob), 500);
+        assert_eq!(
+            RuntimeEvent::FungibleApp(crate::Event::<Test>::Refunded {
+                network_id: BASE_NETWORK_ID,
+                recipient: bob.clone(),
+                asset_id,
+                amount
+            }),
+            last_event()
+        );
+        assert!(FungibleApp::cancel_transfer(
+            RuntimeOrigin::signed(bob),
+            BASE_NETWORK_ID,
+            message_id
+        )
+        .is_err());
+    });
+}
+
+#[test]
+fn cancel_transfer_should_unlock_locked_amount() {
+    new_tester().execute_with(|| {
+        let bob: AccountId = Keyring::Bob.into();
+        // Sidechain precision is 6, so the last 12 digits are not transferred
+        let amount = 1_000_000_000_000_000_001;
+        Tokens::deposit(USDT, &bob, amount).unwrap();
+
+        let message_id = FungibleApp::burn_inner(
+            bob.clone(),
+            BASE_NETWORK_ID,
+            USDT,
+            H160::repeat_byte(2),
+            amount,
+        )
+        .unwrap();
+        assert_eq!(Tokens::free_balance(USDT, &bob), 0);
+
+        assert_ok!(FungibleApp::cancel_transfer(
+            RuntimeOrigin::signed(bob.clone()),
+            BASE_NETWORK_ID,
+            message_id
+        ));
+        assert_eq!(Tokens::free_balance(USDT, &bob), amount);
+    });
+}
+
 #[test]
 fn burn_zero_amount_must_fail() {
     new_tester().execute_with(|| {
@@ -46,6 +46,7 @@ pub trait WeightInfo {
 	fn register_native_app() -> Weight;
 	fn register_existing_native_app() -> Weight;
 	fn register_asset_internal() -> Weight;
+	fn cancel_transfer() -> Weight;
 }
 
 // For backwards compatibility and tests
@@ -84,4 +85,8 @@ impl WeightInfo for () {
 	fn register_asset_internal() -> Weight {
 		Default::default()
     }
+
+	fn cancel_transfer() -> Weight {
+		Default::default()
+	}
 }
@@ -37,7 +37,8 @@ use bridge_types::types::{AssetKind, GenericAdditionalInboundData};
 use bridge_types::GenericNetworkId;
 use bridge_types::H256;
 use frame_support::parameter_types;
-use frame_support::traits::{Everything, GenesisBuild};
+use frame_support::traits::{ConstU64, Everything, GenesisBuild};
+use frame_support::weights::Weight;
 use frame_system as system;
 use sp_keyring::sr25519::Keyring;
 use sp_runtime::testing::Header;
@@ -157,6 +158,10 @@ impl currencies::Config for Test {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const MaxImmediateDispatchWeight: Weight = Weight::MAX;
+}
+
 impl dispatch::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type OriginOutput =
@@ -166,6 +171,15 @@ impl dispatch::Config for Test {
     type Hashing = Keccak256;
     type Call = RuntimeCall;
     type CallFilter = Everything;
+    type CallFilterOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type DispatchResultsRetention = ConstU64<100>;
+    type MaxImmediateDispatchWeight = MaxImmediateDispatchWeight;
+    type DeferredMessageLifetime = ConstU64<100>;
+    type RetryOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type MaxDispatchRetries = frame_support::traits::ConstU32<0>;
+    type FailedMessageLifetime = ConstU64<100>;
+    type MaxBatchCalls = frame_support::traits::ConstU32<20>;
+    type FailureReporter = ();
     type WeightInfo = ();
 }
 
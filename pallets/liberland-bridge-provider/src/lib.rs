@@ -491,4 +491,18 @@ impl<T: Config>
         };
         Transactions::<T>::insert((&network_id, &source), message_id, bridge_request);
     }
+
+    fn outbound_request_info(
+        network_id: GenericNetworkId,
+        message_id: H256,
+    ) -> Option<(AccountId32, LiberlandAssetId, T::Balance)> {
+        let sender = Senders::<T>::get(network_id, message_id)?;
+        let req = Transactions::<T>::get((network_id, &sender), message_id)?;
+        match (req.direction, req.source) {
+            (MessageDirection::Outbound, GenericAccount::Liberland(source)) => {
+                Some((source, req.asset_id, req.amount))
+            }
+            _ => None,
+        }
+    }
 }
@@ -319,7 +319,7 @@ impl<T: Config> bridge_types::traits::Verifier for Pallet<T> {
     }
 
     #[cfg(feature = "runtime-benchmarks")]
-    fn valid_proof() -> Option<Self::Proof> {
+    fn valid_proof(_network_id: GenericNetworkId, _commitment_hash: H256) -> Option<Self::Proof> {
         None
     }
 }
@@ -350,7 +350,7 @@ impl<T: Config> bridge_types::traits::Verifier for MultiEVMVerifier<T> {
     }
 
     #[cfg(feature = "runtime-benchmarks")]
-    fn valid_proof() -> Option<Self::Proof> {
+    fn valid_proof(_network_id: GenericNetworkId, _commitment_hash: H256) -> Option<Self::Proof> {
         None
     }
 }
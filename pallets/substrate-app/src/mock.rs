@@ -44,7 +44,8 @@ use currencies::BasicCurrencyAdapter;
 use bridge_types::types::AssetKind;
 use bridge_types::SubNetworkId;
 use frame_support::parameter_types;
-use frame_support::traits::{Everything, GenesisBuild};
+use frame_support::traits::{ConstU64, Everything, GenesisBuild};
+use frame_support::weights::Weight;
 use frame_support::Deserialize;
 use frame_support::RuntimeDebug;
 use frame_support::Serialize;
@@ -191,6 +192,10 @@ parameter_types! {
     pub GetBridgeAccountId: AccountId = AccountId32::from([3; 32]);
 }
 
+parameter_types! {
+    pub const MaxImmediateDispatchWeight: Weight = Weight::MAX;
+}
+
 impl dispatch::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type OriginOutput = bridge_types::types::CallOriginOutput<SubNetworkId, H256, ()>;
@@ -199,6 +204,15 @@ impl dispatch::Config for Test {
     type Hashing = Keccak256;
     type Call = RuntimeCall;
     type CallFilter = Everything;
+    type CallFilterOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type DispatchResultsRetention = ConstU64<100>;
+    type MaxImmediateDispatchWeight = MaxImmediateDispatchWeight;
+    type DeferredMessageLifetime = ConstU64<100>;
+    type RetryOrigin = frame_system::EnsureRoot<Self::AccountId>;
+    type MaxDispatchRetries = frame_support::traits::ConstU32<0>;
+    type FailedMessageLifetime = ConstU64<100>;
+    type MaxBatchCalls = frame_support::traits::ConstU32<20>;
+    type FailureReporter = ();
     type WeightInfo = ();
 }
 
@@ -59,7 +59,7 @@ benchmarks! {
                 nonce: 1u64,
             }
         );
-        let proof = T::Verifier::valid_proof().unwrap();
+        let proof = T::Verifier::valid_proof(BASE_NETWORK_ID.into(), commitment.hash()).unwrap();
     }: _(RawOrigin::None, BASE_NETWORK_ID, commitment, proof)
     verify {
         assert_eq!(1, <ChannelNonces<T>>::get(BASE_NETWORK_ID));
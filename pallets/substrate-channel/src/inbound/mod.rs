@@ -143,6 +143,7 @@ pub mod pallet {
                 bridge_types::GenericCommitment::Sub(commitment) => commitment
                     .messages
                     .iter()
+                    .filter(|m| !m.is_tombstone())
                     .map(|m| T::MessageDispatch::dispatch_weight(&m.payload))
                     .fold(Weight::zero(), |acc, w| acc.saturating_add(w)),
             };
@@ -186,6 +187,10 @@ pub mod pallet {
             })?;
 
             for (idx, message) in sub_commitment.messages.into_iter().enumerate() {
+                // Cancelled message, which only keeps ids of the other messages
+                if message.is_tombstone() {
+                    continue;
+                }
                 let message_id = MessageId::batched(
                     network_id.into(),
                     T::ThisNetworkId::get(),
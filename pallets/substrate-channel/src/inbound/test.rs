@@ -46,6 +46,7 @@ use sp_runtime::transaction_validity::{
 };
 use sp_runtime::MultiSignature;
 use sp_std::convert::From;
+use std::cell::RefCell;
 
 use bridge_types::traits::MessageDispatch;
 use bridge_types::{GenericNetworkId, GenericTimepoint};
@@ -172,16 +173,29 @@ impl Verifier for MockVerifier {
     }
 
     #[cfg(feature = "runtime-benchmarks")]
-    fn valid_proof() -> Option<Self::Proof> {
+    fn valid_proof(_network_id: GenericNetworkId, _commitment_hash: H256) -> Option<Self::Proof> {
         Some(Default::default())
     }
 }
 
+thread_local! {
+    static DISPATCHED_MESSAGES: RefCell<Vec<MessageId>> = RefCell::new(Vec::new());
+}
+
 // Mock Dispatch
 pub struct MockMessageDispatch;
 
 impl MessageDispatch<Test, SubNetworkId, MessageId, ()> for MockMessageDispatch {
-    fn dispatch(_: SubNetworkId, _: MessageId, _: GenericTimepoint, _: &[u8], _: ()) {}
+    fn dispatch(
+        _: SubNetworkId,
+        message_id: MessageId,
+        _: GenericTimepoint,
+        _: &[u8],
+        _: (),
+    ) -> frame_support::weights::Weight {
+        DISPATCHED_MESSAGES.with(|messages| messages.borrow_mut().push(message_id));
+        Default::default()
+    }
 
     fn dispatch_weight(_: &[u8]) -> frame_support::weights::Weight {
         Default::default()
@@ -300,6 +314,44 @@ fn test_submit() {
     });
 }
 
+#[test]
+fn test_submit_skips_tombstones() {
+    new_tester().execute_with(|| {
+        let message = BridgeMessage {
+            timepoint: Default::default(),
+            payload: vec![0, 1, 2].try_into().unwrap(),
+        };
+        let tombstone = BridgeMessage {
+            timepoint: Default::default(),
+            payload: bridge_types::TOMBSTONE_PAYLOAD.to_vec().try_into().unwrap(),
+        };
+        let commitment =
+            bridge_types::GenericCommitment::Sub(bridge_types::substrate::Commitment {
+                nonce: 1,
+                messages: vec![tombstone, message].try_into().unwrap(),
+            });
+        let call = Call::<Test>::submit {
+            network_id: BASE_NETWORK_ID,
+            commitment,
+            proof: vec![],
+        };
+        assert_ok!(call.dispatch_bypass_filter(RuntimeOrigin::none()));
+
+        // Only the second message is dispatched, with its original id
+        DISPATCHED_MESSAGES.with(|messages| {
+            assert_eq!(
+                *messages.borrow(),
+                vec![MessageId::batched(
+                    BASE_NETWORK_ID.into(),
+                    ThisNetworkId::get(),
+                    1,
+                    1
+                )]
+            )
+        });
+    });
+}
+
 #[test]
 fn test_submit_with_invalid_nonce() {
     new_tester().execute_with(|| {
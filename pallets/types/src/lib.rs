@@ -231,7 +231,7 @@ pub enum GenericCommitment<MaxMessages: Get<u32>, MaxPayload: Get<u32>> {
     #[cfg_attr(feature = "std", serde(rename = "evm"))]
     EVM(evm::Commitment<MaxMessages, MaxPayload>),
     #[cfg_attr(feature = "std", serde(rename = "ton"))]
-    TON(ton::Commitment<MaxPayload>),
+    TON(ton::Commitment<MaxMessages, MaxPayload>),
 }
 
 impl<MaxMessages: Get<u32>, MaxPayload: Get<u32>> GenericCommitment<MaxMessages, MaxPayload> {
@@ -347,13 +347,49 @@ impl From<u32> for LiberlandAssetId {
 pub enum GenericBridgeMessage<MaxPayload: Get<u32>> {
     Sub(substrate::BridgeMessage<MaxPayload>),
     EVM(evm::Message<MaxPayload>),
+    TON(ton::Message<MaxPayload>),
 }
 
+/// Payload of the tombstone which takes the place of a cancelled message in the batch.
+///
+/// Target networks must skip tombstones without dispatching them.
+pub const TOMBSTONE_PAYLOAD: &[u8] = b"tombstone";
+
 impl<N: Get<u32>> GenericBridgeMessage<N> {
     pub fn payload(&self) -> &[u8] {
         match self {
             GenericBridgeMessage::Sub(message) => &message.payload,
             GenericBridgeMessage::EVM(message) => &message.payload,
+            GenericBridgeMessage::TON(message) => &message.payload,
         }
     }
+
+    /// Tombstone with [`TOMBSTONE_PAYLOAD`] which replaces the message when it's cancelled.
+    ///
+    /// Tombstones to EVM networks have zero target and `max_gas`.
+    pub fn tombstone(&self) -> Self {
+        let payload = sp_runtime::BoundedVec::truncate_from(TOMBSTONE_PAYLOAD.to_vec());
+        match self {
+            GenericBridgeMessage::Sub(message) => {
+                GenericBridgeMessage::Sub(substrate::BridgeMessage {
+                    payload,
+                    timepoint: message.timepoint,
+                })
+            }
+            GenericBridgeMessage::EVM(_) => GenericBridgeMessage::EVM(evm::Message {
+                target: H160::zero(),
+                max_gas: U256::zero(),
+                payload,
+            }),
+            GenericBridgeMessage::TON(message) => GenericBridgeMessage::TON(ton::Message {
+                target: message.target.clone(),
+                payload,
+            }),
+        }
+    }
+
+    /// Check if the message is a tombstone of the cancelled message.
+    pub fn is_tombstone(&self) -> bool {
+        self.payload() == TOMBSTONE_PAYLOAD
+    }
 }
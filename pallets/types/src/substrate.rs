@@ -251,6 +251,14 @@ pub struct BridgeMessage<MaxPayload: Get<u32>> {
     pub timepoint: GenericTimepoint,
 }
 
+impl<MaxPayload: Get<u32>> BridgeMessage<MaxPayload> {
+    /// Check if the message is a tombstone of the cancelled message,
+    /// see [`crate::TOMBSTONE_PAYLOAD`].
+    pub fn is_tombstone(&self) -> bool {
+        self.payload.as_slice() == crate::TOMBSTONE_PAYLOAD
+    }
+}
+
 #[derive(
     Clone,
     Copy,
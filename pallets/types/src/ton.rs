@@ -35,6 +35,7 @@ use ethereum_types::H128;
 use serde::{Deserialize, Serialize};
 use sp_core::{Get, RuntimeDebug, H256};
 use sp_runtime::{traits::Hash, BoundedVec};
+use sp_std::prelude::*;
 
 use crate::{MainnetAssetId, MainnetBalance};
 
@@ -246,23 +247,31 @@ pub struct TonAppInfo {
     PartialEq(bound = ""),
     Eq(bound = "")
 )]
-#[scale_info(skip_type_params(MaxPayload))]
+#[scale_info(skip_type_params(MaxMessages, MaxPayload))]
 #[cfg_attr(feature = "std", serde(bound = ""))]
-pub enum Commitment<MaxPayload: Get<u32>> {
+pub enum Commitment<MaxMessages: Get<u32>, MaxPayload: Get<u32>> {
     #[cfg_attr(feature = "std", serde(rename = "inbound"))]
     Inbound(InboundCommitment<MaxPayload>),
+    #[cfg_attr(feature = "std", serde(rename = "outbound"))]
+    Outbound(OutboundCommitment<MaxMessages, MaxPayload>),
+    #[cfg_attr(feature = "std", serde(rename = "statusReport"))]
+    StatusReport(StatusReport<MaxMessages>),
 }
 
-impl<MaxPayload: Get<u32>> Commitment<MaxPayload> {
+impl<MaxMessages: Get<u32>, MaxPayload: Get<u32>> Commitment<MaxMessages, MaxPayload> {
     pub fn hash(&self) -> H256 {
         match self {
             Commitment::Inbound(commitment) => commitment.hash(),
+            Commitment::Outbound(commitment) => commitment.hash(),
+            Commitment::StatusReport(commitment) => commitment.hash(),
         }
     }
 
     pub fn nonce(&self) -> u64 {
         match self {
             Commitment::Inbound(commitment) => commitment.nonce,
+            Commitment::Outbound(commitment) => commitment.nonce,
+            Commitment::StatusReport(commitment) => commitment.nonce,
         }
     }
 }
@@ -295,3 +304,139 @@ impl<MaxPayload: Get<u32>> InboundCommitment<MaxPayload> {
         ("ton-inbound", self).using_encoded(|encoded| sp_runtime::traits::Keccak256::hash(encoded))
     }
 }
+
+#[derive(Encode, Decode, scale_info::TypeInfo, codec::MaxEncodedLen, Derivative)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derivative(
+    Debug(bound = ""),
+    Clone(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[scale_info(skip_type_params(MaxMessages))]
+#[cfg_attr(feature = "std", serde(bound = ""))]
+pub struct StatusReport<MaxMessages: Get<u32>> {
+    /// Channel contract address.
+    pub channel: TonAddress,
+    /// Transaction at which the batch was delivered.
+    pub transaction_id: TonTransactionId,
+    /// Batch nonce of the delivered outbound commitment.
+    pub nonce: u64,
+    /// Dispatch result of each message in the batch.
+    pub results: BoundedVec<bool, MaxMessages>,
+}
+
+impl<MaxMessages: Get<u32>> StatusReport<MaxMessages> {
+    pub fn hash(&self) -> H256 {
+        ("ton-status-report", self)
+            .using_encoded(|encoded| sp_runtime::traits::Keccak256::hash(encoded))
+    }
+}
+
+#[derive(
+    Clone,
+    Copy,
+    RuntimeDebug,
+    Encode,
+    Decode,
+    PartialEq,
+    Eq,
+    Default,
+    scale_info::TypeInfo,
+    codec::MaxEncodedLen,
+)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct AdditionalTONOutboundData {
+    pub target: TonAddress,
+}
+
+/// Wire-format for committed messages
+#[derive(Encode, Decode, scale_info::TypeInfo, codec::MaxEncodedLen, Derivative)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derivative(
+    Debug(bound = ""),
+    Clone(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[scale_info(skip_type_params(MaxPayload))]
+#[cfg_attr(feature = "std", serde(bound = ""))]
+pub struct Message<MaxPayload: Get<u32>> {
+    /// Target application on the TON side.
+    pub target: TonAddress,
+    /// Payload for target application.
+    pub payload: BoundedVec<u8, MaxPayload>,
+}
+
+impl<MaxPayload: Get<u32>> Message<MaxPayload> {
+    /// SHA-256 of `workchain (1 byte) ++ address (32 bytes) ++ payload`.
+    pub fn hash(&self) -> H256 {
+        let mut input = Vec::with_capacity(33 + self.payload.len());
+        input.extend_from_slice(&self.target.workchain.to_be_bytes());
+        input.extend_from_slice(self.target.address.as_bytes());
+        input.extend_from_slice(&self.payload);
+        H256(sp_io::hashing::sha2_256(&input))
+    }
+}
+
+/// Wire-format for commitment
+#[derive(Encode, Decode, scale_info::TypeInfo, codec::MaxEncodedLen, Derivative)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derivative(
+    Debug(bound = ""),
+    Clone(bound = ""),
+    PartialEq(bound = ""),
+    Eq(bound = "")
+)]
+#[scale_info(skip_type_params(MaxMessages, MaxPayload))]
+#[cfg_attr(feature = "std", serde(bound = ""))]
+pub struct OutboundCommitment<MaxMessages: Get<u32>, MaxPayload: Get<u32>> {
+    /// A batch nonce for replay protection and ordering.
+    pub nonce: u64,
+    /// Messages passed through the channel in the current commit.
+    pub messages: BoundedVec<Message<MaxPayload>, MaxMessages>,
+}
+
+impl<MaxMessages: Get<u32>, MaxPayload: Get<u32>> OutboundCommitment<MaxMessages, MaxPayload> {
+    /// SHA-256 of `nonce (8 bytes, big-endian) ++ message hashes`.
+    ///
+    /// TON contracts have native SHA-256 and use big-endian integers, so the channel
+    /// contract can rebuild this hash from the batch cells it receives.
+    pub fn hash(&self) -> H256 {
+        let mut input = Vec::with_capacity(8 + 32 * self.messages.len());
+        input.extend_from_slice(&self.nonce.to_be_bytes());
+        for message in self.messages.iter() {
+            input.extend_from_slice(message.hash().as_bytes());
+        }
+        H256(sp_io::hashing::sha2_256(&input))
+    }
+}
+
+#[test]
+fn test_outbound_commitment_hash() {
+    use hex_literal::hex;
+
+    pub type MaxU32 = sp_runtime::traits::ConstU32<{ u32::MAX }>;
+
+    let message: Message<MaxU32> = Message {
+        target: TonAddress::new(0, H256::repeat_byte(1)),
+        payload: vec![1, 2, 3].try_into().unwrap(),
+    };
+    assert_eq!(
+        message.hash(),
+        H256::from(hex!(
+            "660ca66b9825fcc981b61478f2b6d019e2682bd1cd559495ef3ad825d2453829"
+        ))
+    );
+
+    let commitment: OutboundCommitment<MaxU32, MaxU32> = OutboundCommitment {
+        nonce: 1,
+        messages: vec![message].try_into().unwrap(),
+    };
+    assert_eq!(
+        commitment.hash(),
+        H256::from(hex!(
+            "f1da327082ea0c26ba8ddb5ae58c42856adb0262b324dde3a91703b296b81b69"
+        ))
+    );
+}
@@ -41,7 +41,7 @@ use crate::GenericTimepoint;
 use crate::H256;
 use crate::U256;
 use crate::{
-    types::{BridgeAppInfo, BridgeAssetInfo, MessageStatus, RawAssetInfo},
+    types::{BridgeAppInfo, BridgeAssetInfo, MessageNonce, MessageStatus, RawAssetInfo},
     GenericAccount, GenericNetworkId,
 };
 use codec::FullCodec;
@@ -69,9 +69,10 @@ pub trait Verifier {
     /// The weight of the message verification function
     fn verify_weight(proof: &Self::Proof) -> Weight;
 
-    /// Valid proof for this Verifier, used for benchmarking
+    /// Valid proof of the commitment for this Verifier, used for benchmarking.
+    /// Verifier state needed to accept the proof is initialized as well.
     #[cfg(feature = "runtime-benchmarks")]
-    fn valid_proof() -> Option<Self::Proof>;
+    fn valid_proof(network_id: GenericNetworkId, commitment_hash: H256) -> Option<Self::Proof>;
 }
 
 /// Outbound submission for applications
@@ -90,22 +91,72 @@ pub trait EVMOutboundChannel {
     fn submit_gas(chain_id: EVMChainId) -> Result<U256, DispatchError>;
 }
 
+/// Withdraw messages which are queued but not committed yet
+pub trait OutboundChannelCancel<NetworkId, Additional> {
+    /// Cancel the queued message and return its payload and additional data.
+    fn cancel(
+        network_id: NetworkId,
+        message_id: H256,
+    ) -> Result<(Vec<u8>, Additional), DispatchError>;
+
+    fn cancel_weight() -> Weight;
+}
+
+/// Messages which were cancelled before commit, but still occupy their position in the batch.
+pub trait CancelledMessagesProvider {
+    /// Take positions of the messages cancelled in the batch with `batch_nonce`,
+    /// status reports for these messages should be ignored.
+    fn take_cancelled_messages(network_id: GenericNetworkId, batch_nonce: u64)
+        -> Vec<MessageNonce>;
+}
+
+impl CancelledMessagesProvider for () {
+    fn take_cancelled_messages(
+        _network_id: GenericNetworkId,
+        _batch_nonce: u64,
+    ) -> Vec<MessageNonce> {
+        Vec::new()
+    }
+}
+
 /// Dispatch a message
 pub trait MessageDispatch<T: Config, NetworkId, MessageId, Additional> {
+    /// Dispatch the message and return the actual weight consumed.
     fn dispatch(
         network_id: NetworkId,
         id: MessageId,
         timepoint: GenericTimepoint,
         payload: &[u8],
         additional: Additional,
-    );
+    ) -> Weight;
 
+    /// The worst case weight of the message dispatch.
     fn dispatch_weight(payload: &[u8]) -> Weight;
 
     #[cfg(feature = "runtime-benchmarks")]
     fn successful_dispatch_event(id: MessageId) -> Option<<T as Config>::RuntimeEvent>;
 }
 
+/// Report the inbound message call failure back to the source network
+pub trait DispatchFailureReporter<NetworkId, Call> {
+    /// Report the call of the message with given hash which has been rejected or has failed
+    /// and will not be dispatched again. Returns `true` if the report has been sent.
+    fn report_failure(network_id: NetworkId, message_hash: H256, call: &Call) -> bool;
+
+    /// The worst case weight of the failure report.
+    fn report_weight() -> Weight;
+}
+
+impl<NetworkId, Call> DispatchFailureReporter<NetworkId, Call> for () {
+    fn report_failure(_network_id: NetworkId, _message_hash: H256, _call: &Call) -> bool {
+        false
+    }
+
+    fn report_weight() -> Weight {
+        Weight::zero()
+    }
+}
+
 pub trait AppRegistry<NetworkId, Source> {
     fn register_app(network_id: NetworkId, app: Source) -> DispatchResult;
     fn deregister_app(network_id: NetworkId, app: Source) -> DispatchResult;
@@ -247,6 +298,15 @@ pub trait MessageStatusNotifier<AssetId, AccountId, Balance> {
         amount: Balance,
         status: MessageStatus,
     );
+
+    /// Sender, asset and amount of the outbound request recorded by `outbound_request`,
+    /// `None` if the implementation doesn't track them.
+    fn outbound_request_info(
+        _network_id: GenericNetworkId,
+        _message_id: H256,
+    ) -> Option<(AccountId, AssetId, Balance)> {
+        None
+    }
 }
 
 impl<AssetId, AccountId, Balance> MessageStatusNotifier<AssetId, AccountId, Balance> for () {
@@ -321,7 +381,7 @@ impl<AssetId> EVMFeeHandler<AssetId> for () {
 /// Trait that every origin (like Ethereum origin or Parachain origin) should implement
 pub trait BridgeOriginOutput: Sized {
     /// The Id of the network (i.e. Ethereum network id).
-    type NetworkId: Default;
+    type NetworkId: Default + Parameter;
 
     /// The additional data for origin.
     type Additional: Default;
@@ -334,6 +394,9 @@ pub trait BridgeOriginOutput: Sized {
         additional: Self::Additional,
     ) -> Self;
 
+    /// The Id of the network the message came from.
+    fn network_id(&self) -> Self::NetworkId;
+
     #[allow(clippy::result_unit_err)]
     #[cfg(feature = "runtime-benchmarks")]
     fn try_successful_origin() -> Result<Self, ()>;
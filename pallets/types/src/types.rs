@@ -36,13 +36,14 @@ use crate::ton::{AdditionalTONInboundData, TonAppInfo, TonAssetInfo};
 use crate::{GenericTimepoint, H256};
 use codec::{Decode, Encode};
 use derivative::Derivative;
+use frame_support::weights::Weight;
 use frame_support::RuntimeDebug;
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_beefy::mmr::{BeefyNextAuthoritySet, MmrLeafVersion};
 use sp_core::Get;
 use sp_runtime::traits::Hash;
-use sp_runtime::{Digest, DigestItem};
+use sp_runtime::{Digest, DigestItem, DispatchError};
 use sp_std::vec::Vec;
 
 use crate::GenericNetworkId;
@@ -291,8 +292,8 @@ pub struct CallOriginOutput<NetworkId, MessageId, Additional> {
     pub additional: Additional,
 }
 
-impl<NetworkId: Default, Additional: Default> crate::traits::BridgeOriginOutput
-    for CallOriginOutput<NetworkId, H256, Additional>
+impl<NetworkId: Default + frame_support::Parameter, Additional: Default>
+    crate::traits::BridgeOriginOutput for CallOriginOutput<NetworkId, H256, Additional>
 {
     type NetworkId = NetworkId;
     type Additional = Additional;
@@ -311,6 +312,10 @@ impl<NetworkId: Default, Additional: Default> crate::traits::BridgeOriginOutput
         }
     }
 
+    fn network_id(&self) -> NetworkId {
+        self.network_id.clone()
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn try_successful_origin() -> Result<Self, ()> {
         Ok(Self {
@@ -349,6 +354,37 @@ pub struct GenericCommitmentWithBlock<BlockNumber, MaxMessages: Get<u32>, MaxPay
     pub commitment: crate::GenericCommitment<MaxMessages, MaxPayload>,
 }
 
+/// Outcome of the inbound message dispatch.
+#[derive(Clone, RuntimeDebug, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum DispatchStatus {
+    /// Call was dispatched successfully.
+    Success,
+    /// Call was dispatched, but returned an error.
+    Failed(DispatchError),
+    /// Call was rejected by the call filter.
+    Rejected,
+    /// Failed to decode a call from the message payload.
+    DecodeFailed,
+    /// Call is waiting in the deferred queue.
+    Deferred,
+    /// Deferred call was not dispatched in time.
+    Expired,
+}
+
+/// Stored result of the inbound message dispatch.
+#[derive(Clone, RuntimeDebug, Encode, Decode, PartialEq, Eq, scale_info::TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DispatchRecord<BlockNumber> {
+    /// Block in which the message was dispatched.
+    pub block_number: BlockNumber,
+    pub status: DispatchStatus,
+    /// Actual weight used by the dispatch.
+    pub weight: Weight,
+}
+
 #[derive(
     Clone,
     Copy,